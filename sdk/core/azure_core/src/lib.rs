@@ -48,6 +48,8 @@ pub mod error {
     pub use typespec::error::*;
     pub use typespec_client_core::error::*;
 }
+#[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+pub use typespec_client_core::http::new_reqwest_client_with_options;
 #[cfg(feature = "xml")]
 pub use typespec_client_core::xml;
 pub use typespec_client_core::{
@@ -55,9 +57,9 @@ pub use typespec_client_core::{
     http::{
         headers::Header,
         new_http_client,
-        response::{Model, PinnedStream, Response, ResponseBody},
-        AppendToUrlQuery, Body, Context, HttpClient, Method, Pager, Request, RequestContent,
-        StatusCode, Url,
+        response::{status_from_raw, Model, PinnedStream, Response, ResponseBody},
+        AppendToUrlQuery, Body, CancellationToken, Context, HttpClient, Method, Pager, Request,
+        RequestContent, StatusCode, Url,
     },
     json, parsing,
     sleep::{self, sleep},
@@ -5,6 +5,10 @@ use crate::headers::Headers;
 use std::time::Duration;
 use typespec_client_core::date::OffsetDateTime;
 
+mod poller;
+
+pub use poller::*;
+
 /// Default retry time for long running operations if no retry-after header is present
 ///
 /// This value is the same as the default used in the Azure SDK for Python.
@@ -52,7 +56,8 @@ pub mod location {
         Url,
     };
 
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
     pub enum FinalState {
         AzureAsyncOperation,
         Location,
@@ -0,0 +1,711 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    base64,
+    error::{Error, ErrorKind, ResultExt},
+    headers::{Headers, AZURE_ASYNCOPERATION, LOCATION, OPERATION_LOCATION},
+    json::{from_json, to_json},
+    lro::{get_retry_after, location::get_provisioning_state, location::FinalState, LroStatus},
+    sleep, Context, Method, Pipeline, Request, Response, StatusCode, Url,
+};
+use futures::{stream::unfold, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// The status of a long-running operation, as yielded by a [`Poller`].
+pub enum PollerStatus<T> {
+    /// The operation has not finished yet.
+    ///
+    /// `response` is the raw response to the latest status request.
+    InProgress {
+        status: LroStatus,
+        response: Response,
+    },
+
+    /// The operation succeeded, and `response` contains the final resource or operation result.
+    Succeeded(Response<T>),
+}
+
+impl<T> std::fmt::Debug for PollerStatus<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PollerStatus::InProgress { status, .. } => f
+                .debug_struct("InProgress")
+                .field("status", status)
+                .finish_non_exhaustive(),
+            PollerStatus::Succeeded(_) => f.debug_struct("Succeeded").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Drives a long-running operation (LRO) to completion.
+///
+/// A `Poller` is a [`Stream`] that yields a [`PollerStatus::InProgress`] for each status request that finds the operation still running,
+/// and ends with a single [`PollerStatus::Succeeded`] carrying the final response.
+/// If the operation fails or is canceled, the stream yields an error instead.
+/// Use [`Poller::wait`] when you are only interested in the final response.
+///
+/// The `Azure-AsyncOperation`, `Operation-Location` and `Location` protocols are supported.
+/// The [`FinalState`] passed to [`Poller::from_response`] controls which header is polled and where the final resource is read from.
+/// Between requests, the poller waits for the duration in the `retry-after` headers of the latest response.
+///
+/// Ref: <https://github.com/microsoft/api-guidelines/blob/vNext/azure/Guidelines.md#long-running-operations--jobs>
+#[pin_project::pin_project]
+pub struct Poller<T> {
+    #[pin]
+    #[cfg(not(target_arch = "wasm32"))]
+    stream: Pin<Box<dyn Stream<Item = crate::Result<PollerStatus<T>>> + Send>>,
+
+    #[pin]
+    #[cfg(target_arch = "wasm32")]
+    stream: Pin<Box<dyn Stream<Item = crate::Result<PollerStatus<T>>>>>,
+
+    state: Arc<Mutex<Option<PollerState>>>,
+}
+
+impl<T: Send + 'static> Poller<T> {
+    /// Creates a [`Poller<T>`] from the initial response of a long-running operation.
+    ///
+    /// `request` is the request that started the operation; its method and URL are used to find the final resource.
+    /// If `response` carries none of the polling headers, the operation is treated as already complete
+    /// and the poller yields `response` as the final result.
+    pub fn from_response(
+        pipeline: Pipeline,
+        ctx: Context<'_>,
+        request: &Request,
+        response: Response<T>,
+        final_state: FinalState,
+    ) -> crate::Result<Self> {
+        let state = PollerState::from_initial_response(
+            request.method(),
+            request.url(),
+            response.headers(),
+            final_state,
+        )?;
+        Ok(match state {
+            Some(state) => {
+                let retry_after = get_retry_after(response.headers());
+                Self::new(pipeline, ctx, state, retry_after)
+            }
+            None => Self {
+                stream: Box::pin(futures::stream::once(async move {
+                    Ok(PollerStatus::Succeeded(response))
+                })),
+                state: Arc::new(Mutex::new(None)),
+            },
+        })
+    }
+
+    /// Creates a [`Poller<T>`] that resumes polling an operation, given a token returned by [`Poller::continuation_token`].
+    ///
+    /// The first status request is sent immediately.
+    pub fn from_continuation_token(
+        pipeline: Pipeline,
+        ctx: Context<'_>,
+        continuation_token: &str,
+    ) -> crate::Result<Self> {
+        let state: PollerState = base64::decode(continuation_token)
+            .and_then(from_json)
+            .context(
+                ErrorKind::DataConversion,
+                "invalid poller continuation token",
+            )?;
+        Ok(Self::new(pipeline, ctx, state, Duration::ZERO))
+    }
+
+    fn new(pipeline: Pipeline, ctx: Context<'_>, state: PollerState, delay: Duration) -> Self {
+        let state = Arc::new(Mutex::new(Some(state)));
+        let ctx = ctx.into_owned();
+        let stream = unfold(
+            (Step::Poll(delay), pipeline, ctx, state.clone()),
+            |(step, pipeline, ctx, shared)| async move {
+                let Step::Poll(delay) = step else {
+                    return None;
+                };
+                let mut current = shared.lock().expect("poller state lock poisoned").clone()?;
+
+                sleep(delay).await;
+                let (item, next) = match poll(&pipeline, &ctx, &mut current).await {
+                    Ok(Progress::InProgress {
+                        status,
+                        response,
+                        retry_after,
+                    }) => {
+                        *shared.lock().expect("poller state lock poisoned") = Some(current);
+                        (
+                            Ok(PollerStatus::InProgress { status, response }),
+                            Step::Poll(retry_after),
+                        )
+                    }
+                    Ok(Progress::Succeeded(response)) => {
+                        *shared.lock().expect("poller state lock poisoned") = None;
+                        (Ok(PollerStatus::Succeeded(response)), Step::Done)
+                    }
+                    // The token still points at the last known state so the caller may resume after a transient failure.
+                    Err(error) => (Err(error), Step::Done),
+                };
+                Some((item, (next, pipeline, ctx, shared)))
+            },
+        );
+        Self {
+            stream: Box::pin(stream),
+            state,
+        }
+    }
+}
+
+impl<T> Poller<T> {
+    /// Returns a token that can be passed to [`Poller::from_continuation_token`] to resume polling later, possibly in another process.
+    ///
+    /// Returns `None` once the operation has completed.
+    pub fn continuation_token(&self) -> Option<String> {
+        let state = self.state.lock().expect("poller state lock poisoned");
+        state
+            .as_ref()
+            .and_then(|state| to_json(state).ok())
+            .map(base64::encode)
+    }
+
+    /// Polls the operation until it completes, returning the final response.
+    pub async fn wait(mut self) -> crate::Result<Response<T>> {
+        while let Some(status) = self.next().await {
+            if let PollerStatus::Succeeded(response) = status? {
+                return Ok(response);
+            }
+        }
+        Err(Error::message(
+            ErrorKind::Other,
+            "the poller ended before the operation completed",
+        ))
+    }
+}
+
+impl<T> futures::Stream for Poller<T> {
+    type Item = crate::Result<PollerStatus<T>>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.project().stream.poll_next(cx)
+    }
+}
+
+impl<T> std::fmt::Debug for Poller<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
+
+enum Step {
+    Poll(Duration),
+    Done,
+}
+
+enum Progress<T> {
+    InProgress {
+        status: LroStatus,
+        response: Response,
+        retry_after: Duration,
+    },
+    Succeeded(Response<T>),
+}
+
+/// How the status of the operation is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Protocol {
+    /// The polling URL returns a status monitor with a `status` property.
+    StatusMonitor,
+    /// The polling URL returns `202 Accepted` until the operation completes, then the final result.
+    Location,
+}
+
+/// Everything needed to resume polling, serialized into the continuation token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PollerState {
+    method: String,
+    resource_url: String,
+    final_state: FinalState,
+    protocol: Protocol,
+    polling_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+}
+
+impl PollerState {
+    fn from_initial_response(
+        method: &Method,
+        resource_url: &Url,
+        headers: &Headers,
+        final_state: FinalState,
+    ) -> crate::Result<Option<Self>> {
+        let operation_location: Option<Url> = headers.get_optional_as(&OPERATION_LOCATION)?;
+        let azure_async_operation: Option<Url> = headers.get_optional_as(&AZURE_ASYNCOPERATION)?;
+        let location: Option<Url> = headers.get_optional_as(&LOCATION)?;
+
+        let status_monitor = match final_state {
+            FinalState::AzureAsyncOperation => azure_async_operation.or(operation_location),
+            FinalState::Location | FinalState::OperationLocation => {
+                operation_location.or(azure_async_operation)
+            }
+        };
+        let (protocol, polling_url) = match (status_monitor, &location) {
+            (Some(url), _) => (Protocol::StatusMonitor, url),
+            (None, Some(url)) => (Protocol::Location, url.clone()),
+            (None, None) => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            method: method.to_string(),
+            resource_url: resource_url.to_string(),
+            final_state,
+            protocol,
+            polling_url: polling_url.to_string(),
+            location: location.map(String::from),
+        }))
+    }
+
+    /// Returns the URL of the final resource once a status monitor reports success,
+    /// or `None` if the status monitor response is itself the result.
+    fn final_url(&self, status_monitor: &[u8]) -> Option<String> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct StatusMonitor {
+            resource_location: Option<String>,
+        }
+
+        match self.method.as_str() {
+            "PUT" | "PATCH" => return Some(self.resource_url.clone()),
+            "DELETE" => return None,
+            _ => {}
+        }
+        match self.final_state {
+            FinalState::Location => self.location.clone(),
+            FinalState::OperationLocation => from_json::<_, StatusMonitor>(status_monitor)
+                .ok()
+                .and_then(|body| body.resource_location),
+            FinalState::AzureAsyncOperation => None,
+        }
+    }
+}
+
+/// Sends a single status request, following up with a request for the final resource when the operation succeeded.
+async fn poll<T>(
+    pipeline: &Pipeline,
+    ctx: &Context<'_>,
+    state: &mut PollerState,
+) -> crate::Result<Progress<T>> {
+    let mut request = Request::new(state.polling_url.parse()?, Method::Get);
+    let response: Response = pipeline.send(ctx, &mut request).await?;
+    let (status, headers, body) = response.deconstruct();
+    let body = body.collect().await?;
+    let retry_after = get_retry_after(&headers);
+
+    match state.protocol {
+        Protocol::Location if status == StatusCode::Accepted => {
+            if let Some(location) = headers.get_optional_string(&LOCATION) {
+                state.polling_url = location;
+            }
+            Ok(Progress::InProgress {
+                status: LroStatus::InProgress,
+                response: Response::from_bytes(status, headers, body),
+                retry_after,
+            })
+        }
+        Protocol::Location => Ok(Progress::Succeeded(Response::from_bytes(
+            status, headers, body,
+        ))),
+        Protocol::StatusMonitor => {
+            let lro_status = get_provisioning_state(&body).ok_or_else(|| {
+                Error::message(
+                    ErrorKind::DataConversion,
+                    "the status monitor response does not contain a status",
+                )
+            })?;
+            match lro_status {
+                LroStatus::Succeeded => match state.final_url(&body) {
+                    Some(url) => {
+                        let mut request = Request::new(url.parse()?, Method::Get);
+                        Ok(Progress::Succeeded(pipeline.send(ctx, &mut request).await?))
+                    }
+                    None => Ok(Progress::Succeeded(Response::from_bytes(
+                        status, headers, body,
+                    ))),
+                },
+                LroStatus::Failed | LroStatus::Canceled => {
+                    Err(operation_error(status, &lro_status, &body))
+                }
+                LroStatus::InProgress | LroStatus::Other(_) => Ok(Progress::InProgress {
+                    status: lro_status,
+                    response: Response::from_bytes(status, headers, body),
+                    retry_after,
+                }),
+            }
+        }
+    }
+}
+
+/// Builds the error for a failed or canceled operation from the `error` object in the status monitor, if any.
+fn operation_error(status: StatusCode, lro_status: &LroStatus, body: &[u8]) -> Error {
+    #[derive(Deserialize)]
+    struct ErrorDetail {
+        code: Option<String>,
+        message: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct StatusMonitor {
+        error: Option<ErrorDetail>,
+    }
+
+    let detail = from_json::<_, StatusMonitor>(body)
+        .ok()
+        .and_then(|body| body.error);
+    let (error_code, message) = match detail {
+        Some(detail) => (detail.code, detail.message),
+        None => (None, None),
+    };
+    Error::with_message(
+        ErrorKind::HttpResponse { status, error_code },
+        || match message {
+            Some(message) => format!("long-running operation {lro_status:?}: {message}"),
+            None => format!("long-running operation {lro_status:?}"),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        headers::HeaderName, ClientOptions, FixedRetryOptions, Policy, PolicyResult, RetryOptions,
+        TransportOptions,
+    };
+    use std::collections::VecDeque;
+
+    type CannedResponse = (StatusCode, Vec<(&'static str, &'static str)>, &'static str);
+
+    /// Replies to each request with the next canned response, recording the requested URLs.
+    #[derive(Debug)]
+    struct MockTransport {
+        responses: Mutex<VecDeque<CannedResponse>>,
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl MockTransport {
+        fn new(responses: Vec<CannedResponse>) -> Arc<Self> {
+            Arc::new(Self {
+                responses: Mutex::new(responses.into()),
+                requests: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for MockTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(request.url().to_string());
+            let (status, headers, body) = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("unexpected request");
+            Ok(Response::from_bytes(status, headers_from(headers), body))
+        }
+    }
+
+    fn headers_from(headers: Vec<(&'static str, &'static str)>) -> Headers {
+        let mut result = Headers::new();
+        for (name, value) in headers {
+            result.insert(HeaderName::from_static(name), value);
+        }
+        result
+    }
+
+    fn pipeline(transport: Arc<MockTransport>) -> Pipeline {
+        let mut options = ClientOptions::default();
+        options.set_transport(TransportOptions::new_custom_policy(transport));
+        options.set_retry(RetryOptions::fixed(
+            FixedRetryOptions::default().delay(Duration::from_millis(1)),
+        ));
+        Pipeline::new(None, None, options, Vec::new(), Vec::new())
+    }
+
+    fn initial_response(
+        method: Method,
+        headers: Vec<(&'static str, &'static str)>,
+    ) -> (Request, Response) {
+        let request = Request::new(
+            "https://example.com/widgets/1?api-version=1"
+                .parse()
+                .unwrap(),
+            method,
+        );
+        let response = Response::from_bytes(StatusCode::Accepted, headers_from(headers), "");
+        (request, response)
+    }
+
+    #[tokio::test]
+    pub async fn azure_async_operation_put_reads_resource() {
+        let transport = MockTransport::new(vec![
+            (
+                StatusCode::Ok,
+                vec![("retry-after-ms", "0")],
+                r#"{"status":"InProgress"}"#,
+            ),
+            (StatusCode::Ok, vec![], r#"{"status":"Succeeded"}"#),
+            (StatusCode::Ok, vec![], r#"{"name":"widget"}"#),
+        ]);
+        let (request, response) = initial_response(
+            Method::Put,
+            vec![
+                ("azure-asyncoperation", "https://example.com/operations/1"),
+                ("retry-after-ms", "0"),
+            ],
+        );
+        let poller = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            response,
+            FinalState::AzureAsyncOperation,
+        )
+        .unwrap();
+
+        let statuses: Vec<_> = poller.collect().await;
+        assert_eq!(statuses.len(), 2);
+        assert!(matches!(
+            statuses[0],
+            Ok(PollerStatus::InProgress {
+                status: LroStatus::InProgress,
+                ..
+            })
+        ));
+        let Some(Ok(PollerStatus::Succeeded(response))) = statuses.into_iter().nth(1) else {
+            panic!("expected the operation to succeed");
+        };
+        assert_eq!(
+            response.into_body().collect_string().await.unwrap(),
+            r#"{"name":"widget"}"#
+        );
+        assert_eq!(
+            *transport.requests.lock().unwrap(),
+            vec![
+                "https://example.com/operations/1",
+                "https://example.com/operations/1",
+                "https://example.com/widgets/1?api-version=1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    pub async fn operation_location_post_follows_resource_location() {
+        let transport = MockTransport::new(vec![
+            (
+                StatusCode::Ok,
+                vec![],
+                r#"{"status":"Succeeded","resourceLocation":"https://example.com/results/1"}"#,
+            ),
+            (StatusCode::Ok, vec![], r#"{"result":42}"#),
+        ]);
+        let (request, response) = initial_response(
+            Method::Post,
+            vec![
+                ("operation-location", "https://example.com/operations/1"),
+                ("retry-after-ms", "0"),
+            ],
+        );
+        let response = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            response,
+            FinalState::OperationLocation,
+        )
+        .unwrap()
+        .wait()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            response.into_body().collect_string().await.unwrap(),
+            r#"{"result":42}"#
+        );
+        assert_eq!(
+            transport.requests.lock().unwrap().last().unwrap(),
+            "https://example.com/results/1"
+        );
+    }
+
+    #[tokio::test]
+    pub async fn location_polls_until_not_accepted() {
+        let transport = MockTransport::new(vec![
+            (
+                StatusCode::Accepted,
+                vec![
+                    ("location", "https://example.com/operations/2"),
+                    ("retry-after-ms", "0"),
+                ],
+                "",
+            ),
+            (StatusCode::Ok, vec![], r#"{"done":true}"#),
+        ]);
+        let (request, response) = initial_response(
+            Method::Post,
+            vec![
+                ("location", "https://example.com/operations/1"),
+                ("retry-after-ms", "0"),
+            ],
+        );
+        let response = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            response,
+            FinalState::Location,
+        )
+        .unwrap()
+        .wait()
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+        assert_eq!(
+            *transport.requests.lock().unwrap(),
+            vec![
+                "https://example.com/operations/1",
+                "https://example.com/operations/2",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    pub async fn failed_operation_is_an_error() {
+        let transport = MockTransport::new(vec![(
+            StatusCode::Ok,
+            vec![],
+            r#"{"status":"Failed","error":{"code":"Conflict","message":"widget is locked"}}"#,
+        )]);
+        let (request, response) = initial_response(
+            Method::Delete,
+            vec![
+                ("azure-asyncoperation", "https://example.com/operations/1"),
+                ("retry-after-ms", "0"),
+            ],
+        );
+        let err = Poller::from_response(
+            pipeline(transport),
+            Context::new(),
+            &request,
+            response,
+            FinalState::AzureAsyncOperation,
+        )
+        .unwrap()
+        .wait()
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::HttpResponse { error_code: Some(code), .. } if code == "Conflict"
+        ));
+    }
+
+    #[tokio::test]
+    pub async fn without_polling_headers_the_initial_response_is_final() {
+        let transport = MockTransport::new(vec![]);
+        let (request, response) = initial_response(Method::Put, vec![]);
+        let poller = Poller::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            response,
+            FinalState::AzureAsyncOperation,
+        )
+        .unwrap();
+
+        assert!(poller.continuation_token().is_none());
+        poller.wait().await.unwrap();
+        assert!(transport.requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn resume_from_continuation_token() {
+        let transport = MockTransport::new(vec![
+            (
+                StatusCode::Ok,
+                vec![("retry-after-ms", "0")],
+                r#"{"status":"Running"}"#,
+            ),
+            (StatusCode::Ok, vec![], r#"{"status":"Succeeded"}"#),
+            (StatusCode::Ok, vec![], r#"{"name":"widget"}"#),
+        ]);
+        let (request, response) = initial_response(
+            Method::Patch,
+            vec![
+                ("operation-location", "https://example.com/operations/1"),
+                ("retry-after-ms", "0"),
+            ],
+        );
+        let mut poller = Poller::<()>::from_response(
+            pipeline(transport.clone()),
+            Context::new(),
+            &request,
+            response,
+            FinalState::OperationLocation,
+        )
+        .unwrap();
+
+        let status = poller.next().await.unwrap().unwrap();
+        assert!(matches!(
+            status,
+            PollerStatus::InProgress { status: LroStatus::Other(ref s), .. } if s == "Running"
+        ));
+        let token = poller.continuation_token().unwrap();
+        drop(poller);
+
+        let resumed = Poller::<()>::from_continuation_token(
+            pipeline(transport.clone()),
+            Context::new(),
+            &token,
+        )
+        .unwrap();
+        let response = resumed.wait().await.unwrap();
+        assert_eq!(
+            response.into_body().collect_string().await.unwrap(),
+            r#"{"name":"widget"}"#
+        );
+        assert_eq!(
+            transport.requests.lock().unwrap().last().unwrap(),
+            "https://example.com/widgets/1?api-version=1"
+        );
+    }
+
+    #[test]
+    pub fn invalid_continuation_token() {
+        let err = Poller::<()>::from_continuation_token(
+            pipeline(MockTransport::new(vec![])),
+            Context::new(),
+            "not a token",
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+    }
+}
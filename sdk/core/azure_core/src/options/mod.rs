@@ -6,5 +6,9 @@ pub use telemetry::*;
 
 pub use typespec_client_core::http::{
     builders, AsClientMethodOptions, AsClientOptions, ClientMethodOptions, ClientOptions,
-    ExponentialRetryOptions, FixedRetryOptions, RetryOptions, TransportOptions,
+    ExponentialRetryOptions, FixedRetryOptions, LoggingOptions, ProxyCredentials, ProxyOptions,
+    RetryOptions, TransportOptions,
 };
+
+#[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+pub use typespec_client_core::http::ReqwestClientOptions;
@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::{TelemetryOptions, TelemetryPolicy};
+use crate::{policies::DistributedTracingPolicy, TelemetryOptions, TelemetryPolicy};
 use std::{ops::Deref, sync::Arc};
 use typespec_client_core::http::{self, policies::Policy};
 
@@ -12,7 +12,7 @@ use typespec_client_core::http::{self, policies::Policy};
 /// 1. Client library-specified per-call policies are executed. Per-call policies can fail and bail out of the pipeline
 ///    immediately.
 /// 2. User-specified per-call policies are executed.
-/// 3. Telemetry policy.
+/// 3. Telemetry policy, followed by the distributed tracing policy.
 /// 4. Retry policy. It allows to re-execute the following policies.
 /// 5. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are re-executed
 ///    in case of retries.
@@ -42,6 +42,26 @@ impl Pipeline {
         options: http::ClientOptions,
         per_call_policies: Vec<Arc<dyn Policy>>,
         per_retry_policies: Vec<Arc<dyn Policy>>,
+    ) -> Self {
+        Self::new_with_namespace(
+            None,
+            crate_name,
+            crate_version,
+            options,
+            per_call_policies,
+            per_retry_policies,
+        )
+    }
+
+    /// Creates a new pipeline like [`Pipeline::new()`], whose distributed tracing spans record `az_namespace`
+    /// as the namespace of the Azure resource provider the client calls, such as `Microsoft.Storage`.
+    pub fn new_with_namespace(
+        az_namespace: Option<&'static str>,
+        crate_name: Option<&'static str>,
+        crate_version: Option<&'static str>,
+        options: http::ClientOptions,
+        per_call_policies: Vec<Arc<dyn Policy>>,
+        per_retry_policies: Vec<Arc<dyn Policy>>,
     ) -> Self {
         let mut per_call_policies = per_call_policies.clone();
 
@@ -52,6 +72,7 @@ impl Pipeline {
             &TelemetryOptions::default(),
         );
         per_call_policies.insert(0, Arc::new(telemetry_policy));
+        per_call_policies.insert(1, Arc::new(DistributedTracingPolicy::new(az_namespace)));
 
         Self(http::Pipeline::new(
             options,
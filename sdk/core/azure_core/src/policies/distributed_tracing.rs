@@ -0,0 +1,591 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::headers::{CLIENT_REQUEST_ID, REQUEST_ID, TRACEPARENT};
+use crate::{Context, Request, Uuid};
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+use tracing::{
+    field::{self, Empty},
+    Instrument, Span,
+};
+use typespec::error::{Error, ErrorKind};
+use typespec_client_core::http::policies::{Policy, PolicyResult};
+
+/// A [W3C Trace Context](https://www.w3.org/TR/trace-context/) `traceparent`, identifying the operation that sent a request.
+///
+/// Add a `TraceParent` to the [`Context`] passed to a client method to make its requests part of an existing trace,
+/// such as the trace of the incoming request your service is handling.
+///
+/// # Examples
+///
+/// ```
+/// # use azure_core::{Context, TraceParent};
+/// let parent: TraceParent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".parse()?;
+/// let context = Context::new().with_value(parent);
+/// # Ok::<(), azure_core::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceParent {
+    trace_id: u128,
+    parent_id: u64,
+    flags: u8,
+}
+
+/// The only `traceparent` version defined by the W3C Trace Context specification.
+const VERSION: &str = "00";
+
+/// The flag indicating the caller may have recorded the trace.
+const SAMPLED: u8 = 0x01;
+
+impl TraceParent {
+    /// Creates a `TraceParent` that starts a new, sampled trace.
+    pub fn new() -> Self {
+        Self {
+            trace_id: non_zero(Uuid::new_v4().as_u128()),
+            parent_id: new_parent_id(),
+            flags: SAMPLED,
+        }
+    }
+
+    /// Creates a `TraceParent` from the IDs of an existing trace and operation, such as an OpenTelemetry span context.
+    ///
+    /// Returns an error if either ID is all zeros, which the W3C Trace Context specification defines as invalid.
+    pub fn from_ids(trace_id: u128, parent_id: u64, sampled: bool) -> crate::Result<Self> {
+        if trace_id == 0 || parent_id == 0 {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid traceparent IDs: {trace_id:032x}-{parent_id:016x}")
+            }));
+        }
+
+        Ok(Self {
+            trace_id,
+            parent_id,
+            flags: if sampled { SAMPLED } else { 0 },
+        })
+    }
+
+    /// Creates a `TraceParent` for an operation within the same trace as this one.
+    pub fn child(&self) -> Self {
+        Self {
+            parent_id: new_parent_id(),
+            ..*self
+        }
+    }
+
+    /// Returns the ID of the whole trace, as 32 lowercase hexadecimal characters.
+    pub fn trace_id(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+
+    /// Returns the ID of the operation within the trace, as 16 lowercase hexadecimal characters.
+    pub fn parent_id(&self) -> String {
+        format!("{:016x}", self.parent_id)
+    }
+
+    /// Returns whether the caller may have recorded the trace.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & SAMPLED != 0
+    }
+}
+
+impl Default for TraceParent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{VERSION}-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+impl FromStr for TraceParent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let invalid = || {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid traceparent: {s}")
+            })
+        };
+        let parse_hex = |part: &str, len: usize| {
+            if part.len() == len && part.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+                u128::from_str_radix(part, 16).map_err(|_| invalid())
+            } else {
+                Err(invalid())
+            }
+        };
+
+        let parts: Vec<_> = s.trim().split('-').collect();
+        let [version, trace_id, parent_id, flags] = parts[..] else {
+            return Err(invalid());
+        };
+        if version != VERSION {
+            return Err(invalid());
+        }
+        let trace_id = parse_hex(trace_id, 32)?;
+        let parent_id = parse_hex(parent_id, 16)? as u64;
+        let flags = parse_hex(flags, 2)? as u8;
+        if trace_id == 0 || parent_id == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            trace_id,
+            parent_id,
+            flags,
+        })
+    }
+}
+
+fn new_parent_id() -> u64 {
+    non_zero(Uuid::new_v4().as_u128()) as u64
+}
+
+/// All-zero IDs are invalid, but vanishingly unlikely for a random UUID.
+fn non_zero(id: u128) -> u128 {
+    if id as u64 == 0 {
+        1
+    } else {
+        id
+    }
+}
+
+/// Reads and writes the trace context of [`tracing`] spans, such as the OpenTelemetry context attached by `tracing-opentelemetry`.
+///
+/// Without a `TracePropagator`, the [`DistributedTracingPolicy`] cannot see the trace of the caller's current span,
+/// so each request starts a new trace unless the [`Context`] contains a [`TraceParent`].
+/// Install one with [`set_trace_propagator()`] to make requests part of the caller's trace.
+pub trait TracePropagator: Send + Sync + fmt::Debug {
+    /// Returns the trace context `span` is exported with, or `None` if it has none.
+    fn trace_parent(&self, span: &Span) -> Option<TraceParent>;
+
+    /// Makes `span` a child of `parent` instead of the caller's current span.
+    fn set_parent(&self, span: &Span, parent: TraceParent);
+}
+
+static TRACE_PROPAGATOR: RwLock<Option<Arc<dyn TracePropagator>>> = RwLock::new(None);
+
+/// Sets the [`TracePropagator`] used by every [`DistributedTracingPolicy`] not created with its own.
+pub fn set_trace_propagator(propagator: Arc<dyn TracePropagator>) {
+    *TRACE_PROPAGATOR
+        .write()
+        .unwrap_or_else(|err| err.into_inner()) = Some(propagator);
+}
+
+fn trace_propagator() -> Option<Arc<dyn TracePropagator>> {
+    TRACE_PROPAGATOR
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+}
+
+/// Creates a [`tracing`] span for each client method call, and propagates it to the service in a `traceparent` header.
+///
+/// The span follows the OpenTelemetry semantic conventions for HTTP clients and Azure SDKs, so it can be exported
+/// with `tracing-opentelemetry`. It records `az.namespace`, `http.request.method`, `server.address`, `server.port`,
+/// `url.full` without its query, `http.response.status_code`, `az.client_request_id`, and `az.service_request_id`,
+/// which is the `x-ms-request-id` returned by the service.
+///
+/// The `traceparent` identifies the span using the [`TracePropagator`], if one is installed. Otherwise, the span
+/// records the `trace_id` and `span_id` sent in the `traceparent`.
+///
+/// Every pipeline created with [`Pipeline::new()`](crate::Pipeline::new()) runs this policy right after the [`TelemetryPolicy`](crate::TelemetryPolicy).
+#[derive(Clone, Debug, Default)]
+pub struct DistributedTracingPolicy {
+    az_namespace: Option<&'static str>,
+    propagator: Option<Arc<dyn TracePropagator>>,
+}
+
+impl DistributedTracingPolicy {
+    /// Creates a new `DistributedTracingPolicy`.
+    ///
+    /// `az_namespace` is the namespace of the Azure resource provider the client calls, such as `Microsoft.Storage`.
+    pub fn new(az_namespace: Option<&'static str>) -> Self {
+        Self {
+            az_namespace,
+            propagator: None,
+        }
+    }
+
+    /// Uses `propagator` instead of the one set with [`set_trace_propagator()`].
+    pub fn with_propagator(mut self, propagator: Arc<dyn TracePropagator>) -> Self {
+        self.propagator = Some(propagator);
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for DistributedTracingPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let url = request.url();
+        let mut url_full = url.clone();
+        url_full.set_query(None);
+        let _ = url_full.set_username("");
+        let _ = url_full.set_password(None);
+        let span = tracing::info_span!(
+            "HTTP",
+            otel.name = %request.method(),
+            otel.kind = "client",
+            otel.status_code = Empty,
+            az.namespace = self.az_namespace,
+            http.request.method = %request.method(),
+            server.address = url.host_str(),
+            server.port = url.port_or_known_default(),
+            url.full = %url_full,
+            trace_id = Empty,
+            span_id = Empty,
+            az.client_request_id = request.headers().get_optional_str(&CLIENT_REQUEST_ID),
+            http.response.status_code = Empty,
+            az.service_request_id = Empty,
+            error.type = Empty,
+        );
+
+        let parent = ctx.value::<TraceParent>().copied();
+        let propagated = self
+            .propagator
+            .clone()
+            .or_else(trace_propagator)
+            .and_then(|propagator| {
+                if let Some(parent) = parent {
+                    propagator.set_parent(&span, parent);
+                }
+                propagator.trace_parent(&span)
+            });
+        let traceparent = propagated.unwrap_or_else(|| {
+            parent
+                .as_ref()
+                .map_or_else(TraceParent::new, TraceParent::child)
+        });
+        span.record("trace_id", field::display(traceparent.trace_id()));
+        span.record("span_id", field::display(traceparent.parent_id()));
+
+        // Don't replace a traceparent the caller set explicitly.
+        if request.headers().get_optional_str(&TRACEPARENT).is_none() {
+            request.insert_header(TRACEPARENT, traceparent.to_string());
+        }
+
+        let result = next[0]
+            .send(ctx, request, &next[1..])
+            .instrument(span.clone())
+            .await;
+
+        match &result {
+            Ok(response) => {
+                let status = response.status();
+                span.record("http.response.status_code", u16::from(status));
+                if let Some(request_id) = response.headers().get_optional_str(&REQUEST_ID) {
+                    span.record("az.service_request_id", request_id);
+                }
+                if !status.is_success() {
+                    span.record("otel.status_code", "ERROR");
+                    span.record("error.type", u16::from(status));
+                }
+            }
+            Err(error) => {
+                span.record("otel.status_code", "ERROR");
+                match error.http_status() {
+                    Some(status) => {
+                        span.record("http.response.status_code", u16::from(status));
+                        span.record("error.type", u16::from(status));
+                    }
+                    None => {
+                        span.record("error.type", error.kind().to_string());
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{headers::Headers, Method, Response, StatusCode};
+    use std::sync::Mutex;
+    use tracing::{span, Subscriber};
+    use tracing_subscriber::{
+        layer::{self, SubscriberExt as _},
+        registry::LookupSpan,
+        Layer, Registry,
+    };
+    use typespec_client_core::test::TracingOutput;
+
+    #[test]
+    fn traceparent_roundtrip() {
+        let value = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+        let traceparent: TraceParent = value.parse().unwrap();
+        assert_eq!(traceparent.trace_id(), "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(traceparent.parent_id(), "b7ad6b7169203331");
+        assert_eq!(traceparent.to_string(), value);
+
+        let child = traceparent.child();
+        assert_eq!(child.trace_id(), traceparent.trace_id());
+        assert_ne!(child.parent_id(), traceparent.parent_id());
+        assert!(child.to_string().ends_with("-01"));
+    }
+
+    #[test]
+    fn traceparent_invalid() {
+        for value in [
+            "",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331",
+            "01-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01",
+            "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319c-0000000000000000-01",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b716920333-01",
+            "00-0af7651916cd43dd8448eb211c80319c-+7ad6b7169203331-01",
+        ] {
+            assert!(value.parse::<TraceParent>().is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn traceparent_from_ids() {
+        let traceparent = TraceParent::from_ids(
+            0x0af7651916cd43dd8448eb211c80319c,
+            0xb7ad6b7169203331,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            traceparent.to_string(),
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00"
+        );
+        assert!(!traceparent.is_sampled());
+        assert!(TraceParent::from_ids(0, 1, true).is_err());
+        assert!(TraceParent::from_ids(1, 0, true).is_err());
+    }
+
+    /// Gives every span a trace context, continuing its parent span's trace, like `tracing-opentelemetry`.
+    struct TraceContextLayer;
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for TraceContextLayer {
+        fn on_new_span(
+            &self,
+            _attrs: &span::Attributes<'_>,
+            id: &span::Id,
+            ctx: layer::Context<'_, S>,
+        ) {
+            let span = ctx.span(id).unwrap();
+            let traceparent = span
+                .parent()
+                .and_then(|parent| {
+                    parent
+                        .extensions()
+                        .get::<TraceParent>()
+                        .map(TraceParent::child)
+                })
+                .unwrap_or_default();
+            span.extensions_mut().insert(traceparent);
+        }
+    }
+
+    /// Reads and writes the trace context attached by the [`TraceContextLayer`].
+    #[derive(Debug)]
+    struct LayerPropagator;
+
+    impl TracePropagator for LayerPropagator {
+        fn trace_parent(&self, span: &Span) -> Option<TraceParent> {
+            span.with_subscriber(|(id, dispatch)| {
+                let span = dispatch.downcast_ref::<Registry>()?.span(id)?;
+                let traceparent = span.extensions().get::<TraceParent>().copied();
+                traceparent
+            })
+            .flatten()
+        }
+
+        fn set_parent(&self, span: &Span, parent: TraceParent) {
+            span.with_subscriber(|(id, dispatch)| {
+                if let Some(span) = dispatch.downcast_ref::<Registry>().and_then(|r| r.span(id)) {
+                    span.extensions_mut().replace(parent.child());
+                }
+            });
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockTransport {
+        traceparents: Mutex<Vec<String>>,
+        spans: Mutex<Vec<Option<TraceParent>>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for MockTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.traceparents
+                .lock()
+                .unwrap()
+                .push(request.headers().get_optional_string(&TRACEPARENT).unwrap());
+            self.spans
+                .lock()
+                .unwrap()
+                .push(LayerPropagator.trace_parent(&Span::current()));
+            let mut headers = Headers::new();
+            headers.insert(REQUEST_ID, "request-1");
+            Ok(Response::from_bytes(StatusCode::Ok, headers, "{}"))
+        }
+    }
+
+    #[tokio::test]
+    async fn propagates_traceparent() {
+        let transport = Arc::new(MockTransport::default());
+        let policies: Vec<Arc<dyn Policy>> = vec![
+            Arc::new(DistributedTracingPolicy::new(Some("Microsoft.Test"))),
+            transport.clone(),
+        ];
+        let send = |ctx: Context<'static>| {
+            let policies = policies.clone();
+            async move {
+                let mut request = Request::new(
+                    "https://contoso.com/path?sig=secret".parse().unwrap(),
+                    Method::Get,
+                );
+                policies[0]
+                    .send(&ctx, &mut request, &policies[1..])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        send(Context::new()).await;
+        let parent: TraceParent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+            .parse()
+            .unwrap();
+        send(Context::new().with_value(parent)).await;
+
+        let traceparents = transport.traceparents.lock().unwrap();
+        let new_trace: TraceParent = traceparents[0].parse().unwrap();
+        assert_ne!(new_trace.trace_id(), parent.trace_id());
+
+        let child: TraceParent = traceparents[1].parse().unwrap();
+        assert_eq!(child.trace_id(), parent.trace_id());
+        assert_ne!(child.parent_id(), parent.parent_id());
+    }
+
+    #[tokio::test]
+    async fn propagates_current_span_context() {
+        let _guard = tracing::subscriber::set_default(Registry::default().with(TraceContextLayer));
+
+        let transport = Arc::new(MockTransport::default());
+        let policies: Vec<Arc<dyn Policy>> = vec![
+            Arc::new(
+                DistributedTracingPolicy::new(Some("Microsoft.Test"))
+                    .with_propagator(Arc::new(LayerPropagator)),
+            ),
+            transport.clone(),
+        ];
+        let send = |ctx: Context<'static>| {
+            let policies = policies.clone();
+            async move {
+                let mut request =
+                    Request::new("https://contoso.com/path".parse().unwrap(), Method::Get);
+                policies[0]
+                    .send(&ctx, &mut request, &policies[1..])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let caller = tracing::info_span!("caller");
+        let caller_context = LayerPropagator.trace_parent(&caller).unwrap();
+        send(Context::new()).instrument(caller.clone()).await;
+        let parent: TraceParent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+            .parse()
+            .unwrap();
+        send(Context::new().with_value(parent))
+            .instrument(caller)
+            .await;
+
+        let traceparents = transport.traceparents.lock().unwrap();
+        let spans = transport.spans.lock().unwrap();
+
+        // The request joins the caller's trace, and its traceparent identifies the span the policy created.
+        let sent: TraceParent = traceparents[0].parse().unwrap();
+        assert_eq!(sent.trace_id(), caller_context.trace_id());
+        assert_ne!(sent.parent_id(), caller_context.parent_id());
+        assert_eq!(Some(sent), spans[0]);
+
+        // A TraceParent in the Context takes precedence over the caller's span.
+        let sent: TraceParent = traceparents[1].parse().unwrap();
+        assert_eq!(sent.trace_id(), parent.trace_id());
+        assert_ne!(sent.parent_id(), parent.parent_id());
+        assert_eq!(Some(sent), spans[1]);
+    }
+
+    #[tokio::test]
+    async fn records_span_attributes() {
+        let output = TracingOutput::default();
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let policies: Vec<Arc<dyn Policy>> = vec![
+            Arc::new(DistributedTracingPolicy::new(Some("Microsoft.Test"))),
+            Arc::new(MockTransport::default()),
+        ];
+        let mut request = Request::new(
+            "https://contoso.com/path?sig=secret".parse().unwrap(),
+            Method::Get,
+        );
+        policies[0]
+            .send(&Context::new(), &mut request, &policies[1..])
+            .await
+            .unwrap();
+
+        let output = output.contents();
+        let traceparent: TraceParent = request
+            .headers()
+            .get_optional_str(&TRACEPARENT)
+            .unwrap()
+            .parse()
+            .unwrap();
+        for attribute in [
+            format!("trace_id={}", traceparent.trace_id()),
+            format!("span_id={}", traceparent.parent_id()),
+        ] {
+            assert!(output.contains(&attribute), "missing {attribute}: {output}");
+        }
+        for attribute in [
+            "otel.name=GET",
+            "otel.kind=\"client\"",
+            "az.namespace=\"Microsoft.Test\"",
+            "http.request.method=GET",
+            "server.address=\"contoso.com\"",
+            "server.port=443",
+            "url.full=https://contoso.com/path",
+            "http.response.status_code=200",
+            "az.service_request_id=\"request-1\"",
+        ] {
+            assert!(output.contains(attribute), "missing {attribute}: {output}");
+        }
+        assert!(!output.contains("secret"), "{output}");
+    }
+}
@@ -2,9 +2,11 @@
 // Licensed under the MIT License.
 
 mod bearer_token_policy;
+mod distributed_tracing;
 mod telemetry;
 
 pub use bearer_token_policy::BearerTokenCredentialPolicy;
+pub use distributed_tracing::*;
 
 pub use telemetry::*;
 pub use typespec_client_core::http::policies::*;
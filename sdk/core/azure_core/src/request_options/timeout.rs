@@ -1,9 +1,23 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::{AppendToUrlQuery, Url};
+use crate::{policies::Deadline, AppendToUrlQuery, Url};
 use std::time::Duration;
 
+/// A timeout for an operation.
+///
+/// Appended to a request URL, it asks the service to stop processing the request after the timeout.
+/// To also stop waiting on the client, convert it to a [`Deadline`] and add that to the [`Context`](crate::Context)
+/// passed to the client method.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use azure_core::{request_options::Timeout, Context, Deadline};
+/// let timeout = Timeout::new(Duration::from_secs(30));
+/// let context = Context::new().with_value(Deadline::from(timeout));
+/// ```
 #[derive(Debug, Clone, Copy)]
 pub struct Timeout(Duration);
 
@@ -24,6 +38,13 @@ impl AppendToUrlQuery for Timeout {
     }
 }
 
+/// Creates a [`Deadline`] that is the duration of the `Timeout` from now.
+impl From<Timeout> for Deadline {
+    fn from(timeout: Timeout) -> Self {
+        Deadline::after(timeout.0)
+    }
+}
+
 impl From<Duration> for Timeout {
     fn from(d: Duration) -> Self {
         Self(d)
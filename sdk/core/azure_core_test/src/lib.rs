@@ -3,33 +3,210 @@
 
 #![doc = include_str!("../README.md")]
 
+pub mod recording;
+
 /// Live recording and playing back of client library tests.
 pub mod recorded {
     pub use azure_core_test_macros::test;
 }
 
 pub use azure_core::test::TestMode;
+use azure_core::{
+    error::{ErrorKind, ResultExt},
+    ClientOptions, TransportOptions, Uuid,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use recording::{Matcher, PlaybackPolicy, RecordingPolicy, Sanitizer, SessionFile};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// The name of the variable that records the seed of the random number generator.
+const RANDOM_SEED_VARIABLE: &str = "RandomSeed";
+
+/// The characters used in random names, which are valid in the names of most resources.
+const RANDOM_NAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
 
 /// Context information required by recorded client library tests.
 ///
 /// This context is required for any recorded tests not attributed as `#[recorded::test(live)]`
 /// to setup up the HTTP client to record or play back session records.
+///
+/// Clones share the same recorded variables and random number generator.
 #[derive(Clone, Debug)]
 pub struct TestContext {
+    crate_dir: &'static str,
+    module_path: &'static str,
     test_mode: TestMode,
     test_name: &'static str,
+    sanitizers: Vec<Sanitizer>,
+    matcher: Matcher,
+    session: Arc<SessionFile>,
+    random: Arc<Mutex<Option<ChaCha8Rng>>>,
 }
 
 impl TestContext {
     /// Not intended for use outside the `azure_core` crates.
     #[doc(hidden)]
-    pub fn new(test_mode: TestMode, test_name: &'static str) -> Self {
+    pub fn new(
+        crate_dir: &'static str,
+        module_path: &'static str,
+        test_mode: TestMode,
+        test_name: &'static str,
+    ) -> Self {
+        let path = recording_path(crate_dir, module_path, test_name);
         Self {
+            crate_dir,
+            module_path,
             test_mode,
             test_name,
+            sanitizers: Vec::new(),
+            matcher: Matcher::default(),
+            session: Arc::new(SessionFile::new(path)),
+            random: Arc::default(),
         }
     }
 
+    /// Adds a [`Sanitizer`] that scrubs secrets from this test's session recording.
+    ///
+    /// Sanitizers must be added before calling [`TestContext::client_options()`].
+    /// They can also be added using attributes like `#[recorded::test(sanitize_headers("x-ms-encryption-key"))]`.
+    pub fn add_sanitizer(&mut self, sanitizer: Sanitizer) {
+        self.sanitizers.push(sanitizer);
+    }
+
+    /// Sets the [`Matcher`] that decides whether a request matches a recorded request during playback.
+    ///
+    /// The matcher must be set before calling [`TestContext::client_options()`].
+    /// It can also be configured using attributes like `#[recorded::test(ignore_headers("x-ms-request-nonce"), json_body)]`.
+    pub fn set_matcher(&mut self, matcher: Matcher) {
+        self.matcher = matcher;
+    }
+
+    /// Gets the [`Matcher`] used during playback.
+    pub fn matcher(&self) -> &Matcher {
+        &self.matcher
+    }
+
+    /// Gets [`ClientOptions`] that play back or record this test's session, depending on the current [`TestMode`].
+    ///
+    /// * [`TestMode::Playback`] - Requests are answered from the session recording, and are not sent.
+    /// * [`TestMode::Record`] - Requests are sent, and they and their responses are recorded to the session recording.
+    /// * [`TestMode::Live`] - Requests are sent, and nothing is recorded.
+    ///
+    /// Fails in playback mode if the session recording does not exist.
+    pub fn client_options(&self) -> azure_core::Result<ClientOptions> {
+        let mut options = ClientOptions::default();
+        match self.test_mode {
+            TestMode::Playback => {
+                let policy = PlaybackPolicy::new(self.recording_path())?
+                    .with_sanitizers(self.sanitizers.clone())
+                    .with_matcher(self.matcher.clone());
+                options.set_transport(TransportOptions::new_custom_policy(Arc::new(policy)))
+            }
+            TestMode::Record => {
+                let policy = RecordingPolicy::with_session(
+                    self.session.clone(),
+                    azure_core::new_http_client(),
+                )
+                .with_sanitizers(self.sanitizers.clone());
+                options.set_transport(TransportOptions::new_custom_policy(Arc::new(policy)))
+            }
+            TestMode::Live => {}
+        }
+        Ok(options)
+    }
+
+    /// Gets the path of this test's session recording.
+    ///
+    /// Recordings are stored under the `tests/recordings` directory of the crate containing the test,
+    /// in a directory for each module and a file named after the test function: for example, `tests/recordings/secrets/get_secret.json`.
+    pub fn recording_path(&self) -> PathBuf {
+        recording_path(self.crate_dir, self.module_path, self.test_name)
+    }
+
+    /// Gets the value of the variable `name`, which is recorded so the test plays back with the same value.
+    ///
+    /// * [`TestMode::Playback`] - Returns the recorded value. `value` is not called.
+    /// * [`TestMode::Record`] - Calls `value`, then records and returns the result.
+    /// * [`TestMode::Live`] - Calls `value` and returns the result.
+    ///
+    /// Use variables for values that differ every time a test is recorded, like unique resource names,
+    /// timestamps, or values read from environment variables that are not set during playback.
+    /// Fails in playback mode if the variable was not recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use azure_core_test::{recorded, TestContext};
+    /// use azure_core::Result;
+    ///
+    /// #[recorded::test]
+    /// async fn create_database(ctx: TestContext) -> Result<()> {
+    ///     let endpoint = ctx.variable("endpoint", || std::env::var("ENDPOINT").unwrap())?;
+    ///     todo!()
+    /// }
+    /// ```
+    pub fn variable(
+        &self,
+        name: &str,
+        value: impl FnOnce() -> String,
+    ) -> azure_core::Result<String> {
+        match self.test_mode {
+            TestMode::Playback => self.session.recorded_variable(name),
+            TestMode::Record => {
+                let value = value();
+                self.session.record(|session| {
+                    session.variables.insert(name.to_owned(), value.clone());
+                })?;
+                Ok(value)
+            }
+            TestMode::Live => Ok(value()),
+        }
+    }
+
+    /// Gets a random `u64`.
+    ///
+    /// Random values are generated from a seed recorded with the session,
+    /// so the test plays back with the same sequence of values that was recorded.
+    pub fn random_u64(&self) -> azure_core::Result<u64> {
+        self.with_random(|random| random.gen())
+    }
+
+    /// Gets a random version 4 [`Uuid`].
+    ///
+    /// Random values are generated from a seed recorded with the session,
+    /// so the test plays back with the same sequence of values that was recorded.
+    pub fn random_uuid(&self) -> azure_core::Result<Uuid> {
+        self.with_random(|random| {
+            let mut bytes: [u8; 16] = random.gen();
+            // Set the version and variant bits, as in `Uuid::new_v4()`.
+            bytes[6] = (bytes[6] & 0x0f) | 0x40;
+            bytes[8] = (bytes[8] & 0x3f) | 0x80;
+            Uuid::from_bytes(bytes)
+        })
+    }
+
+    /// Gets a random name starting with `prefix` followed by `len` random lowercase letters and digits,
+    /// for example to name resources the test creates.
+    ///
+    /// Random values are generated from a seed recorded with the session,
+    /// so the test plays back with the same sequence of values that was recorded.
+    pub fn random_name(&self, prefix: &str, len: usize) -> azure_core::Result<String> {
+        self.with_random(|random| {
+            let mut name = String::with_capacity(prefix.len() + len);
+            name.push_str(prefix);
+            name.extend(
+                (0..len).map(|_| {
+                    RANDOM_NAME_CHARS[random.gen_range(0..RANDOM_NAME_CHARS.len())] as char
+                }),
+            );
+            name
+        })
+    }
+
     /// Gets the current [`TestMode`].
     pub fn test_mode(&self) -> TestMode {
         self.test_mode
@@ -39,6 +216,32 @@ impl TestContext {
     pub fn test_name(&self) -> &'static str {
         self.test_name
     }
+
+    /// Calls `f` with the random number generator, seeding it from the recorded seed if it is not already seeded.
+    fn with_random<T>(&self, f: impl FnOnce(&mut ChaCha8Rng) -> T) -> azure_core::Result<T> {
+        let mut random = self.random.lock().expect("random lock poisoned");
+        let random = match &mut *random {
+            Some(random) => random,
+            None => {
+                let seed =
+                    self.variable(RANDOM_SEED_VARIABLE, || rand::random::<u64>().to_string())?;
+                let seed = seed.parse().with_context(ErrorKind::MockFramework, || {
+                    format!("invalid recorded random seed '{seed}'")
+                })?;
+                random.insert(ChaCha8Rng::seed_from_u64(seed))
+            }
+        };
+        Ok(f(random))
+    }
+}
+
+fn recording_path(crate_dir: &str, module_path: &str, test_name: &str) -> PathBuf {
+    let mut path = PathBuf::from(crate_dir);
+    path.push("tests");
+    path.push("recordings");
+    path.extend(module_path.split("::"));
+    path.push(format!("{test_name}.json"));
+    path
 }
 
 #[cfg(test)]
@@ -47,8 +250,66 @@ mod tests {
 
     #[test]
     fn test_content_new() {
-        let ctx = TestContext::new(TestMode::default(), "test_content_new");
+        let ctx = TestContext::new(
+            env!("CARGO_MANIFEST_DIR"),
+            module_path!(),
+            TestMode::default(),
+            "test_content_new",
+        );
         assert_eq!(ctx.test_mode(), TestMode::Playback);
         assert_eq!(ctx.test_name(), "test_content_new");
     }
+
+    #[test]
+    fn recording_path() {
+        let ctx = TestContext::new(
+            "/src/my_crate",
+            "my_crate::client::tests",
+            TestMode::Record,
+            "get_secret",
+        );
+        assert_eq!(
+            ctx.recording_path(),
+            PathBuf::from("/src/my_crate/tests/recordings/my_crate/client/tests/get_secret.json")
+        );
+    }
+
+    fn temp_context(test_mode: TestMode, test_name: &'static str) -> TestContext {
+        let crate_dir =
+            std::env::temp_dir().join(format!("azure_core_test-{}", std::process::id()));
+        let crate_dir: &'static str = crate_dir.to_str().unwrap().to_owned().leak();
+        TestContext::new(crate_dir, module_path!(), test_mode, test_name)
+    }
+
+    #[test]
+    fn variables_play_back() {
+        let recorder = temp_context(TestMode::Record, "variables_play_back");
+        let name = recorder.variable("name", || "recorded".to_owned()).unwrap();
+        let number = recorder.random_u64().unwrap();
+        let uuid = recorder.random_uuid().unwrap();
+        let random_name = recorder.clone().random_name("db-", 8).unwrap();
+        assert_eq!(name, "recorded");
+        assert_eq!(uuid.get_version_num(), 4);
+        assert!(random_name.starts_with("db-"), "{random_name}");
+        assert_eq!(random_name.len(), 11);
+
+        let player = temp_context(TestMode::Playback, "variables_play_back");
+        assert_eq!(player.variable("name", || unreachable!()).unwrap(), name);
+        assert_eq!(player.random_u64().unwrap(), number);
+        assert_eq!(player.random_uuid().unwrap(), uuid);
+        assert_eq!(player.random_name("db-", 8).unwrap(), random_name);
+
+        let err = player.variable("other", || unreachable!()).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MockFramework);
+
+        std::fs::remove_file(player.recording_path()).unwrap();
+    }
+
+    #[test]
+    fn variables_live() {
+        let ctx = temp_context(TestMode::Live, "variables_live");
+        assert_eq!(ctx.variable("name", || "live".to_owned()).unwrap(), "live");
+        ctx.random_uuid().unwrap();
+        assert!(!ctx.recording_path().exists());
+    }
 }
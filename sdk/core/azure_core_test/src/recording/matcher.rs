@@ -0,0 +1,154 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::session::{RecordedBody, RecordedRequest};
+use serde_json::Value;
+
+/// Request headers that differ every time a request is sent, so are ignored by the default [`Matcher`].
+const VOLATILE_HEADERS: &[&str] = &[
+    "authorization",
+    "date",
+    "traceparent",
+    "user-agent",
+    "x-ms-client-request-id",
+    "x-ms-date",
+];
+
+/// Decides whether a request sent during playback matches a recorded request.
+///
+/// Requests match if they have the same method, path, query, and body, and every header present on both has the same value,
+/// apart from ignored headers. Headers that change every time a request is sent, like `x-ms-date`, `Authorization`,
+/// and `x-ms-client-request-id`, are ignored by default.
+///
+/// # Examples
+///
+/// ```
+/// # use azure_core_test::recording::Matcher;
+/// // Ignore the `x-ms-request-nonce` header, and match JSON bodies regardless of formatting or property order.
+/// let matcher = Matcher::default()
+///     .ignore_header("x-ms-request-nonce")
+///     .json_bodies(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    ignored_headers: Vec<String>,
+    json_bodies: bool,
+}
+
+impl Matcher {
+    /// Ignores the header `name` when matching requests.
+    pub fn ignore_header(mut self, name: impl AsRef<str>) -> Self {
+        self.ignored_headers
+            .push(name.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    /// Set whether bodies that are both valid JSON match if they have the same values,
+    /// regardless of whitespace and the order of properties. The default is `false`, which requires bodies to be identical.
+    pub fn json_bodies(mut self, json_bodies: bool) -> Self {
+        self.json_bodies = json_bodies;
+        self
+    }
+
+    /// Returns `true` if the sanitized `request` sent during playback matches the `recorded` request.
+    pub(crate) fn matches(&self, recorded: &RecordedRequest, request: &RecordedRequest) -> bool {
+        recorded.method.eq_ignore_ascii_case(&request.method)
+            && recorded.uri == request.uri
+            && self.bodies_match(&recorded.body, &request.body)
+            && request.headers.iter().all(|(name, value)| {
+                self.ignored_headers.contains(name)
+                    || recorded
+                        .headers
+                        .get(name)
+                        .map_or(true, |recorded| recorded == value)
+            })
+    }
+
+    fn bodies_match(&self, recorded: &RecordedBody, body: &RecordedBody) -> bool {
+        if recorded == body {
+            return true;
+        }
+        if !self.json_bodies {
+            return false;
+        }
+        match (&recorded.body, &body.body) {
+            (Some(recorded), Some(body)) => {
+                match (
+                    serde_json::from_str::<Value>(recorded),
+                    serde_json::from_str::<Value>(body),
+                ) {
+                    (Ok(recorded), Ok(body)) => recorded == body,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for Matcher {
+    fn default() -> Self {
+        Self {
+            ignored_headers: VOLATILE_HEADERS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            json_bodies: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn request(headers: &[(&str, &str)], body: &str) -> RecordedRequest {
+        RecordedRequest {
+            method: "PUT".to_string(),
+            uri: "/secrets/name?api-version=7.5".to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+            body: RecordedBody::new(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn default_matcher_ignores_volatile_headers() {
+        let matcher = Matcher::default();
+        let recorded = request(&[("x-ms-date", "then"), ("content-type", "json")], "{}");
+        assert!(matcher.matches(
+            &recorded,
+            &request(
+                &[("x-ms-date", "now"), ("x-ms-client-request-id", "1")],
+                "{}"
+            )
+        ));
+        assert!(!matcher.matches(&recorded, &request(&[("content-type", "text")], "{}")));
+        assert!(!matcher.matches(&recorded, &request(&[], "{ }")));
+    }
+
+    #[test]
+    fn matcher_ignores_headers() {
+        let matcher = Matcher::default().ignore_header("X-MS-Nonce");
+        assert!(matcher.matches(
+            &request(&[("x-ms-nonce", "1")], ""),
+            &request(&[("x-ms-nonce", "2")], "")
+        ));
+        assert!(!Matcher::default().matches(
+            &request(&[("x-ms-nonce", "1")], ""),
+            &request(&[("x-ms-nonce", "2")], "")
+        ));
+    }
+
+    #[test]
+    fn matcher_compares_json_bodies() {
+        let matcher = Matcher::default().json_bodies(true);
+        let recorded = request(&[], r#"{"a":1,"b":[true,null]}"#);
+        assert!(matcher.matches(&recorded, &request(&[], r#"{ "b": [true, null], "a": 1 }"#)));
+        assert!(!matcher.matches(&recorded, &request(&[], r#"{"a":2,"b":[true,null]}"#)));
+        assert!(!matcher.matches(&request(&[], "a"), &request(&[], "b")));
+    }
+}
@@ -0,0 +1,375 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Transports that record the requests a test sends and play them back later.
+//!
+//! Each test records a single session file containing every request it sent and the response it received,
+//! along with any variables it recorded using [`TestContext::variable()`](crate::TestContext::variable()).
+//! Tests attributed with [`#[recorded::test]`](crate::recorded::test) get these transports from
+//! [`TestContext::client_options()`](crate::TestContext::client_options()), so most tests need not use them directly.
+//!
+//! Secrets are scrubbed from recordings by [`Sanitizer`]s, and a [`Matcher`] decides which recorded request answers each request during playback.
+
+mod matcher;
+mod sanitizer;
+mod session;
+
+pub use matcher::Matcher;
+pub use sanitizer::Sanitizer;
+pub use session::SANITIZED;
+
+use azure_core::{
+    error::{Error, ErrorKind},
+    Context, HttpClient, Policy, PolicyResult, Request,
+};
+use session::{Entry, RecordedRequest, RecordedResponse, Session};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Transport [`Policy`] that sends requests using an [`HttpClient`] and records each request and response to a session file.
+///
+/// The session file is rewritten after every response, so it is complete even if the test fails part way through.
+/// `Authorization` header values are never recorded, and any [`Sanitizer`]s are applied to each request and response before it is recorded.
+#[derive(Debug)]
+pub struct RecordingPolicy {
+    http_client: Arc<dyn HttpClient>,
+    sanitizers: Vec<Sanitizer>,
+    session: Arc<SessionFile>,
+}
+
+impl RecordingPolicy {
+    /// Creates a `RecordingPolicy` that sends requests using `http_client` and records them to the session file at `path`.
+    ///
+    /// Any existing session file is replaced once the first response is received.
+    pub fn new(path: impl Into<PathBuf>, http_client: Arc<dyn HttpClient>) -> Self {
+        Self::with_session(Arc::new(SessionFile::new(path.into())), http_client)
+    }
+
+    /// Creates a `RecordingPolicy` that records to a session file shared with a [`TestContext`](crate::TestContext).
+    pub(crate) fn with_session(
+        session: Arc<SessionFile>,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Self {
+        Self {
+            http_client,
+            sanitizers: Vec::new(),
+            session,
+        }
+    }
+
+    /// Sets the [`Sanitizer`]s applied to each request and response before it is recorded.
+    pub fn with_sanitizers(mut self, sanitizers: Vec<Sanitizer>) -> Self {
+        self.sanitizers = sanitizers;
+        self
+    }
+
+    /// Gets the path of the session file.
+    pub fn path(&self) -> &Path {
+        self.session.path()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for RecordingPolicy {
+    async fn send(
+        &self,
+        _ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        // there must be no more policies
+        assert_eq!(0, next.len());
+
+        let mut recorded_request = RecordedRequest::new(request)?;
+        let response = self.http_client.execute_request(request).await?;
+
+        // The response body can only be read once, so record it and return a copy.
+        let (response, mut recorded_response) = RecordedResponse::new(response).await?;
+        for sanitizer in &self.sanitizers {
+            sanitizer.sanitize_request(&mut recorded_request);
+            sanitizer.sanitize_response(&mut recorded_response);
+        }
+
+        self.session.record(|session| {
+            session.entries.push(Entry {
+                request: recorded_request,
+                response: recorded_response,
+            })
+        })?;
+
+        Ok(response)
+    }
+}
+
+/// Transport [`Policy`] that responds to requests with the responses recorded in a session file, without sending them.
+///
+/// Requests may be sent in any order. Each request is answered with the response to the first recorded request
+/// that matches it and has not already been played back, so repeated identical requests are answered in the order they were recorded.
+/// Each request is sanitized by the same [`Sanitizer`]s used to record the session before the [`Matcher`] compares it to the recorded requests.
+#[derive(Debug)]
+pub struct PlaybackPolicy {
+    path: PathBuf,
+    sanitizers: Vec<Sanitizer>,
+    matcher: Matcher,
+    entries: Mutex<Vec<Option<Entry>>>,
+}
+
+impl PlaybackPolicy {
+    /// Creates a `PlaybackPolicy` that plays back the session file at `path`.
+    ///
+    /// Fails if the session file does not exist or cannot be parsed.
+    pub fn new(path: impl Into<PathBuf>) -> azure_core::Result<Self> {
+        let path = path.into();
+        let session = Session::load(&path)?;
+        Ok(Self {
+            path,
+            sanitizers: Vec::new(),
+            matcher: Matcher::default(),
+            entries: Mutex::new(session.entries.into_iter().map(Some).collect()),
+        })
+    }
+
+    /// Sets the [`Sanitizer`]s applied to each request before it is matched, which should be the same as those used to record the session.
+    pub fn with_sanitizers(mut self, sanitizers: Vec<Sanitizer>) -> Self {
+        self.sanitizers = sanitizers;
+        self
+    }
+
+    /// Sets the [`Matcher`] that decides whether a request matches a recorded request.
+    pub fn with_matcher(mut self, matcher: Matcher) -> Self {
+        self.matcher = matcher;
+        self
+    }
+
+    /// Gets the path of the session file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for PlaybackPolicy {
+    async fn send(
+        &self,
+        _ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        // there must be no more policies
+        assert_eq!(0, next.len());
+
+        let mut sanitized = RecordedRequest::new(request)?;
+        for sanitizer in &self.sanitizers {
+            sanitizer.sanitize_request(&mut sanitized);
+        }
+
+        let mut entries = self.entries.lock().expect("session lock poisoned");
+        for entry in entries.iter_mut() {
+            let matched = entry
+                .as_ref()
+                .is_some_and(|entry| self.matcher.matches(&entry.request, &sanitized));
+            if matched {
+                let entry = entry.take().expect("matched entry");
+                return entry.response.to_response();
+            }
+        }
+
+        Err(Error::with_message(ErrorKind::MockFramework, || {
+            format!(
+                "no unused recording in '{}' matches request {} {}; re-record the test with AZURE_TEST_MODE=record",
+                self.path.display(),
+                request.method(),
+                request.path_and_query(),
+            )
+        }))
+    }
+}
+
+/// A session file shared by a [`TestContext`](crate::TestContext) and its [`RecordingPolicy`], so that variables and
+/// entries recorded by either are saved together.
+#[derive(Debug)]
+pub(crate) struct SessionFile {
+    path: PathBuf,
+    session: Mutex<Option<Session>>,
+}
+
+impl SessionFile {
+    /// Creates a `SessionFile` at `path`, which is not read until a recorded variable is needed.
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            session: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records changes to a new, empty session, replacing any existing session file.
+    pub(crate) fn record(&self, f: impl FnOnce(&mut Session)) -> azure_core::Result<()> {
+        let mut session = self.session.lock().expect("session lock poisoned");
+        let session = session.get_or_insert_with(Session::default);
+        f(session);
+        session.save(&self.path)
+    }
+
+    /// Gets the variable `name` recorded in the existing session file, reading the file if it has not been read already.
+    pub(crate) fn recorded_variable(&self, name: &str) -> azure_core::Result<String> {
+        let mut session = self.session.lock().expect("session lock poisoned");
+        let session = match &mut *session {
+            Some(session) => session,
+            None => session.insert(Session::load(&self.path)?),
+        };
+        session.variables.get(name).cloned().ok_or_else(|| {
+            Error::with_message(ErrorKind::MockFramework, || {
+                format!(
+                    "variable '{name}' is not recorded in '{}'; re-record the test with AZURE_TEST_MODE=record",
+                    self.path.display(),
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{headers::Headers, Method, Response, StatusCode};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Responds to each request with a numbered body, echoing the request path.
+    #[derive(Debug, Default)]
+    struct NumberingClient {
+        requests: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl HttpClient for NumberingClient {
+        async fn execute_request(&self, request: &Request) -> azure_core::Result<Response> {
+            let number = self.requests.fetch_add(1, Ordering::SeqCst);
+            Ok(Response::from_bytes(
+                StatusCode::Ok,
+                Headers::new(),
+                format!("{number}:{}", request.path_and_query()),
+            ))
+        }
+    }
+
+    fn session_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("azure_core_test-{}", std::process::id()))
+            .join(format!("{name}.json"))
+    }
+
+    async fn send(policy: &dyn Policy, path: &str) -> azure_core::Result<String> {
+        let mut request = Request::new(
+            format!("https://contoso.com{path}").parse().unwrap(),
+            Method::Get,
+        );
+        request.insert_header("authorization", "Bearer secret");
+        request.insert_header("x-ms-date", "now");
+        let response = policy.send(&Context::new(), &mut request, &[]).await?;
+        let body = response.into_body().collect().await?;
+        Ok(String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn plays_back_in_any_order() {
+        let path = session_path("plays_back_in_any_order");
+        let recorder = RecordingPolicy::new(&path, Arc::new(NumberingClient::default()));
+        assert_eq!(send(&recorder, "/a").await.unwrap(), "0:/a");
+        assert_eq!(send(&recorder, "/b").await.unwrap(), "1:/b");
+        assert_eq!(send(&recorder, "/a").await.unwrap(), "2:/a");
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        assert!(!recording.contains("secret"), "{recording}");
+
+        let player = PlaybackPolicy::new(&path).unwrap();
+        assert_eq!(send(&player, "/b").await.unwrap(), "1:/b");
+        assert_eq!(send(&player, "/a").await.unwrap(), "0:/a");
+        assert_eq!(send(&player, "/a").await.unwrap(), "2:/a");
+
+        let err = send(&player, "/a").await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MockFramework);
+        let err = send(&player, "/c").await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MockFramework);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn sanitizes_recordings() {
+        let path = session_path("sanitizes_recordings");
+        let sanitizers = vec![Sanitizer::uri_regex("sig=[^&]+", "sig=Sanitized").unwrap()];
+        let recorder = RecordingPolicy::new(&path, Arc::new(NumberingClient::default()))
+            .with_sanitizers(sanitizers.clone());
+        assert_eq!(send(&recorder, "/a?sig=one").await.unwrap(), "0:/a?sig=one");
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            recording.contains(r#""uri": "/a?sig=Sanitized""#),
+            "{recording}"
+        );
+
+        // Requests with different secrets still match once they are sanitized.
+        let player = PlaybackPolicy::new(&path)
+            .unwrap()
+            .with_sanitizers(sanitizers)
+            .with_matcher(Matcher::default().json_bodies(true));
+        assert_eq!(send(&player, "/a?sig=two").await.unwrap(), "0:/a?sig=one");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Responds to each request with Azure Cosmos DB's non-standard `449 Retry With`.
+    #[derive(Debug)]
+    struct RetryWithClient;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl HttpClient for RetryWithClient {
+        async fn execute_request(&self, _request: &Request) -> azure_core::Result<Response> {
+            Ok(
+                Response::from_bytes(StatusCode::BadRequest, Headers::new(), "")
+                    .with_raw_status(449),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn records_non_standard_status() {
+        let path = session_path("records_non_standard_status");
+        let mut request = Request::new("https://contoso.com/a".parse().unwrap(), Method::Get);
+
+        let recorder = RecordingPolicy::new(&path, Arc::new(RetryWithClient));
+        let response = recorder
+            .send(&Context::new(), &mut request, &[])
+            .await
+            .unwrap();
+        assert_eq!(response.raw_status(), 449);
+        let recording = std::fs::read_to_string(&path).unwrap();
+        assert!(recording.contains(r#""status": 449"#), "{recording}");
+
+        let player = PlaybackPolicy::new(&path).unwrap();
+        let response = player
+            .send(&Context::new(), &mut request, &[])
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BadRequest);
+        assert_eq!(response.raw_status(), 449);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn playback_requires_recording() {
+        let err = PlaybackPolicy::new(session_path("playback_requires_recording")).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MockFramework);
+    }
+}
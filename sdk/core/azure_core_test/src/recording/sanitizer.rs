@@ -0,0 +1,345 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::session::{RecordedBody, RecordedRequest, RecordedResponse};
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Scrubs secrets from requests and responses before they are recorded.
+///
+/// Sanitizers are also applied to each request during playback before it is matched,
+/// so requests still match recordings with secrets that differ from one run to the next.
+///
+/// # Examples
+///
+/// ```
+/// # use azure_core_test::recording::{Sanitizer, SANITIZED};
+/// # fn main() -> azure_core::Result<()> {
+/// let sanitizers = vec![
+///     // Replace the whole value of the `x-ms-encryption-key` header.
+///     Sanitizer::header("x-ms-encryption-key", SANITIZED),
+///     // Replace just the signature in the `Location` header.
+///     Sanitizer::header_regex("location", "sig=[^&]+", "sig=Sanitized")?,
+///     // Replace the `password` of every user in JSON bodies.
+///     Sanitizer::body_json_path("$.users[*].password", SANITIZED)?,
+///     // Replace the account name in URIs.
+///     Sanitizer::uri_regex("/accounts/[^/]+", "/accounts/Sanitized")?,
+/// ];
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sanitizer {
+    kind: SanitizerKind,
+}
+
+#[derive(Clone, Debug)]
+enum SanitizerKind {
+    Header {
+        name: String,
+        regex: Option<Regex>,
+        replacement: String,
+    },
+    BodyJsonPath {
+        path: Vec<PathSegment>,
+        replacement: String,
+    },
+    Uri {
+        regex: Regex,
+        replacement: String,
+    },
+}
+
+impl Sanitizer {
+    /// Replaces the whole value of the header `name` in requests and responses with `replacement`.
+    pub fn header(name: impl AsRef<str>, replacement: impl Into<String>) -> Self {
+        Self {
+            kind: SanitizerKind::Header {
+                name: name.as_ref().to_ascii_lowercase(),
+                regex: None,
+                replacement: replacement.into(),
+            },
+        }
+    }
+
+    /// Replaces every match of `regex` in the value of the header `name` in requests and responses with `replacement`.
+    ///
+    /// The `replacement` may refer to capture groups, as described by [`Regex::replace_all()`].
+    pub fn header_regex(
+        name: impl AsRef<str>,
+        regex: &str,
+        replacement: impl Into<String>,
+    ) -> azure_core::Result<Self> {
+        Ok(Self {
+            kind: SanitizerKind::Header {
+                name: name.as_ref().to_ascii_lowercase(),
+                regex: Some(parse_regex(regex)?),
+                replacement: replacement.into(),
+            },
+        })
+    }
+
+    /// Replaces every value selected by the JSON `path` in request and response bodies with the string `replacement`.
+    ///
+    /// Paths start with `$` followed by any number of `.name`, `['name']`, `[index]`, or `[*]` segments,
+    /// where `*` selects every property of an object or element of an array. Bodies that are not JSON are not changed.
+    pub fn body_json_path(path: &str, replacement: impl Into<String>) -> azure_core::Result<Self> {
+        Ok(Self {
+            kind: SanitizerKind::BodyJsonPath {
+                path: parse_json_path(path)?,
+                replacement: replacement.into(),
+            },
+        })
+    }
+
+    /// Replaces every match of `regex` in request URIs with `replacement`.
+    ///
+    /// URIs are recorded as a path and query, for example `/secrets/name?api-version=7.5`.
+    /// The `replacement` may refer to capture groups, as described by [`Regex::replace_all()`].
+    pub fn uri_regex(regex: &str, replacement: impl Into<String>) -> azure_core::Result<Self> {
+        Ok(Self {
+            kind: SanitizerKind::Uri {
+                regex: parse_regex(regex)?,
+                replacement: replacement.into(),
+            },
+        })
+    }
+
+    pub(crate) fn sanitize_request(&self, request: &mut RecordedRequest) {
+        match &self.kind {
+            SanitizerKind::Header { .. } => self.sanitize_headers(&mut request.headers),
+            SanitizerKind::BodyJsonPath { .. } => self.sanitize_body(&mut request.body),
+            SanitizerKind::Uri { regex, replacement } => {
+                request.uri = regex
+                    .replace_all(&request.uri, replacement.as_str())
+                    .into_owned();
+            }
+        }
+    }
+
+    pub(crate) fn sanitize_response(&self, response: &mut RecordedResponse) {
+        match &self.kind {
+            SanitizerKind::Header { .. } => self.sanitize_headers(&mut response.headers),
+            SanitizerKind::BodyJsonPath { .. } => self.sanitize_body(&mut response.body),
+            SanitizerKind::Uri { .. } => {}
+        }
+    }
+
+    fn sanitize_headers(&self, headers: &mut BTreeMap<String, String>) {
+        let SanitizerKind::Header {
+            name,
+            regex,
+            replacement,
+        } = &self.kind
+        else {
+            return;
+        };
+        if let Some(value) = headers.get_mut(name) {
+            *value = match regex {
+                Some(regex) => regex.replace_all(value, replacement.as_str()).into_owned(),
+                None => replacement.clone(),
+            };
+        }
+    }
+
+    fn sanitize_body(&self, body: &mut RecordedBody) {
+        let SanitizerKind::BodyJsonPath { path, replacement } = &self.kind else {
+            return;
+        };
+        let Some(text) = &body.body else {
+            return;
+        };
+        let Ok(mut json) = serde_json::from_str::<Value>(text) else {
+            return;
+        };
+        if replace_json_path(&mut json, path, replacement) {
+            body.body = Some(json.to_string());
+        }
+    }
+}
+
+/// A segment of a JSON path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PathSegment {
+    Property(String),
+    Index(usize),
+    Wildcard,
+}
+
+fn parse_regex(regex: &str) -> azure_core::Result<Regex> {
+    Regex::new(regex).with_context(ErrorKind::MockFramework, || {
+        format!("invalid sanitizer regex '{regex}'")
+    })
+}
+
+fn parse_json_path(path: &str) -> azure_core::Result<Vec<PathSegment>> {
+    let invalid = || {
+        Error::with_message(ErrorKind::MockFramework, || {
+            format!("invalid sanitizer JSON path '{path}'")
+        })
+    };
+
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let segment = match &after[..end] {
+                "" => return Err(invalid()),
+                "*" => PathSegment::Wildcard,
+                name => PathSegment::Property(name.to_string()),
+            };
+            segments.push(segment);
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let inner = &after[..end];
+            let segment = if inner == "*" {
+                PathSegment::Wildcard
+            } else if let Some(name) = inner
+                .strip_prefix('\'')
+                .and_then(|name| name.strip_suffix('\''))
+            {
+                PathSegment::Property(name.to_string())
+            } else {
+                PathSegment::Index(inner.parse().map_err(|_| invalid())?)
+            };
+            segments.push(segment);
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(segments)
+}
+
+/// Replaces every value in `json` selected by `path` with the string `replacement`, returning `true` if any were replaced.
+fn replace_json_path(json: &mut Value, path: &[PathSegment], replacement: &str) -> bool {
+    let Some((segment, rest)) = path.split_first() else {
+        *json = Value::String(replacement.to_string());
+        return true;
+    };
+    let values: Vec<&mut Value> = match (segment, json) {
+        (PathSegment::Property(name), Value::Object(object)) => {
+            object.get_mut(name).into_iter().collect()
+        }
+        (PathSegment::Index(index), Value::Array(array)) => {
+            array.get_mut(*index).into_iter().collect()
+        }
+        (PathSegment::Wildcard, Value::Object(object)) => object.values_mut().collect(),
+        (PathSegment::Wildcard, Value::Array(array)) => array.iter_mut().collect(),
+        _ => return false,
+    };
+
+    // Replace every selected value, rather than stopping at the first.
+    let mut replaced = false;
+    for value in values {
+        replaced |= replace_json_path(value, rest, replacement);
+    }
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::SANITIZED;
+
+    fn body(text: &str) -> RecordedBody {
+        RecordedBody {
+            body: Some(text.to_string()),
+            body_base64: None,
+        }
+    }
+
+    fn request() -> RecordedRequest {
+        RecordedRequest {
+            method: "POST".to_string(),
+            uri: "/accounts/contoso/users?sig=secret&api-version=1".to_string(),
+            headers: BTreeMap::from([
+                ("x-ms-key".to_string(), "secret".to_string()),
+                (
+                    "location".to_string(),
+                    "https://contoso.com/?sig=secret&se=1".to_string(),
+                ),
+            ]),
+            body: body(
+                r#"{"users":[{"name":"a","password":"secret"},{"name":"b","password":"secret"}]}"#,
+            ),
+        }
+    }
+
+    #[test]
+    fn sanitizes_headers() {
+        let mut request = request();
+        Sanitizer::header("X-MS-Key", SANITIZED).sanitize_request(&mut request);
+        Sanitizer::header_regex("location", "sig=[^&]+", "sig=Sanitized")
+            .unwrap()
+            .sanitize_request(&mut request);
+        assert_eq!(request.headers["x-ms-key"], "Sanitized");
+        assert_eq!(
+            request.headers["location"],
+            "https://contoso.com/?sig=Sanitized&se=1"
+        );
+    }
+
+    #[test]
+    fn sanitizes_uri() {
+        let mut request = request();
+        Sanitizer::uri_regex("/accounts/[^/]+", "/accounts/Sanitized")
+            .unwrap()
+            .sanitize_request(&mut request);
+        Sanitizer::uri_regex("sig=[^&]+", "sig=Sanitized")
+            .unwrap()
+            .sanitize_request(&mut request);
+        assert_eq!(
+            request.uri,
+            "/accounts/Sanitized/users?sig=Sanitized&api-version=1"
+        );
+    }
+
+    #[test]
+    fn sanitizes_body_json_path() {
+        let mut request = request();
+        Sanitizer::body_json_path("$.users[*].password", SANITIZED)
+            .unwrap()
+            .sanitize_request(&mut request);
+        Sanitizer::body_json_path("$['users'][1].name", SANITIZED)
+            .unwrap()
+            .sanitize_request(&mut request);
+        assert_eq!(
+            request.body.body.as_deref(),
+            Some(
+                r#"{"users":[{"name":"a","password":"Sanitized"},{"name":"Sanitized","password":"Sanitized"}]}"#
+            )
+        );
+
+        // Bodies without a match, or that are not JSON, are left as they are.
+        let mut unmatched = body(r#"{ "other": 1 }"#);
+        let mut text = body("password");
+        let sanitizer = Sanitizer::body_json_path("$.password", SANITIZED).unwrap();
+        sanitizer.sanitize_body(&mut unmatched);
+        sanitizer.sanitize_body(&mut text);
+        assert_eq!(unmatched.body.as_deref(), Some(r#"{ "other": 1 }"#));
+        assert_eq!(text.body.as_deref(), Some("password"));
+    }
+
+    #[test]
+    fn parses_json_paths() {
+        assert_eq!(parse_json_path("$").unwrap(), vec![]);
+        assert_eq!(
+            parse_json_path("$.a[0]['b.c'].*[*]").unwrap(),
+            vec![
+                PathSegment::Property("a".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Property("b.c".to_string()),
+                PathSegment::Wildcard,
+                PathSegment::Wildcard,
+            ]
+        );
+        for invalid in ["", "a.b", "$.", "$..a", "$[a]", "$[0", "$a"] {
+            parse_json_path(invalid).expect_err(invalid);
+        }
+    }
+}
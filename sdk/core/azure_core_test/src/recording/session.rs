@@ -0,0 +1,184 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{
+    base64,
+    error::{Error, ErrorKind, ResultExt},
+    headers::{HeaderName, HeaderValue, Headers},
+    status_from_raw, Body, Request, Response,
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// The value recorded in place of a secret.
+pub const SANITIZED: &str = "Sanitized";
+
+/// All the requests sent by a single test and the responses received, in the order they were sent,
+/// along with any variables the test recorded.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Session {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) variables: BTreeMap<String, String>,
+    pub(crate) entries: Vec<Entry>,
+}
+
+impl Session {
+    /// Reads a session from the file at `path`.
+    pub(crate) fn load(path: &Path) -> azure_core::Result<Self> {
+        let contents = std::fs::read(path).with_context(ErrorKind::MockFramework, || {
+            format!(
+                "cannot read session recording '{}'; record it by running the test with AZURE_TEST_MODE=record",
+                path.display()
+            )
+        })?;
+        serde_json::from_slice(&contents).with_context(ErrorKind::MockFramework, || {
+            format!("cannot parse session recording '{}'", path.display())
+        })
+    }
+
+    /// Writes this session to the file at `path`, creating any missing directories.
+    pub(crate) fn save(&self, path: &Path) -> azure_core::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(ErrorKind::MockFramework, || {
+                format!("cannot create recordings directory '{}'", dir.display())
+            })?;
+        }
+        let contents = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, contents).with_context(ErrorKind::MockFramework, || {
+            format!("cannot write session recording '{}'", path.display())
+        })
+    }
+}
+
+/// A request and the response it received.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct Entry {
+    pub(crate) request: RecordedRequest,
+    pub(crate) response: RecordedResponse,
+}
+
+/// A recorded request.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct RecordedRequest {
+    pub(crate) method: String,
+    pub(crate) uri: String,
+    pub(crate) headers: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub(crate) body: RecordedBody,
+}
+
+impl RecordedRequest {
+    pub(crate) fn new(request: &Request) -> azure_core::Result<Self> {
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = if name.as_str() == "authorization" {
+                    SANITIZED
+                } else {
+                    value.as_str()
+                };
+                (name.as_str().to_owned(), value.to_owned())
+            })
+            .collect();
+        Ok(Self {
+            method: request.method().to_string(),
+            uri: request.path_and_query(),
+            headers,
+            body: RecordedBody::new(request_body(request)?),
+        })
+    }
+}
+
+/// A recorded response.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct RecordedResponse {
+    pub(crate) status: u16,
+    pub(crate) headers: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub(crate) body: RecordedBody,
+}
+
+impl RecordedResponse {
+    /// Reads the whole body of `response`, returning a copy of the response and a recording of it.
+    pub(crate) async fn new(response: Response) -> azure_core::Result<(Response, Self)> {
+        // Record the status the service actually returned, even if `StatusCode` cannot represent it.
+        let raw_status = response.raw_status();
+        let (status, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+        let recorded = Self {
+            status: raw_status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.as_str().to_owned(), value.as_str().to_owned()))
+                .collect(),
+            body: RecordedBody::new(&body),
+        };
+        Ok((
+            Response::from_bytes(status, headers, body).with_raw_status(raw_status),
+            recorded,
+        ))
+    }
+
+    pub(crate) fn to_response(&self) -> azure_core::Result<Response> {
+        let status = status_from_raw(self.status).with_context(ErrorKind::MockFramework, || {
+            format!("invalid recorded status code {}", self.status)
+        })?;
+        let mut headers = Headers::new();
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from(name.to_owned()),
+                HeaderValue::from(value.to_owned()),
+            );
+        }
+        Ok(Response::from_bytes(status, headers, self.body.to_bytes()?)
+            .with_raw_status(self.status))
+    }
+}
+
+/// A recorded body: text if it is valid UTF-8, so recordings are easy to read; otherwise, base64-encoded.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) struct RecordedBody {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) body_base64: Option<String>,
+}
+
+impl RecordedBody {
+    pub(crate) fn new(body: &[u8]) -> Self {
+        if body.is_empty() {
+            return Self::default();
+        }
+        match std::str::from_utf8(body) {
+            Ok(text) => Self {
+                body: Some(text.to_owned()),
+                body_base64: None,
+            },
+            Err(_) => Self {
+                body: None,
+                body_base64: Some(base64::encode(body)),
+            },
+        }
+    }
+
+    pub(crate) fn to_bytes(&self) -> azure_core::Result<Bytes> {
+        match (&self.body, &self.body_base64) {
+            (Some(text), _) => Ok(Bytes::from(text.clone())),
+            (None, Some(encoded)) => Ok(Bytes::from(base64::decode(encoded)?)),
+            (None, None) => Ok(Bytes::new()),
+        }
+    }
+}
+
+fn request_body(request: &Request) -> azure_core::Result<&[u8]> {
+    match request.body() {
+        Body::Bytes(bytes) => Ok(bytes),
+        #[cfg(not(target_arch = "wasm32"))]
+        Body::SeekableStream(_) => Err(Error::message(
+            ErrorKind::MockFramework,
+            "requests with streamed bodies cannot be recorded or played back",
+        )),
+    }
+}
@@ -5,10 +5,13 @@ use azure_core::test::TestMode;
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::sync::LazyLock;
-use syn::{parse::Parse, spanned::Spanned, FnArg, ItemFn, Meta, PatType, Result, Token};
+use syn::{
+    parse::Parse, punctuated::Punctuated, spanned::Spanned, FnArg, ItemFn, LitStr, Meta, PatType,
+    Result, Token,
+};
 
 const INVALID_RECORDED_ATTRIBUTE_MESSAGE: &str =
-    "expected `#[recorded::test]` or `#[recorded::test(live)]`";
+    "expected `#[recorded::test]` or `#[recorded::test(live)]`, optionally with `json_body`, `ignore_headers(..)`, `sanitize_headers(..)`, `sanitize_body(..)`, or `sanitize_uri(..)`";
 const INVALID_RECORDED_FUNCTION_MESSAGE: &str = "expected `fn(TestContext)` function signature";
 
 // cspell:ignore asyncness
@@ -42,10 +45,21 @@ pub fn parse_test(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
         Some(FnArg::Typed(PatType { pat, ty, .. })) if is_test_context(ty.as_ref()) => {
             let test_mode = test_mode_to_tokens(test_mode);
             let fn_name = &sig.ident;
+            let recording = recording_to_tokens(&recorded_attrs);
 
             quote! {
                 #[allow(dead_code)]
-                let #pat = #ty::new(#test_mode, stringify!(#fn_name));
+                let #pat = {
+                    #[allow(unused_mut)]
+                    let mut ctx = #ty::new(
+                        ::core::env!("CARGO_MANIFEST_DIR"),
+                        ::core::module_path!(),
+                        #test_mode,
+                        ::core::stringify!(#fn_name),
+                    );
+                    #recording
+                    ctx
+                };
             }
         }
         _ => {
@@ -83,6 +97,11 @@ static TEST_MODE: LazyLock<TestMode> = LazyLock::new(|| {
 #[derive(Debug, Default)]
 struct Attributes {
     live: bool,
+    json_body: bool,
+    ignore_headers: Vec<LitStr>,
+    sanitize_headers: Vec<LitStr>,
+    sanitize_body: Vec<LitStr>,
+    sanitize_uri: Vec<LitStr>,
 }
 
 impl Parse for Attributes {
@@ -96,6 +115,7 @@ impl Parse for Attributes {
                     })?;
                     match ident.to_string().as_str() {
                         "live" => attrs.live = true,
+                        "json_body" => attrs.json_body = true,
                         _ => {
                             return Err(syn::Error::new(
                                 arg.span(),
@@ -104,6 +124,26 @@ impl Parse for Attributes {
                         }
                     }
                 }
+                Meta::List(list) => {
+                    let ident = list.path.get_ident().ok_or_else(|| {
+                        syn::Error::new(arg.span(), INVALID_RECORDED_ATTRIBUTE_MESSAGE)
+                    })?;
+                    let values = match ident.to_string().as_str() {
+                        "ignore_headers" => &mut attrs.ignore_headers,
+                        "sanitize_headers" => &mut attrs.sanitize_headers,
+                        "sanitize_body" => &mut attrs.sanitize_body,
+                        "sanitize_uri" => &mut attrs.sanitize_uri,
+                        _ => {
+                            return Err(syn::Error::new(
+                                arg.span(),
+                                INVALID_RECORDED_ATTRIBUTE_MESSAGE,
+                            ))
+                        }
+                    };
+                    values.extend(
+                        list.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?,
+                    );
+                }
                 _ => {
                     return Err(syn::Error::new(
                         arg.span(),
@@ -135,6 +175,54 @@ fn is_test_context(arg: &syn::Type) -> bool {
         && path.segments[1].ident == "TestContext"
 }
 
+/// Configures the sanitizers and matcher of a `TestContext` named `ctx` from the attributes.
+fn recording_to_tokens(attrs: &Attributes) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    for name in &attrs.sanitize_headers {
+        tokens.extend(quote! {
+            ctx.add_sanitizer(::azure_core_test::recording::Sanitizer::header(
+                #name,
+                ::azure_core_test::recording::SANITIZED,
+            ));
+        });
+    }
+    for path in &attrs.sanitize_body {
+        tokens.extend(quote! {
+            ctx.add_sanitizer(
+                ::azure_core_test::recording::Sanitizer::body_json_path(
+                    #path,
+                    ::azure_core_test::recording::SANITIZED,
+                )
+                .expect("invalid `sanitize_body` JSON path"),
+            );
+        });
+    }
+    for regex in &attrs.sanitize_uri {
+        tokens.extend(quote! {
+            ctx.add_sanitizer(
+                ::azure_core_test::recording::Sanitizer::uri_regex(
+                    #regex,
+                    ::azure_core_test::recording::SANITIZED,
+                )
+                .expect("invalid `sanitize_uri` regex"),
+            );
+        });
+    }
+
+    if attrs.json_body || !attrs.ignore_headers.is_empty() {
+        let ignore_headers = &attrs.ignore_headers;
+        let json_body = attrs.json_body.then(|| quote! { .json_bodies(true) });
+        tokens.extend(quote! {
+            ctx.set_matcher(
+                ctx.matcher().clone()
+                    #(.ignore_header(#ignore_headers))*
+                    #json_body,
+            );
+        });
+    }
+    tokens
+}
+
 fn test_mode_to_tokens(test_mode: TestMode) -> TokenStream {
     match test_mode {
         TestMode::Playback => quote! { ::azure_core_test::TestMode::Playback },
@@ -181,6 +269,40 @@ mod tests {
         attr.parse_args::<Attributes>().unwrap_err();
     }
 
+    #[test]
+    fn attributes_parse_recording() {
+        let attr: Attribute = syn::parse_quote! {
+            #[recorded(
+                json_body,
+                ignore_headers("x-ms-nonce", "x-ms-other"),
+                sanitize_headers("x-ms-key"),
+                sanitize_body("$.password"),
+                sanitize_uri("sig=[^&]+"),
+            )]
+        };
+        let attrs: Attributes = attr.parse_args().unwrap();
+        assert!(!attrs.live);
+        assert!(attrs.json_body);
+        let values = |lits: &[LitStr]| lits.iter().map(LitStr::value).collect::<Vec<_>>();
+        assert_eq!(values(&attrs.ignore_headers), ["x-ms-nonce", "x-ms-other"]);
+        assert_eq!(values(&attrs.sanitize_headers), ["x-ms-key"]);
+        assert_eq!(values(&attrs.sanitize_body), ["$.password"]);
+        assert_eq!(values(&attrs.sanitize_uri), ["sig=[^&]+"]);
+    }
+
+    #[test]
+    fn attributes_parse_recording_requires_strings() {
+        let attr: Attribute = syn::parse_quote! {
+            #[recorded(sanitize_headers(x_ms_key))]
+        };
+        attr.parse_args::<Attributes>().unwrap_err();
+
+        let attr: Attribute = syn::parse_quote! {
+            #[recorded(other("x-ms-key"))]
+        };
+        attr.parse_args::<Attributes>().unwrap_err();
+    }
+
     #[test]
     fn is_test_context() {
         let types: Vec<syn::Type> = vec![
@@ -226,6 +348,19 @@ mod tests {
         parse_test(attr, item).unwrap_err();
     }
 
+    #[test]
+    fn parse_recorded_playback_with_sanitizers() {
+        let attr = quote! { sanitize_headers("x-ms-key"), ignore_headers("x-ms-nonce") };
+        let item = quote! {
+            async fn recorded(ctx: TestContext) {
+                todo!()
+            }
+        };
+        let tokens = parse_test(attr, item).unwrap().to_string();
+        assert!(tokens.contains("add_sanitizer"), "{tokens}");
+        assert!(tokens.contains("set_matcher"), "{tokens}");
+    }
+
     #[test]
     fn parse_recorded_live() {
         let attr = quote! { live };
@@ -3,15 +3,23 @@
 
 use crate::{
     constants,
-    models::{ContainerProperties, Item, PatchDocument, QueryResults, ThroughputProperties},
+    models::{
+        ContainerProperties, Item, PartitionKeyRange, PatchDocument, QueryResults,
+        ThroughputProperties, TransactionalBatchResponse,
+    },
     options::{QueryOptions, ReadContainerOptions},
-    pipeline::CosmosPipeline,
+    pipeline::{ChangeFeedState, CosmosPipeline},
     resource_context::{ResourceLink, ResourceType},
-    DeleteContainerOptions, ItemOptions, PartitionKey, Query, QueryPartitionStrategy,
-    ThroughputOptions,
+    ChangeFeedOptions, DeleteContainerOptions, ItemOptions, PartitionKey, Query,
+    QueryPartitionStrategy, ReadPartitionKeyRangesOptions, ThroughputOptions, TransactionalBatch,
+    TransactionalBatchOptions, MAX_TRANSACTIONAL_BATCH_OPERATIONS,
 };
 
-use azure_core::{Method, Pager, Request, Response};
+use azure_core::{
+    error::{Error, ErrorKind, HttpError},
+    headers::Headers,
+    Method, Pager, Request, Response,
+};
 use serde::{de::DeserializeOwned, Serialize};
 
 /// A client for working with a specific container in a Cosmos DB account.
@@ -447,14 +455,78 @@ impl ContainerClient {
             .await
     }
 
-    /// Executes a single-partition query against items in the container.
+    /// Executes a transactional batch of item operations within a single logical partition.
+    ///
+    /// The operations are executed in order, and atomically: if any operation fails, none of them are applied.
+    /// A failed batch is not an `Err`: the response has the status code of the failed operation, so check
+    /// [`TransactionalBatchResponse::is_success()`] and the per-operation results instead.
+    /// Errors that prevent the batch from running at all, such as authorization failures, are still returned as an `Err`.
+    ///
+    /// # Arguments
+    /// * `batch` - The operations to execute. See [`TransactionalBatch`] for how to build a batch.
+    /// * `options` - Optional parameters for the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use azure_data_cosmos::{clients::ContainerClient, TransactionalBatch};
+    /// # async fn doc() {
+    /// # let container_client: ContainerClient = panic!("this is a non-running example");
+    /// let batch = TransactionalBatch::new("customer1")
+    ///     .upsert_item(serde_json::json!({"id": "order1", "status": "paid"}), None)
+    ///     .unwrap()
+    ///     .delete_item("cart1", None);
+    /// let response = container_client
+    ///     .execute_transactional_batch(batch, None)
+    ///     .await.unwrap()
+    ///     .deserialize_body()
+    ///     .await.unwrap();
+    /// if !response.is_success() {
+    ///     for result in response.results {
+    ///         println!("status: {}", result.status_code);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn execute_transactional_batch(
+        &self,
+        batch: TransactionalBatch,
+        options: Option<TransactionalBatchOptions<'_>>,
+    ) -> azure_core::Result<Response<TransactionalBatchResponse>> {
+        let options = options.unwrap_or_default();
+        let operation_count = batch.operations().len();
+        if operation_count == 0 || operation_count > MAX_TRANSACTIONAL_BATCH_OPERATIONS {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("a transactional batch must contain between 1 and {MAX_TRANSACTIONAL_BATCH_OPERATIONS} operations, but it contains {operation_count}")
+            }));
+        }
+
+        let url = self.pipeline.url(&self.items_link);
+        let mut req = Request::new(url, Method::Post);
+        req.insert_header(constants::IS_BATCH_REQUEST, "True");
+        req.insert_header(constants::BATCH_ATOMIC, "True");
+        req.insert_header(constants::BATCH_CONTINUE_ON_ERROR, "False");
+        req.insert_headers(batch.partition_key())?;
+        req.set_json(&batch.operations())?;
+        self.pipeline
+            .send(
+                options.method_options.context,
+                &mut req,
+                self.items_link.clone(),
+            )
+            .await
+            .or_else(failed_batch_response)
+    }
+
+    /// Executes a query against items in the container.
     ///
     /// The resulting document will be deserialized into the type provided as `T`.
     /// If you want to deserialize the document to a direct representation of the JSON returned, use [`serde_json::Value`] as the target type.
     ///
     /// We recommend using ["turbofish" syntax](https://doc.rust-lang.org/book/appendix-02-operators.html#:~:text=turbofish) (`query_items::<SomeTargetType>(...)`) to specify the target type, as it makes type inference easier.
     ///
-    /// **NOTE:** Currently, the Azure Cosmos DB SDK for Rust only supports single-partition querying. Cross-partition queries may be supported in the future.
+    /// Pass [`QueryPartitionStrategy::CrossPartition`] as `partition_key` to query every partition in the container.
+    /// See [`QueryPartitionStrategy`] for the kinds of queries that can run across partitions.
     ///
     /// # Arguments
     ///
@@ -500,6 +572,19 @@ impl ContainerClient {
     /// # }
     /// ```
     ///
+    /// A query without a partition key is executed against each partition in turn:
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_data_cosmos::{clients::ContainerClient, QueryPartitionStrategy};
+    /// # let container_client: ContainerClient = panic!("this is a non-running example");
+    /// let items = container_client.query_items::<serde_json::Value>(
+    ///     "SELECT * FROM c WHERE c.is_active = true",
+    ///     QueryPartitionStrategy::CrossPartition,
+    ///     None).unwrap();
+    /// # }
+    /// ```
+    ///
     /// See [`PartitionKey`](crate::PartitionKey) for more information on how to specify a partition key, and [`Query`] for more information on how to specify a query.
     pub fn query_items<T: DeserializeOwned + Send>(
         &self,
@@ -510,14 +595,222 @@ impl ContainerClient {
         let options = options.unwrap_or_default();
         let url = self.pipeline.url(&self.items_link);
         let mut base_request = Request::new(url, Method::Post);
-        let QueryPartitionStrategy::SinglePartition(partition_key) = partition_key.into();
-        base_request.insert_headers(&partition_key)?;
+        match partition_key.into() {
+            QueryPartitionStrategy::SinglePartition(partition_key) => {
+                base_request.insert_headers(&partition_key)?;
+                self.pipeline.send_query_request(
+                    options.method_options.context,
+                    query.into(),
+                    base_request,
+                    self.items_link.clone(),
+                )
+            }
+            QueryPartitionStrategy::CrossPartition => {
+                self.pipeline.send_cross_partition_query_request(
+                    options.method_options.context,
+                    query.into(),
+                    base_request,
+                    self.link.clone(),
+                    self.items_link.clone(),
+                )
+            }
+        }
+    }
 
-        self.pipeline.send_query_request(
+    /// Lists the partition key ranges of the container.
+    ///
+    /// Each range is a physical partition of the container, and can be used to read the change feed of just that partition
+    /// with [`ChangeFeedOptions::partition_key_range_id`](crate::ChangeFeedOptions::partition_key_range_id).
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    pub async fn read_partition_key_ranges(
+        &self,
+        options: Option<ReadPartitionKeyRangesOptions<'_>>,
+    ) -> azure_core::Result<Vec<PartitionKeyRange>> {
+        let options = options.unwrap_or_default();
+        self.pipeline
+            .read_partition_key_ranges(options.method_options.context, &self.link)
+            .await
+    }
+
+    /// Reads the change feed of the container: the items that were created or updated, in the order they were changed within each partition.
+    ///
+    /// Deletes are not included in the change feed.
+    /// Each page contains changes from a single partition key range, and the pager ends once every range has no further changes.
+    /// Every page carries a continuation token in its `x-ms-continuation` header (see [`constants::CONTINUATION`](crate::constants::CONTINUATION)),
+    /// which can be passed in [`ChangeFeedOptions::continuation`](crate::ChangeFeedOptions::continuation) to resume reading right after that page.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request, including where to start reading.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_data_cosmos::{clients::ContainerClient, constants, ChangeFeedOptions, ChangeFeedStartFrom};
+    /// use futures::StreamExt;
+    ///
+    /// # let container_client: ContainerClient = panic!("this is a non-running example");
+    /// let options = ChangeFeedOptions {
+    ///     start_from: ChangeFeedStartFrom::Now,
+    ///     ..Default::default()
+    /// };
+    /// let mut pager = container_client.read_change_feed::<serde_json::Value>(Some(options)).unwrap();
+    /// let mut continuation = None;
+    /// while let Some(page) = pager.next().await {
+    ///     let page = page.unwrap();
+    ///     continuation = page.headers().get_optional_string(&constants::CONTINUATION);
+    ///     for item in page.deserialize_body().await.unwrap().items {
+    ///         println!("changed: {item}");
+    ///     }
+    /// }
+    /// // Save `continuation` to pick up from here next time.
+    /// # }
+    /// ```
+    pub fn read_change_feed<T: DeserializeOwned + Send>(
+        &self,
+        options: Option<ChangeFeedOptions<'_>>,
+    ) -> azure_core::Result<Pager<QueryResults<T>>> {
+        let options = options.unwrap_or_default();
+        let state = match (&options.continuation, &options.partition_key_range_id) {
+            (Some(continuation), _) => {
+                Some(ChangeFeedState::from_continuation_token(continuation)?)
+            }
+            (None, Some(range_id)) => Some(ChangeFeedState::new(
+                &options.start_from,
+                [range_id.clone()],
+            )),
+            (None, None) => None,
+        };
+
+        self.pipeline.send_change_feed_request(
             options.method_options.context,
-            query.into(),
-            base_request,
+            state,
+            options.start_from,
+            options.max_item_count,
+            self.link.clone(),
             self.items_link.clone(),
         )
     }
 }
+
+/// Recovers the response to a failed batch, which the pipeline returns as an `Err` because it has the failed operation's status code.
+///
+/// Errors whose body is not a list of per-operation results are returned unchanged.
+fn failed_batch_response(error: Error) -> azure_core::Result<Response<TransactionalBatchResponse>> {
+    let Some(http_error) = HttpError::try_from(&error) else {
+        return Err(error);
+    };
+    if serde_json::from_slice::<TransactionalBatchResponse>(http_error.body()).is_err() {
+        return Err(error);
+    }
+    let mut headers = Headers::new();
+    for (name, value) in http_error.headers() {
+        headers.insert(name.clone(), value.clone());
+    }
+    Ok(Response::from_bytes(
+        http_error.status(),
+        headers,
+        http_error.body().clone(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use azure_core::{
+        headers::Headers, Context, Policy, PolicyResult, Request, Response, StatusCode,
+    };
+
+    use super::ContainerClient;
+    use crate::{
+        pipeline::tests::mock_pipeline,
+        resource_context::{ResourceLink, ResourceType},
+        TransactionalBatch,
+    };
+
+    /// Responds to every request with the given status and body.
+    #[derive(Debug)]
+    struct StaticTransport {
+        status: StatusCode,
+        body: &'static str,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for StaticTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let mut headers = Headers::new();
+            headers.insert("x-ms-activity-id", "activity");
+            Ok(Response::from_bytes(self.status, headers, self.body))
+        }
+    }
+
+    fn container_client(status: StatusCode, body: &'static str) -> ContainerClient {
+        let pipeline = mock_pipeline(Arc::new(StaticTransport { status, body }));
+        ContainerClient::new(
+            pipeline,
+            &ResourceLink::root(ResourceType::Databases).item("db"),
+            "container",
+        )
+    }
+
+    fn batch() -> TransactionalBatch {
+        TransactionalBatch::new("customer1")
+            .create_item(serde_json::json!({"id": "order1"}), None)
+            .unwrap()
+            .delete_item("cart1", None)
+    }
+
+    #[tokio::test]
+    pub async fn failed_batch_returns_operation_results() {
+        let client = container_client(
+            StatusCode::Conflict,
+            r#"[{"statusCode":409,"requestCharge":1.2},{"statusCode":424,"requestCharge":0}]"#,
+        );
+
+        let response = client
+            .execute_transactional_batch(batch(), None)
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::Conflict, response.status());
+        assert_eq!(
+            Some("activity"),
+            response
+                .headers()
+                .get_optional_str(&"x-ms-activity-id".into())
+        );
+
+        let response = response.deserialize_body().await.unwrap();
+        assert!(!response.is_success());
+        assert_eq!(
+            vec![409, 424],
+            response
+                .results
+                .iter()
+                .map(|result| result.status_code)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    pub async fn batch_that_does_not_run_is_err() {
+        let client = container_client(
+            StatusCode::Unauthorized,
+            r#"{"code":"Unauthorized","message":"The input authorization token can't serve the request."}"#,
+        );
+
+        let error = client
+            .execute_transactional_batch(batch(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(Some(StatusCode::Unauthorized), error.http_status());
+    }
+}
@@ -8,12 +8,22 @@
 
 use azure_core::{headers::HeaderName, request_options::ContentType};
 
+pub const A_IM: HeaderName = HeaderName::from_static("a-im");
 pub const QUERY: HeaderName = HeaderName::from_static("x-ms-documentdb-query");
 pub const PARTITION_KEY: HeaderName = HeaderName::from_static("x-ms-documentdb-partitionkey");
 pub const CONTINUATION: HeaderName = HeaderName::from_static("x-ms-continuation");
+pub const ENABLE_CROSS_PARTITION_QUERY: HeaderName =
+    HeaderName::from_static("x-ms-documentdb-query-enablecrosspartition");
+pub const PARTITION_KEY_RANGE_ID: HeaderName =
+    HeaderName::from_static("x-ms-documentdb-partitionkeyrangeid");
+pub const SUB_STATUS: HeaderName = HeaderName::from_static("x-ms-substatus");
 pub const INDEX_METRICS: HeaderName = HeaderName::from_static("x-ms-cosmos-index-utilization");
 pub const QUERY_METRICS: HeaderName = HeaderName::from_static("x-ms-documentdb-query-metrics");
 pub const IS_UPSERT: HeaderName = HeaderName::from_static("x-ms-documentdb-is-upsert");
+pub const IS_BATCH_REQUEST: HeaderName = HeaderName::from_static("x-ms-cosmos-is-batch-request");
+pub const BATCH_ATOMIC: HeaderName = HeaderName::from_static("x-ms-cosmos-batch-atomic");
+pub const BATCH_CONTINUE_ON_ERROR: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-batch-continue-on-error");
 pub const OFFER_THROUGHPUT: HeaderName = HeaderName::from_static("x-ms-offer-throughput");
 pub const OFFER_AUTOPILOT_SETTINGS: HeaderName =
     HeaderName::from_static("x-ms-cosmos-offer-autopilot-settings");
@@ -17,6 +17,7 @@ mod partition_key;
 pub(crate) mod pipeline;
 mod query;
 pub(crate) mod resource_context;
+mod transactional_batch;
 pub(crate) mod utils;
 
 pub mod models;
@@ -27,3 +28,4 @@ pub use clients::CosmosClient;
 pub use options::*;
 pub use partition_key::*;
 pub use query::*;
+pub use transactional_batch::*;
@@ -10,15 +10,19 @@ mod container_properties;
 mod indexing_policy;
 mod item;
 mod partition_key_definition;
+mod partition_key_range;
 mod patch_operations;
 mod throughput_properties;
+mod transactional_batch_response;
 
 pub use container_properties::*;
 pub use indexing_policy::*;
 pub use item::*;
 pub use partition_key_definition::*;
+pub use partition_key_range::*;
 pub use patch_operations::*;
 pub use throughput_properties::*;
+pub use transactional_batch_response::*;
 
 fn deserialize_cosmos_timestamp<'de, D>(deserializer: D) -> Result<Option<OffsetDateTime>, D::Error>
 where
@@ -39,7 +43,7 @@ where
     }
 }
 
-/// A page of query results from [`ContainerClient::query_items`](crate::clients::ContainerClient::query_items()) where each item is of type `T`.
+/// A page of query results from [`ContainerClient::query_items`](crate::clients::ContainerClient::query_items()), or of changes from [`ContainerClient::read_change_feed`](crate::clients::ContainerClient::read_change_feed()), where each item is of type `T`.
 #[non_exhaustive]
 #[derive(Clone, Default, Debug, Deserialize)]
 pub struct QueryResults<T> {
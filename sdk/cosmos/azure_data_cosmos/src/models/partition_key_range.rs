@@ -0,0 +1,25 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use serde::Deserialize;
+
+/// A physical partition of a container, identified by a range of effective partition key values.
+///
+/// Returned by [`ContainerClient::read_partition_key_ranges()`](crate::clients::ContainerClient::read_partition_key_ranges()).
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionKeyRange {
+    /// The ID of the range.
+    pub id: String,
+
+    /// The lowest effective partition key in the range.
+    pub min_inclusive: String,
+
+    /// The effective partition key just past the end of the range.
+    pub max_exclusive: String,
+
+    /// The IDs of the ranges this range was split from, if any.
+    #[serde(default)]
+    pub parents: Vec<String>,
+}
@@ -0,0 +1,138 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{Etag, Model, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// The results of a [`TransactionalBatch`](crate::TransactionalBatch), with one entry per operation, in the order the operations were added.
+///
+/// If any operation failed, none of the operations were applied.
+/// The failed operation reports its own status code, and every other operation reports `424 Failed Dependency`.
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, Deserialize)]
+#[serde(transparent)]
+pub struct TransactionalBatchResponse {
+    pub results: Vec<TransactionalBatchOperationResult>,
+}
+
+impl TransactionalBatchResponse {
+    /// Returns `true` if every operation in the batch succeeded and the batch was committed.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|result| result.is_success())
+    }
+}
+
+// The service returns a bare JSON array, so we deserialize the body ourselves rather than deriving `Model`.
+impl Model for TransactionalBatchResponse {
+    async fn from_response_body(
+        body: azure_core::ResponseBody,
+    ) -> typespec_client_core::Result<Self> {
+        body.json().await
+    }
+}
+
+/// The result of a single operation in a [`TransactionalBatch`](crate::TransactionalBatch).
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionalBatchOperationResult {
+    /// The HTTP status code of the operation.
+    pub status_code: u16,
+
+    /// A more specific code for failures, if any.
+    #[serde(default)]
+    pub sub_status_code: Option<u32>,
+
+    /// The entity tag of the item after the operation, if the operation returns an item.
+    #[serde(default, rename = "eTag")]
+    pub etag: Option<Etag>,
+
+    /// The request units consumed by the operation.
+    #[serde(default)]
+    pub request_charge: Option<f64>,
+
+    /// The item returned by the operation, if any.
+    #[serde(default)]
+    pub resource_body: Option<serde_json::Value>,
+}
+
+impl TransactionalBatchOperationResult {
+    /// Returns the status code of the operation as a [`StatusCode`], if it is a known status code.
+    pub fn status(&self) -> Option<StatusCode> {
+        StatusCode::try_from(self.status_code).ok()
+    }
+
+    /// Returns `true` if the operation succeeded.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status_code)
+    }
+
+    /// Deserializes the item returned by the operation into `T`.
+    ///
+    /// Returns `Ok(None)` if the operation did not return an item.
+    pub fn deserialize_body<T: DeserializeOwned>(&self) -> azure_core::Result<Option<T>> {
+        self.resource_body
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use azure_core::StatusCode;
+    use serde::Deserialize;
+
+    use super::TransactionalBatchResponse;
+
+    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    struct Order {
+        id: String,
+    }
+
+    #[test]
+    pub fn deserialize_successful_batch() {
+        let response: TransactionalBatchResponse = serde_json::from_str(
+            r#"[
+                {"statusCode":201,"requestCharge":6.2,"eTag":"\"0100\"","resourceBody":{"id":"order1"}},
+                {"statusCode":204,"requestCharge":4.1}
+            ]"#,
+        )
+        .unwrap();
+
+        assert!(response.is_success());
+        assert_eq!(response.results[0].status(), Some(StatusCode::Created));
+        assert_eq!(
+            response.results[0].deserialize_body::<Order>().unwrap(),
+            Some(Order {
+                id: "order1".into()
+            })
+        );
+        assert_eq!(
+            response.results[1].deserialize_body::<Order>().unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    pub fn deserialize_failed_batch() {
+        let response: TransactionalBatchResponse = serde_json::from_str(
+            r#"[
+                {"statusCode":424,"requestCharge":0},
+                {"statusCode":412,"subStatusCode":0,"requestCharge":1.2}
+            ]"#,
+        )
+        .unwrap();
+
+        assert!(!response.is_success());
+        assert_eq!(
+            response.results[0].status(),
+            Some(StatusCode::FailedDependency)
+        );
+        assert_eq!(
+            response.results[1].status(),
+            Some(StatusCode::PreconditionFailed)
+        );
+    }
+}
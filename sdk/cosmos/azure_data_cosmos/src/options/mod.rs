@@ -1,10 +1,45 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use azure_core::{ClientMethodOptions, ClientOptions};
+use azure_core::{date::OffsetDateTime, ClientMethodOptions, ClientOptions, Etag};
 
 use crate::models::ThroughputProperties;
 
+/// Where a change feed starts when it is not resumed from a continuation token.
+#[derive(Clone, Debug, Default)]
+pub enum ChangeFeedStartFrom {
+    /// Read every change since the container was created.
+    #[default]
+    Beginning,
+
+    /// Read only the changes made after the first request to each partition.
+    Now,
+
+    /// Read the changes made since the specified time.
+    PointInTime(OffsetDateTime),
+}
+
+/// Options to be passed to [`ContainerClient::read_change_feed()`](crate::clients::ContainerClient::read_change_feed()).
+#[derive(Clone, Default)]
+pub struct ChangeFeedOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+
+    /// Where to start reading. Ignored when `continuation` is set.
+    pub start_from: ChangeFeedStartFrom,
+
+    /// Resume the change feed from a token returned in the `x-ms-continuation` header of a previous page.
+    pub continuation: Option<String>,
+
+    /// Read only the changes in a single partition key range, instead of the whole container.
+    ///
+    /// Use [`ContainerClient::read_partition_key_ranges()`](crate::clients::ContainerClient::read_partition_key_ranges()) to list the ranges of a container.
+    /// Ignored when `continuation` is set.
+    pub partition_key_range_id: Option<String>,
+
+    /// The maximum number of items returned in each page.
+    pub max_item_count: Option<u32>,
+}
+
 /// Options used when creating a [`CosmosClient`](crate::CosmosClient).
 #[derive(Clone, Default)]
 pub struct CosmosClientOptions {
@@ -61,6 +96,12 @@ pub struct QueryOptions<'a> {
     pub method_options: ClientMethodOptions<'a>,
 }
 
+/// Options to be passed to [`ContainerClient::read_partition_key_ranges()`](crate::clients::ContainerClient::read_partition_key_ranges()).
+#[derive(Clone, Default)]
+pub struct ReadPartitionKeyRangesOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+}
+
 /// Options to be passed to [`ContainerClient::read()`](crate::clients::ContainerClient::read()).
 #[derive(Clone, Default)]
 pub struct ReadContainerOptions<'a> {
@@ -78,3 +119,18 @@ pub struct ReadDatabaseOptions<'a> {
 pub struct ThroughputOptions<'a> {
     pub method_options: ClientMethodOptions<'a>,
 }
+
+/// Options to be passed to [`ContainerClient::execute_transactional_batch()`](crate::clients::ContainerClient::execute_transactional_batch()).
+#[derive(Clone, Default)]
+pub struct TransactionalBatchOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+}
+
+/// Options for a single operation in a [`TransactionalBatch`](crate::TransactionalBatch).
+#[derive(Clone, Debug, Default)]
+pub struct TransactionalBatchItemOptions {
+    /// Only apply the operation if the item's current entity tag matches this value.
+    ///
+    /// If it does not match, the operation fails with `412 Precondition Failed` and the whole batch is rolled back.
+    pub if_match: Option<Etag>,
+}
@@ -7,13 +7,22 @@ use crate::constants;
 
 /// Describes the partition strategy that will be used when querying.
 ///
-/// Currently, the only supported strategy is [`QueryPartitionStrategy::SinglePartition`], which executes the query against a single partition, specified by the [`PartitionKey`] provided.
+/// Two strategies are supported:
+///
+/// * [`QueryPartitionStrategy::SinglePartition`], which executes the query against a single partition, specified by the [`PartitionKey`] provided.
+/// * [`QueryPartitionStrategy::CrossPartition`], which executes the query against every partition in the container, one after the other.
 ///
 /// [`QueryPartitionStrategy`] implements [`From`] for any type that is convertible to a `PartitionKey`.
 /// This allows you to use any of the syntaxes specified in the [`PartitionKey`] docs any place an [`Into<QueryPartitionStrategy>`] is expected.
 #[derive(Debug, Clone)]
 pub enum QueryPartitionStrategy {
     SinglePartition(PartitionKey),
+
+    /// Executes the query against each partition key range of the container in turn.
+    ///
+    /// Each page of results comes from a single partition key range.
+    /// Queries that need results to be combined across partitions, such as those using `ORDER BY`, `GROUP BY`, `DISTINCT`, aggregates or `OFFSET`/`LIMIT`, are not supported.
+    CrossPartition,
 }
 
 impl<T: Into<PartitionKey>> From<T> for QueryPartitionStrategy {
@@ -296,7 +305,9 @@ mod tests {
     /// Validates that a given value is `impl Into<QueryPartitionStrategy>` and works as-expected.
     fn key_to_single_partition_strategy_string(v: impl Into<QueryPartitionStrategy>) -> String {
         let strategy = v.into();
-        let QueryPartitionStrategy::SinglePartition(key) = strategy;
+        let QueryPartitionStrategy::SinglePartition(key) = strategy else {
+            panic!("expected a single-partition strategy");
+        };
         key_to_string(key)
     }
 
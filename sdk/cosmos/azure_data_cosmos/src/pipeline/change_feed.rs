@@ -0,0 +1,443 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Reading the change feed of a container.
+//!
+//! The change feed is read one partition key range at a time. The service reports the position reached in each range
+//! as an `ETag`, which is sent back in `If-None-Match` to read the next changes, and answers `304 Not Modified` once
+//! the range has no more changes. The positions of all ranges make up the continuation token of the feed.
+
+use std::collections::{HashMap, VecDeque};
+
+use azure_core::{
+    base64, date,
+    error::{ErrorKind, HttpError, ResultExt},
+    headers::{
+        HeaderName, HeaderValue, Headers, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, MAX_ITEM_COUNT,
+    },
+    json::{from_json, to_json},
+    Context, Method, Pager, Request, Response, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use typespec_client_core::http::PagerResult;
+
+use crate::{
+    constants,
+    pipeline::{cross_partition::is_partition_split, CosmosPipeline},
+    resource_context::ResourceLink,
+    ChangeFeedStartFrom,
+};
+
+/// The `If-None-Match` value that starts reading a range from the current time.
+const START_FROM_NOW: &str = "*";
+
+/// The body of the page yielded once every range has been read to its end.
+const EMPTY_PAGE: &str = r#"{"Documents":[]}"#;
+
+/// The position reached in each partition key range, serialized into the change feed continuation token.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeFeedState {
+    /// Sent as `If-Modified-Since` for ranges that have not been read yet, when starting from a point in time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    if_modified_since: Option<String>,
+
+    ranges: VecDeque<RangePosition>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RangePosition {
+    id: String,
+
+    /// The `ETag` returned by the latest read of this range, or `None` if the range has not been read yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+}
+
+impl ChangeFeedState {
+    /// Creates the state for reading the given ranges, none of which have been read yet.
+    pub fn new(
+        start_from: &ChangeFeedStartFrom,
+        range_ids: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let etag = match start_from {
+            ChangeFeedStartFrom::Now => Some(START_FROM_NOW.to_string()),
+            _ => None,
+        };
+        let if_modified_since = match start_from {
+            ChangeFeedStartFrom::PointInTime(time) => Some(date::to_rfc1123(time)),
+            _ => None,
+        };
+        Self {
+            if_modified_since,
+            ranges: range_ids
+                .into_iter()
+                .map(|id| RangePosition {
+                    id,
+                    etag: etag.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Restores the state from a token produced by [`ChangeFeedState::continuation_token`].
+    pub fn from_continuation_token(token: &str) -> azure_core::Result<Self> {
+        base64::decode(token).and_then(from_json).context(
+            ErrorKind::DataConversion,
+            "invalid change feed continuation token",
+        )
+    }
+
+    pub fn continuation_token(&self) -> azure_core::Result<String> {
+        Ok(base64::encode(to_json(self)?))
+    }
+}
+
+/// The position of a change feed [`Pager`] between pages.
+struct ChangeFeedCursor {
+    state: ChangeFeedState,
+
+    /// The number of ranges, at the front of `state.ranges`, that have not been read to their end in this pass.
+    pending: usize,
+}
+
+impl CosmosPipeline {
+    /// Reads the change feed of a container, starting from `state`, or from every current partition key range if `state` is `None`.
+    ///
+    /// Each range is read until the service reports no further changes, and the pager ends once every range has been read to its end,
+    /// with an empty `304 Not Modified` page. Every page carries, in its `x-ms-continuation` header, a token that resumes the feed right after that page.
+    pub fn send_change_feed_request<T: DeserializeOwned>(
+        &self,
+        ctx: Context<'_>,
+        state: Option<ChangeFeedState>,
+        start_from: ChangeFeedStartFrom,
+        max_item_count: Option<u32>,
+        container_link: ResourceLink,
+        items_link: ResourceLink,
+    ) -> azure_core::Result<Pager<T>> {
+        let mut base_request = Request::new(self.url(&items_link), Method::Get);
+        base_request.insert_header(constants::A_IM, "Incremental feed");
+        if let Some(max_item_count) = max_item_count {
+            base_request.insert_header(MAX_ITEM_COUNT, max_item_count.to_string());
+        }
+
+        let pipeline = self.clone();
+        let ctx = ctx.into_owned();
+        let initial_state = state;
+        Ok(Pager::from_callback(
+            move |cursor: Option<ChangeFeedCursor>| {
+                let pipeline = pipeline.clone();
+                let base_request = base_request.clone();
+                let ctx = ctx.clone();
+                let container_link = container_link.clone();
+                let items_link = items_link.clone();
+                let initial_state = initial_state.clone();
+                let start_from = start_from.clone();
+                async move {
+                    let ChangeFeedCursor {
+                        mut state,
+                        mut pending,
+                    } = match cursor {
+                        Some(cursor) => cursor,
+                        None => {
+                            let state = match initial_state {
+                                Some(state) => state,
+                                None => ChangeFeedState::new(
+                                    &start_from,
+                                    pipeline
+                                        .read_partition_key_ranges(ctx.clone(), &container_link)
+                                        .await?
+                                        .into_iter()
+                                        .map(|range| range.id),
+                                ),
+                            };
+                            let pending = state.ranges.len();
+                            ChangeFeedCursor { state, pending }
+                        }
+                    };
+
+                    loop {
+                        let Some(position) = state.ranges.front().cloned() else {
+                            return Err(azure_core::Error::message(
+                                ErrorKind::DataConversion,
+                                "the change feed has no partition key ranges",
+                            ));
+                        };
+
+                        let mut req = base_request.clone();
+                        req.insert_header(constants::PARTITION_KEY_RANGE_ID, position.id.clone());
+                        match (&position.etag, &state.if_modified_since) {
+                            (Some(etag), _) => req.insert_header(IF_NONE_MATCH, etag.clone()),
+                            (None, Some(since)) => {
+                                req.insert_header(IF_MODIFIED_SINCE, since.clone())
+                            }
+                            (None, None) => {}
+                        }
+
+                        let (status, mut headers, body) = match pipeline
+                            .send::<()>(ctx.clone(), &mut req, items_link.clone())
+                            .await
+                        {
+                            Ok(response) => {
+                                let (status, headers, body) = response.deconstruct();
+                                (status, headers, body.collect().await?)
+                            }
+                            Err(err) => match not_modified_headers(&err) {
+                                Some(headers) => {
+                                    (StatusCode::NotModified, headers, EMPTY_PAGE.into())
+                                }
+                                None if is_partition_split(&err) => {
+                                    let children: Vec<_> = pipeline
+                                        .read_partition_key_ranges(ctx.clone(), &container_link)
+                                        .await?
+                                        .into_iter()
+                                        .filter(|range| range.parents.contains(&position.id))
+                                        .collect();
+                                    if children.is_empty() {
+                                        return Err(err);
+                                    }
+
+                                    // The parent's position is valid on each of its children.
+                                    state.ranges.pop_front();
+                                    pending += children.len() - 1;
+                                    for range in children.into_iter().rev() {
+                                        state.ranges.push_front(RangePosition {
+                                            id: range.id,
+                                            etag: position.etag.clone(),
+                                        });
+                                    }
+                                    continue;
+                                }
+                                None => return Err(err),
+                            },
+                        };
+
+                        // The range is now positioned after the changes in this response.
+                        if let Some(etag) = headers.get_optional_string(&ETAG) {
+                            state.ranges[0].etag = Some(etag);
+                        }
+
+                        if status == StatusCode::NotModified {
+                            // This range is caught up, so move on to the next one.
+                            state.ranges.rotate_left(1);
+                            pending -= 1;
+                            if pending > 0 {
+                                continue;
+                            }
+                        }
+
+                        headers.insert(constants::CONTINUATION, state.continuation_token()?);
+                        let response: Response<T> = Response::from_bytes(status, headers, body);
+                        return Ok(if pending == 0 {
+                            PagerResult::Complete { response }
+                        } else {
+                            PagerResult::Continue {
+                                response,
+                                continuation: ChangeFeedCursor { state, pending },
+                            }
+                        });
+                    }
+                }
+            },
+        ))
+    }
+}
+
+/// Returns the headers of a `304 Not Modified` response, which the pipeline reports as an error.
+fn not_modified_headers(error: &azure_core::Error) -> Option<Headers> {
+    let http_error = HttpError::try_from(error)?;
+    if http_error.status() != StatusCode::NotModified {
+        return None;
+    }
+    let headers: HashMap<HeaderName, HeaderValue> = http_error
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                HeaderName::from(name.clone()),
+                HeaderValue::from(value.clone()),
+            )
+        })
+        .collect();
+    Some(headers.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use azure_core::{Policy, PolicyResult};
+    use futures::StreamExt;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        models::QueryResults, pipeline::tests::mock_pipeline, resource_context::ResourceType,
+    };
+
+    /// Simulates a container with two ranges: range "0" has one page of changes, and range "1" has none.
+    #[derive(Debug, Default)]
+    struct MockContainer {
+        requests: Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for MockContainer {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn azure_core::Policy>],
+        ) -> PolicyResult {
+            if request.url().path().ends_with("/pkranges") {
+                return Ok(Response::from_bytes(
+                    StatusCode::Ok,
+                    Headers::new(),
+                    r#"{"PartitionKeyRanges":[
+                        {"id":"1","minInclusive":"80","maxExclusive":"FF"},
+                        {"id":"0","minInclusive":"","maxExclusive":"80"}]}"#,
+                ));
+            }
+
+            assert_eq!(
+                request
+                    .headers()
+                    .get_optional_string(&constants::A_IM)
+                    .as_deref(),
+                Some("Incremental feed")
+            );
+            let range = request
+                .headers()
+                .get_optional_string(&constants::PARTITION_KEY_RANGE_ID)
+                .unwrap();
+            let if_none_match = request.headers().get_optional_string(&IF_NONE_MATCH);
+            self.requests
+                .lock()
+                .unwrap()
+                .push((range.clone(), if_none_match.clone()));
+
+            let mut headers = Headers::new();
+            let (status, body) = match (range.as_str(), if_none_match.as_deref()) {
+                ("0", None) => {
+                    headers.insert(ETAG, "\"10\"");
+                    (StatusCode::Ok, r#"{"Documents":[{"n":1},{"n":2}]}"#)
+                }
+                ("0", Some("\"10\"")) => {
+                    headers.insert(ETAG, "\"10\"");
+                    (StatusCode::NotModified, "")
+                }
+                ("1", None | Some("\"5\"")) => {
+                    headers.insert(ETAG, "\"5\"");
+                    (StatusCode::NotModified, "")
+                }
+                other => panic!("unexpected request {other:?}"),
+            };
+            Ok(Response::from_bytes(status, headers, body))
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Doc {
+        n: u32,
+    }
+
+    fn read(pipeline: &CosmosPipeline, state: Option<ChangeFeedState>) -> Pager<QueryResults<Doc>> {
+        let container_link = ResourceLink::root(ResourceType::Databases)
+            .item("db")
+            .feed(ResourceType::Containers)
+            .item("container");
+        let items_link = container_link.feed(ResourceType::Items);
+        pipeline
+            .send_change_feed_request(
+                Context::new(),
+                state,
+                ChangeFeedStartFrom::Beginning,
+                None,
+                container_link,
+                items_link,
+            )
+            .unwrap()
+    }
+
+    async fn collect_pages(pager: Pager<QueryResults<Doc>>) -> Vec<(StatusCode, Vec<u32>, String)> {
+        pager
+            .then(|page| async move {
+                let page = page.unwrap();
+                let status = page.status();
+                let continuation = page
+                    .headers()
+                    .get_optional_string(&constants::CONTINUATION)
+                    .unwrap();
+                let items = page.deserialize_body().await.unwrap().items;
+                (
+                    status,
+                    items.into_iter().map(|doc| doc.n).collect(),
+                    continuation,
+                )
+            })
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    pub async fn reads_every_range_until_caught_up_and_resumes() {
+        let container = Arc::new(MockContainer::default());
+        let pipeline = mock_pipeline(container.clone());
+
+        let pages = collect_pages(read(&pipeline, None)).await;
+        assert_eq!(pages.len(), 2);
+        assert_eq!(
+            (pages[0].0, pages[0].1.clone()),
+            (StatusCode::Ok, vec![1, 2])
+        );
+        assert_eq!(
+            (pages[1].0, pages[1].1.clone()),
+            (StatusCode::NotModified, vec![])
+        );
+        assert_eq!(
+            *container.requests.lock().unwrap(),
+            vec![
+                ("0".to_string(), None),
+                ("0".to_string(), Some("\"10\"".to_string())),
+                ("1".to_string(), None),
+            ]
+        );
+
+        // Resuming from the last token finds no new changes.
+        container.requests.lock().unwrap().clear();
+        let state = ChangeFeedState::from_continuation_token(&pages[1].2).unwrap();
+        let pages = collect_pages(read(&pipeline, Some(state))).await;
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            *container.requests.lock().unwrap(),
+            vec![
+                ("0".to_string(), Some("\"10\"".to_string())),
+                ("1".to_string(), Some("\"5\"".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn start_from_sets_initial_position() {
+        let now = ChangeFeedState::new(&ChangeFeedStartFrom::Now, ["0".to_string()]);
+        assert_eq!(now.ranges[0].etag.as_deref(), Some(START_FROM_NOW));
+        assert!(now.if_modified_since.is_none());
+
+        let since = ChangeFeedState::new(
+            &ChangeFeedStartFrom::PointInTime(date::parse_rfc3339("2024-10-16T00:00:00Z").unwrap()),
+            ["0".to_string()],
+        );
+        assert!(since.ranges[0].etag.is_none());
+        assert_eq!(
+            since.if_modified_since.as_deref(),
+            Some("Wed, 16 Oct 2024 00:00:00 GMT")
+        );
+    }
+
+    #[test]
+    pub fn invalid_continuation_token() {
+        let err = ChangeFeedState::from_continuation_token("not a token").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+    }
+}
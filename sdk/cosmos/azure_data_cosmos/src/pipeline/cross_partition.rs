@@ -0,0 +1,333 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Fan-out of queries that are not scoped to a single partition key.
+//!
+//! The Gateway can only execute a query against one physical partition at a time, so a cross-partition query
+//! is executed against each partition key range in turn, with a separate continuation token for each range.
+
+use std::collections::VecDeque;
+
+use azure_core::{
+    error::{Error, ErrorKind, HttpError},
+    Context, Method, Pager, Request, Response, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use typespec_client_core::http::PagerResult;
+
+use crate::{
+    constants,
+    models::PartitionKeyRange,
+    pipeline::CosmosPipeline,
+    resource_context::{ResourceLink, ResourceType},
+    Query,
+};
+
+/// Sub-status codes the service returns alongside `410 Gone` when a partition key range has been split or merged.
+const PARTITION_KEY_RANGE_GONE: &str = "1002";
+const COMPLETING_SPLIT: &str = "1007";
+
+/// The maximum number of times the ranges are re-read while fetching a single page, in case of repeated splits.
+const MAX_RANGE_REFRESHES: usize = 3;
+
+#[derive(Deserialize)]
+struct PartitionKeyRanges {
+    #[serde(rename = "PartitionKeyRanges")]
+    partition_key_ranges: Vec<PartitionKeyRange>,
+}
+
+/// The progress of a cross-partition query within a single partition key range.
+#[derive(Clone, Debug)]
+struct RangeCursor {
+    range: PartitionKeyRange,
+    continuation: Option<String>,
+}
+
+impl CosmosPipeline {
+    /// Reads the current partition key ranges of a container, ordered by their minimum effective partition key.
+    pub async fn read_partition_key_ranges(
+        &self,
+        ctx: Context<'_>,
+        container_link: &ResourceLink,
+    ) -> azure_core::Result<Vec<PartitionKeyRange>> {
+        let link = container_link.feed(ResourceType::PartitionKeyRanges);
+        let mut ranges = Vec::new();
+        let mut continuation = None;
+        loop {
+            let mut req = Request::new(self.url(&link), Method::Get);
+            if let Some(continuation) = continuation {
+                req.insert_header(constants::CONTINUATION, continuation);
+            }
+            let resp: Response = self.send(ctx.clone(), &mut req, link.clone()).await?;
+            continuation = resp.headers().get_optional_string(&constants::CONTINUATION);
+            let page: PartitionKeyRanges = resp.into_body().json().await?;
+            ranges.extend(page.partition_key_ranges);
+            if continuation.is_none() {
+                break;
+            }
+        }
+
+        // Effective partition keys are hex strings, so they sort lexicographically.
+        ranges.sort_by(|a, b| a.min_inclusive.cmp(&b.min_inclusive));
+        Ok(ranges)
+    }
+
+    /// Executes a query against every partition key range of a container, one range after the other.
+    ///
+    /// Each page of the resulting [`Pager`] comes from a single range.
+    /// If a range is split while the query is running, the query continues on the child ranges from where the parent left off.
+    pub fn send_cross_partition_query_request<T: DeserializeOwned>(
+        &self,
+        ctx: Context<'_>,
+        query: Query,
+        mut base_request: Request,
+        container_link: ResourceLink,
+        items_link: ResourceLink,
+    ) -> azure_core::Result<Pager<T>> {
+        base_request.insert_header(constants::QUERY, "True");
+        base_request.insert_header(constants::ENABLE_CROSS_PARTITION_QUERY, "True");
+        base_request.add_mandatory_header(&constants::QUERY_CONTENT_TYPE);
+        base_request.set_json(&query)?;
+
+        let pipeline = self.clone();
+        let ctx = ctx.into_owned();
+        Ok(Pager::from_callback(
+            move |cursors: Option<VecDeque<RangeCursor>>| {
+                let pipeline = pipeline.clone();
+                let base_request = base_request.clone();
+                let ctx = ctx.clone();
+                let container_link = container_link.clone();
+                let items_link = items_link.clone();
+                async move {
+                    let mut cursors = match cursors {
+                        Some(cursors) => cursors,
+                        None => pipeline
+                            .read_partition_key_ranges(ctx.clone(), &container_link)
+                            .await?
+                            .into_iter()
+                            .map(|range| RangeCursor {
+                                range,
+                                continuation: None,
+                            })
+                            .collect(),
+                    };
+
+                    let mut refreshes = 0;
+                    loop {
+                        let cursor = cursors.pop_front().ok_or_else(|| {
+                            Error::message(
+                                ErrorKind::DataConversion,
+                                "the container has no partition key ranges",
+                            )
+                        })?;
+
+                        let mut req = base_request.clone();
+                        req.insert_header(
+                            constants::PARTITION_KEY_RANGE_ID,
+                            cursor.range.id.clone(),
+                        );
+                        if let Some(continuation) = &cursor.continuation {
+                            req.insert_header(constants::CONTINUATION, continuation.clone());
+                        }
+
+                        // Matching directly on the awaited result keeps the (possibly non-`Send`) response out of the rest of the future.
+                        let err = match pipeline
+                            .send::<T>(ctx.clone(), &mut req, items_link.clone())
+                            .await
+                        {
+                            Ok(response) => {
+                                if let Some(continuation) = response
+                                    .headers()
+                                    .get_optional_string(&constants::CONTINUATION)
+                                {
+                                    cursors.push_front(RangeCursor {
+                                        range: cursor.range,
+                                        continuation: Some(continuation),
+                                    });
+                                }
+                                return Ok(if cursors.is_empty() {
+                                    PagerResult::Complete { response }
+                                } else {
+                                    PagerResult::Continue {
+                                        response,
+                                        continuation: cursors,
+                                    }
+                                });
+                            }
+                            Err(err) => err,
+                        };
+                        if !is_partition_split(&err) || refreshes >= MAX_RANGE_REFRESHES {
+                            return Err(err);
+                        }
+
+                        refreshes += 1;
+                        let children: Vec<_> = pipeline
+                            .read_partition_key_ranges(ctx.clone(), &container_link)
+                            .await?
+                            .into_iter()
+                            .filter(|range| range.parents.contains(&cursor.range.id))
+                            .collect();
+                        if children.is_empty() {
+                            return Err(err);
+                        }
+
+                        // The parent's continuation token is valid on each of its children.
+                        for range in children.into_iter().rev() {
+                            cursors.push_front(RangeCursor {
+                                range,
+                                continuation: cursor.continuation.clone(),
+                            });
+                        }
+                    }
+                }
+            },
+        ))
+    }
+}
+
+/// Checks if an error indicates that the partition key range targeted by the request no longer exists.
+pub(super) fn is_partition_split(error: &Error) -> bool {
+    let Some(http_error) = HttpError::try_from(error) else {
+        return false;
+    };
+    http_error.status() == StatusCode::Gone
+        && matches!(
+            http_error
+                .headers()
+                .get(constants::SUB_STATUS.as_str())
+                .map(String::as_str),
+            Some(PARTITION_KEY_RANGE_GONE | COMPLETING_SPLIT)
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use azure_core::{
+        headers::{HeaderName, Headers},
+        Policy, PolicyResult,
+    };
+    use futures::StreamExt;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{models::QueryResults, pipeline::tests::mock_pipeline};
+
+    /// Simulates a container whose range "0" is split into ranges "1" and "2" once the first page has been read.
+    #[derive(Debug, Default)]
+    struct MockContainer {
+        split: Mutex<bool>,
+        queries: Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    impl MockContainer {
+        fn ranges(&self) -> &'static str {
+            if *self.split.lock().unwrap() {
+                r#"{"PartitionKeyRanges":[
+                    {"id":"2","minInclusive":"80","maxExclusive":"FF","parents":["0"]},
+                    {"id":"1","minInclusive":"","maxExclusive":"80","parents":["0"]}]}"#
+            } else {
+                r#"{"PartitionKeyRanges":[{"id":"0","minInclusive":"","maxExclusive":"FF"}]}"#
+            }
+        }
+    }
+
+    fn header(request: &Request, name: HeaderName) -> Option<String> {
+        request.headers().get_optional_string(&name)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for MockContainer {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            if request.url().path().ends_with("/pkranges") {
+                return Ok(Response::from_bytes(
+                    StatusCode::Ok,
+                    Headers::new(),
+                    self.ranges(),
+                ));
+            }
+
+            assert_eq!(
+                header(request, constants::ENABLE_CROSS_PARTITION_QUERY).as_deref(),
+                Some("True")
+            );
+            let range = header(request, constants::PARTITION_KEY_RANGE_ID).unwrap();
+            let continuation = header(request, constants::CONTINUATION);
+            self.queries
+                .lock()
+                .unwrap()
+                .push((range.clone(), continuation.clone()));
+
+            let mut headers = Headers::new();
+            let body = match (range.as_str(), continuation.as_deref()) {
+                ("0", None) => {
+                    *self.split.lock().unwrap() = true;
+                    headers.insert(constants::CONTINUATION, "page-2");
+                    r#"{"Documents":[{"n":1}]}"#
+                }
+                ("0", Some(_)) => {
+                    headers.insert(constants::SUB_STATUS, PARTITION_KEY_RANGE_GONE);
+                    return Ok(Response::from_bytes(
+                        StatusCode::Gone,
+                        headers,
+                        r#"{"code":"Gone"}"#,
+                    ));
+                }
+                ("1", Some("page-2")) => r#"{"Documents":[{"n":2}]}"#,
+                ("2", Some("page-2")) => r#"{"Documents":[{"n":3}]}"#,
+                other => panic!("unexpected query {other:?}"),
+            };
+            Ok(Response::from_bytes(StatusCode::Ok, headers, body))
+        }
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    struct Doc {
+        n: u32,
+    }
+
+    #[tokio::test]
+    pub async fn cross_partition_query_follows_splits() {
+        let container = Arc::new(MockContainer::default());
+        let pipeline = mock_pipeline(container.clone());
+        let container_link = ResourceLink::root(ResourceType::Databases)
+            .item("db")
+            .feed(ResourceType::Containers)
+            .item("container");
+        let items_link = container_link.feed(ResourceType::Items);
+
+        let pager: Pager<QueryResults<Doc>> = pipeline
+            .send_cross_partition_query_request(
+                Context::new(),
+                Query::from("SELECT * FROM c"),
+                Request::new(pipeline.url(&items_link), Method::Post),
+                container_link,
+                items_link,
+            )
+            .unwrap();
+        let items: Vec<u32> = pager
+            .then(|page| async move { page.unwrap().deserialize_body().await.unwrap().items })
+            .flat_map(futures::stream::iter)
+            .map(|doc| doc.n)
+            .collect()
+            .await;
+
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(
+            *container.queries.lock().unwrap(),
+            vec![
+                ("0".to_string(), None),
+                ("0".to_string(), Some("page-2".to_string())),
+                ("1".to_string(), Some("page-2".to_string())),
+                ("2".to_string(), Some("page-2".to_string())),
+            ]
+        );
+    }
+}
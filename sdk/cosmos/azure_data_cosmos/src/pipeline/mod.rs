@@ -2,17 +2,25 @@
 // Licensed under the MIT License.
 
 mod authorization_policy;
+mod change_feed;
+mod cross_partition;
+mod retry_classifier;
 mod signature_target;
 
 use std::sync::Arc;
 
 pub use authorization_policy::AuthorizationPolicy;
-use azure_core::{ClientOptions, Context, Method, Model, Pager, Request, Response};
+use azure_core::{
+    AsClientOptions, ClientOptions, Context, Method, Model, Pager, Request, Response,
+};
+pub use change_feed::ChangeFeedState;
 use futures::StreamExt;
 use serde::{de::DeserializeOwned, Deserialize};
 use typespec_client_core::http::PagerResult;
 use url::Url;
 
+use retry_classifier::CosmosRetryClassifier;
+
 use crate::{
     constants,
     models::ThroughputProperties,
@@ -31,11 +39,20 @@ impl CosmosPipeline {
     pub fn new(
         endpoint: Url,
         auth_policy: AuthorizationPolicy,
-        client_options: ClientOptions,
+        mut client_options: ClientOptions,
     ) -> Self {
+        // Retry the service's transient failures too, unless the caller chose which failures to retry,
+        // either with a classifier or with a custom retry policy.
+        let mut retry = client_options.retry().cloned().unwrap_or_default();
+        if retry.classifier().is_none() && !retry.is_custom() {
+            retry.set_classifier(Arc::new(CosmosRetryClassifier::default()));
+            client_options.set_retry(retry);
+        }
+
         CosmosPipeline {
             endpoint,
-            pipeline: azure_core::Pipeline::new(
+            pipeline: azure_core::Pipeline::new_with_namespace(
+                Some("Microsoft.DocumentDB"),
                 option_env!("CARGO_PKG_NAME"),
                 option_env!("CARGO_PKG_VERSION"),
                 client_options,
@@ -183,3 +200,57 @@ impl CosmosPipeline {
         self.send(context, &mut req, offer_link).await
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use azure_core::{
+        credentials::{AccessToken, TokenCredential},
+        date::OffsetDateTime,
+        ClientOptions, FixedRetryOptions, Policy, RetryOptions, TransportOptions,
+    };
+
+    use super::{AuthorizationPolicy, CosmosPipeline};
+
+    #[derive(Debug)]
+    struct TestTokenCredential;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl TokenCredential for TestTokenCredential {
+        async fn get_token(&self, _scopes: &[&str]) -> azure_core::Result<AccessToken> {
+            Ok(AccessToken::new(
+                "token",
+                OffsetDateTime::now_utc().saturating_add(time::Duration::minutes(5)),
+            ))
+        }
+
+        async fn clear_cache(&self) -> azure_core::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Creates a pipeline that sends every request to `transport` instead of the network.
+    pub fn mock_pipeline(transport: Arc<dyn Policy>) -> CosmosPipeline {
+        mock_pipeline_with_retry(
+            transport,
+            RetryOptions::fixed(FixedRetryOptions::default().delay(Duration::from_millis(1))),
+        )
+    }
+
+    /// Creates a pipeline that sends every request to `transport` instead of the network, retrying with `retry`.
+    pub fn mock_pipeline_with_retry(
+        transport: Arc<dyn Policy>,
+        retry: RetryOptions,
+    ) -> CosmosPipeline {
+        let mut client_options = ClientOptions::default();
+        client_options.set_transport(TransportOptions::new_custom_policy(transport));
+        client_options.set_retry(retry);
+        CosmosPipeline::new(
+            "https://myaccount.documents.azure.com/".parse().unwrap(),
+            AuthorizationPolicy::from_token_credential(Arc::new(TestTokenCredential)),
+            client_options,
+        )
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{DefaultRetryClassifier, FailedAttempt, RetryClassifier, StatusCode};
+
+use crate::constants;
+
+/// Sub-status codes the service returns alongside `410 Gone` while it is moving a partition, which resolve on their own.
+///
+/// Splits and merges (`1002` and `1007`) are not retried here, since cross-partition queries handle them by re-reading the partition key ranges.
+const TRANSIENT_GONE_SUB_STATUSES: &[&str] = &[
+    "1000", // NameCacheIsStale
+    "1008", // CompletingPartitionMigration
+];
+
+/// The non-standard status the service returns when a write conflicted with a concurrent write, and should be retried.
+const RETRY_WITH: u16 = 449;
+
+/// Retries the failures the [`DefaultRetryClassifier`] retries, as well as `449 Retry With` responses
+/// and `410 Gone` responses while a partition is being moved.
+#[derive(Debug, Default)]
+pub(crate) struct CosmosRetryClassifier {
+    default: DefaultRetryClassifier,
+}
+
+impl RetryClassifier for CosmosRetryClassifier {
+    fn should_retry(&self, attempt: &FailedAttempt<'_>) -> bool {
+        if attempt.raw_status() == Some(RETRY_WITH) {
+            return true;
+        }
+        if attempt.status() == Some(StatusCode::Gone) {
+            return attempt
+                .headers()
+                .and_then(|headers| headers.get_optional_str(&constants::SUB_STATUS))
+                .is_some_and(|sub_status| TRANSIENT_GONE_SUB_STATUSES.contains(&sub_status));
+        }
+        self.default.should_retry(attempt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use azure_core::{
+        headers::Headers, Context, FailedAttempt, FixedRetryOptions, Method, Policy, PolicyResult,
+        Request, Response, RetryOptions, RetryPolicy, StatusCode,
+    };
+
+    use crate::{
+        pipeline::tests::mock_pipeline_with_retry,
+        resource_context::{ResourceLink, ResourceType},
+    };
+
+    /// Responds with the given status and sub-status to the first request, then with `200 OK`.
+    #[derive(Debug)]
+    struct FailOnceTransport {
+        status: u16,
+        sub_status: Option<&'static str>,
+        requests: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for FailOnceTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            if self.requests.fetch_add(1, Ordering::SeqCst) > 0 {
+                return Ok(Response::from_bytes(StatusCode::Ok, Headers::new(), "{}"));
+            }
+            let mut headers = Headers::new();
+            if let Some(sub_status) = self.sub_status {
+                headers.insert("x-ms-substatus", sub_status);
+            }
+            // Like the HTTP client, fall back to `400 Bad Request` for statuses `StatusCode` cannot represent.
+            let status = StatusCode::try_from(self.status).unwrap_or(StatusCode::BadRequest);
+            Ok(Response::from_bytes(status, headers, "{}").with_raw_status(self.status))
+        }
+    }
+
+    /// Never retries, and never waits.
+    #[derive(Debug)]
+    struct NeverRetryPolicy;
+
+    impl RetryPolicy for NeverRetryPolicy {
+        fn is_expired(&self, _duration_since_start: Duration, _retry_count: u32) -> bool {
+            false
+        }
+
+        fn sleep_duration(&self, _retry_count: u32) -> Duration {
+            Duration::ZERO
+        }
+
+        fn should_retry(&self, _attempt: &FailedAttempt<'_>) -> bool {
+            false
+        }
+    }
+
+    async fn requests_sent_with(
+        status: u16,
+        sub_status: Option<&'static str>,
+        retry: RetryOptions,
+    ) -> usize {
+        let transport = Arc::new(FailOnceTransport {
+            status,
+            sub_status,
+            requests: AtomicUsize::new(0),
+        });
+        let pipeline = mock_pipeline_with_retry(transport.clone(), retry);
+        let link = ResourceLink::root(ResourceType::Databases).item("db");
+        let mut request = Request::new(pipeline.url(&link), Method::Get);
+        let _ = pipeline
+            .send::<()>(Context::new(), &mut request, link)
+            .await;
+        transport.requests.load(Ordering::SeqCst)
+    }
+
+    async fn requests_sent(status: u16, sub_status: Option<&'static str>) -> usize {
+        requests_sent_with(
+            status,
+            sub_status,
+            RetryOptions::fixed(FixedRetryOptions::default().delay(Duration::from_millis(1))),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    pub async fn retries_gone_while_partition_moves() {
+        assert_eq!(2, requests_sent(410, Some("1000")).await);
+        assert_eq!(2, requests_sent(410, Some("1008")).await);
+    }
+
+    #[tokio::test]
+    pub async fn does_not_retry_gone_after_split() {
+        assert_eq!(1, requests_sent(410, Some("1002")).await);
+        assert_eq!(1, requests_sent(410, Some("1007")).await);
+    }
+
+    #[tokio::test]
+    pub async fn retries_retry_with() {
+        assert_eq!(2, requests_sent(449, None).await);
+        assert_eq!(1, requests_sent(400, None).await);
+    }
+
+    #[tokio::test]
+    pub async fn custom_retry_policy_decides_what_to_retry() {
+        let retry = RetryOptions::custom(Arc::new(NeverRetryPolicy));
+        assert_eq!(1, requests_sent_with(449, None, retry.clone()).await);
+        assert_eq!(1, requests_sent_with(410, Some("1000"), retry).await);
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::Etag;
+use serde::Serialize;
+
+use crate::{models::PatchDocument, PartitionKey, TransactionalBatchItemOptions};
+
+/// The maximum number of operations the service accepts in a single transactional batch.
+pub const MAX_TRANSACTIONAL_BATCH_OPERATIONS: usize = 100;
+
+/// A set of item operations, within a single logical partition, that are executed atomically.
+///
+/// Either every operation in the batch succeeds, or none of them are applied.
+/// Build a batch with [`TransactionalBatch::new()`] and the various `*_item` methods,
+/// then submit it with [`ContainerClient::execute_transactional_batch()`](crate::clients::ContainerClient::execute_transactional_batch()).
+///
+/// # Examples
+///
+/// ```rust
+/// # use azure_data_cosmos::{models::PatchDocument, TransactionalBatch};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #[derive(serde::Serialize)]
+/// struct Order {
+///     id: String,
+///     customer_id: String,
+///     total: f64,
+/// }
+///
+/// let batch = TransactionalBatch::new("customer1")
+///     .create_item(Order { id: "order1".into(), customer_id: "customer1".into(), total: 42.0 }, None)?
+///     .patch_item("customer1", PatchDocument::default().with_increment("/order_count", 1)?, None)?
+///     .delete_item("cart1", None);
+/// # assert_eq!(batch.operations().len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TransactionalBatch {
+    partition_key: PartitionKey,
+    operations: Vec<BatchOperation>,
+}
+
+impl TransactionalBatch {
+    /// Creates an empty batch whose operations all target the logical partition identified by `partition_key`.
+    pub fn new(partition_key: impl Into<PartitionKey>) -> Self {
+        Self {
+            partition_key: partition_key.into(),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Returns the partition key shared by every operation in the batch.
+    pub fn partition_key(&self) -> &PartitionKey {
+        &self.partition_key
+    }
+
+    /// Returns the operations in the batch, in the order they will be executed.
+    pub fn operations(&self) -> &[BatchOperation] {
+        &self.operations
+    }
+
+    /// Adds an operation that creates a new item.
+    ///
+    /// Returns an error if the item cannot be serialized to JSON.
+    pub fn create_item<T: Serialize>(
+        self,
+        item: T,
+        options: Option<TransactionalBatchItemOptions>,
+    ) -> azure_core::Result<Self> {
+        let body = serde_json::to_value(item)?;
+        Ok(self.push(BatchOperationType::Create, None, Some(body), options))
+    }
+
+    /// Adds an operation that creates an item, or replaces it if an item with the same ID already exists.
+    ///
+    /// Returns an error if the item cannot be serialized to JSON.
+    pub fn upsert_item<T: Serialize>(
+        self,
+        item: T,
+        options: Option<TransactionalBatchItemOptions>,
+    ) -> azure_core::Result<Self> {
+        let body = serde_json::to_value(item)?;
+        Ok(self.push(BatchOperationType::Upsert, None, Some(body), options))
+    }
+
+    /// Adds an operation that replaces the item with the ID `item_id`.
+    ///
+    /// Returns an error if the item cannot be serialized to JSON.
+    pub fn replace_item<T: Serialize>(
+        self,
+        item_id: impl Into<String>,
+        item: T,
+        options: Option<TransactionalBatchItemOptions>,
+    ) -> azure_core::Result<Self> {
+        let body = serde_json::to_value(item)?;
+        Ok(self.push(
+            BatchOperationType::Replace,
+            Some(item_id.into()),
+            Some(body),
+            options,
+        ))
+    }
+
+    /// Adds an operation that reads the item with the ID `item_id`.
+    pub fn read_item(
+        self,
+        item_id: impl Into<String>,
+        options: Option<TransactionalBatchItemOptions>,
+    ) -> Self {
+        self.push(
+            BatchOperationType::Read,
+            Some(item_id.into()),
+            None,
+            options,
+        )
+    }
+
+    /// Adds an operation that deletes the item with the ID `item_id`.
+    pub fn delete_item(
+        self,
+        item_id: impl Into<String>,
+        options: Option<TransactionalBatchItemOptions>,
+    ) -> Self {
+        self.push(
+            BatchOperationType::Delete,
+            Some(item_id.into()),
+            None,
+            options,
+        )
+    }
+
+    /// Adds an operation that applies `patch` to the item with the ID `item_id`.
+    ///
+    /// Returns an error if the patch document cannot be serialized to JSON.
+    pub fn patch_item(
+        self,
+        item_id: impl Into<String>,
+        patch: PatchDocument,
+        options: Option<TransactionalBatchItemOptions>,
+    ) -> azure_core::Result<Self> {
+        let body = serde_json::to_value(patch)?;
+        Ok(self.push(
+            BatchOperationType::Patch,
+            Some(item_id.into()),
+            Some(body),
+            options,
+        ))
+    }
+
+    fn push(
+        mut self,
+        operation_type: BatchOperationType,
+        id: Option<String>,
+        resource_body: Option<serde_json::Value>,
+        options: Option<TransactionalBatchItemOptions>,
+    ) -> Self {
+        let options = options.unwrap_or_default();
+        self.operations.push(BatchOperation {
+            operation_type,
+            id,
+            resource_body,
+            if_match: options.if_match,
+        });
+        self
+    }
+}
+
+/// The kind of a [`BatchOperation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum BatchOperationType {
+    Create,
+    Upsert,
+    Replace,
+    Read,
+    Delete,
+    Patch,
+}
+
+/// A single operation in a [`TransactionalBatch`].
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOperation {
+    operation_type: BatchOperationType,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_body: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    if_match: Option<Etag>,
+}
+
+impl BatchOperation {
+    /// Returns the kind of operation.
+    pub fn operation_type(&self) -> BatchOperationType {
+        self.operation_type
+    }
+
+    /// Returns the ID of the item targeted by the operation, if the operation specifies one.
+    ///
+    /// Create and upsert operations take the ID from the item itself.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use crate::{models::PatchDocument, TransactionalBatch, TransactionalBatchItemOptions};
+
+    #[derive(Serialize)]
+    struct Order {
+        id: String,
+        total: u32,
+    }
+
+    #[test]
+    pub fn serialize_operations() {
+        let batch = TransactionalBatch::new("customer1")
+            .create_item(
+                Order {
+                    id: "order1".into(),
+                    total: 42,
+                },
+                None,
+            )
+            .unwrap()
+            .upsert_item(
+                Order {
+                    id: "order2".into(),
+                    total: 7,
+                },
+                None,
+            )
+            .unwrap()
+            .replace_item(
+                "order3",
+                Order {
+                    id: "order3".into(),
+                    total: 1,
+                },
+                Some(TransactionalBatchItemOptions {
+                    if_match: Some("\"etag3\"".into()),
+                }),
+            )
+            .unwrap()
+            .read_item("order4", None)
+            .delete_item("order5", None)
+            .patch_item(
+                "customer1",
+                PatchDocument::default()
+                    .with_add("/status", "active")
+                    .unwrap(),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_string(batch.operations()).unwrap(),
+            concat!(
+                r#"[{"operationType":"Create","resourceBody":{"id":"order1","total":42}},"#,
+                r#"{"operationType":"Upsert","resourceBody":{"id":"order2","total":7}},"#,
+                r#"{"operationType":"Replace","id":"order3","resourceBody":{"id":"order3","total":1},"ifMatch":"\"etag3\""},"#,
+                r#"{"operationType":"Read","id":"order4"},"#,
+                r#"{"operationType":"Delete","id":"order5"},"#,
+                r#"{"operationType":"Patch","id":"customer1","resourceBody":{"operations":[{"op":"add","path":"/status","value":"active"}]}}]"#,
+            )
+        );
+    }
+}
@@ -88,14 +88,18 @@ impl TestAccount {
             return Err("invalid connection string, missing 'AccountKey'".into());
         };
 
-        let context_id = format!(
-            "{}_{}_{}",
-            context.test_name(),
-            OffsetDateTime::now_utc().format(format_description!(
-                "[year]_[month]_[day]T[hour]_[minute]_[second]"
-            ))?,
-            Uuid::new_v4().as_simple()
-        );
+        // Record the context ID, so that recorded tests create the same databases during playback.
+        let timestamp = OffsetDateTime::now_utc().format(format_description!(
+            "[year]_[month]_[day]T[hour]_[minute]_[second]"
+        ))?;
+        let context_id = context.variable("context_id", || {
+            format!(
+                "{}_{}_{}",
+                context.test_name(),
+                timestamp,
+                Uuid::new_v4().as_simple()
+            )
+        })?;
 
         TRACING.call_once(|| {
             // Enable tracing for tests, if it's not already enabled
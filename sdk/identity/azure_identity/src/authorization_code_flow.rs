@@ -5,8 +5,6 @@
 //!
 //! You can learn more about the `OAuth2` authorization code flow [here](https://docs.microsoft.com/azure/active-directory/develop/v2-oauth2-auth-code-flow).
 
-#![allow(dead_code)]
-
 use crate::oauth2_http_client::Oauth2HttpClient;
 use azure_core::{
     error::{ErrorKind, ResultExt},
@@ -20,26 +18,23 @@ use std::sync::Arc;
 ///
 /// The values for `client_id`, `client_secret`, `tenant_id`, and `redirect_url` can all be found
 /// inside of the Azure portal.
-#[allow(dead_code)]
 pub fn authorize(
     client_id: ClientId,
     client_secret: Option<ClientSecret>,
+    authority_host: &Url,
     tenant_id: &str,
     redirect_url: Url,
     scopes: &[&str],
-) -> AuthorizationCodeFlow {
-    let auth_url = oauth2::AuthUrl::from_url(
-        Url::parse(&format!(
-            "https://login.microsoftonline.com/{tenant_id}/oauth2/v2.0/authorize"
-        ))
-        .expect("Invalid authorization endpoint URL"),
-    );
-    let token_url = oauth2::TokenUrl::from_url(
-        Url::parse(&format!(
-            "https://login.microsoftonline.com/{tenant_id}/oauth2/v2.0/token"
-        ))
-        .expect("Invalid token endpoint URL"),
-    );
+) -> azure_core::Result<AuthorizationCodeFlow> {
+    let endpoint = |name: &str| {
+        authority_host
+            .join(&format!("/{tenant_id}/oauth2/v2.0/{name}"))
+            .with_context(ErrorKind::DataConversion, || {
+                format!("The supplied tenant id could not be url encoded: {tenant_id}")
+            })
+    };
+    let auth_url = oauth2::AuthUrl::from_url(endpoint("authorize")?);
+    let token_url = oauth2::TokenUrl::from_url(endpoint("token")?);
 
     // Set up the config for the Microsoft Graph OAuth2 process.
     let client = BasicClient::new(client_id, client_secret, auth_url, Some(token_url))
@@ -61,12 +56,12 @@ pub fn authorize(
         .set_pkce_challenge(pkce_code_challenge)
         .url();
 
-    AuthorizationCodeFlow {
+    Ok(AuthorizationCodeFlow {
         client,
         authorize_url,
         csrf_state,
         pkce_code_verifier,
-    }
+    })
 }
 
 /// An object representing an OAuth 2.0 authorization code flow.
@@ -82,7 +77,6 @@ pub struct AuthorizationCodeFlow {
     pub pkce_code_verifier: oauth2::PkceCodeVerifier,
 }
 
-#[allow(dead_code)]
 impl AuthorizationCodeFlow {
     /// Exchange an authorization code for a token.
     pub async fn exchange(
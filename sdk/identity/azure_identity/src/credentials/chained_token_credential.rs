@@ -0,0 +1,404 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{
+    credentials::{AccessToken, TokenCredential},
+    error::{Error, ErrorKind},
+};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Provides options to configure how a [`ChainedTokenCredential`] tries its sources.
+#[derive(Clone, Debug, Default)]
+pub struct ChainedTokenCredentialOptions {
+    stop_on_authentication_failure: bool,
+    reuse_selected_source: bool,
+}
+
+impl ChainedTokenCredentialOptions {
+    /// Set whether to stop trying sources after a source that is available fails to authenticate,
+    /// instead of falling through to the next source. The default is `false`.
+    ///
+    /// A source is considered available if it received an error response from its authority, for example
+    /// because its client secret has expired. Errors before a response is received, for example because
+    /// the source is not configured or its endpoint cannot be reached, always fall through to the next source.
+    pub fn set_stop_on_authentication_failure(&mut self, stop_on_authentication_failure: bool) {
+        self.stop_on_authentication_failure = stop_on_authentication_failure;
+    }
+
+    /// Whether to stop trying sources after a source that is available fails to authenticate.
+    pub fn stop_on_authentication_failure(&self) -> bool {
+        self.stop_on_authentication_failure
+    }
+
+    /// Set whether to use only the source that returned a token for all later requests, instead of trying
+    /// each source in order every time. The default is `false`.
+    ///
+    /// Reusing the selected source avoids waiting for unavailable sources on every request, but the chain will not
+    /// fall back to another source if the selected source stops working, until [`TokenCredential::clear_cache`] is called.
+    pub fn set_reuse_selected_source(&mut self, reuse_selected_source: bool) {
+        self.reuse_selected_source = reuse_selected_source;
+    }
+
+    /// Whether to use only the source that returned a token for all later requests.
+    pub fn reuse_selected_source(&self) -> bool {
+        self.reuse_selected_source
+    }
+}
+
+/// Provides a `TokenCredential` that tries each of its sources in order until one returns a token.
+///
+/// Each request tries the sources in order again, unless [`ChainedTokenCredentialOptions::set_reuse_selected_source`]
+/// is set. If no source returns a token, the error lists each source's failure by name.
+///
+/// # Example
+///
+/// Try workload identity first, then the Azure CLI.
+/// ```no_run
+/// # use azure_core::credentials::TokenCredential;
+/// # use azure_identity::{AzureCliCredential, ChainedTokenCredential, TokenCredentialOptions, WorkloadIdentityCredential};
+/// # use std::sync::Arc;
+/// # fn main() -> azure_core::Result<()> {
+/// let sources: Vec<Arc<dyn TokenCredential>> = vec![
+///     WorkloadIdentityCredential::from_env(TokenCredentialOptions::default())?,
+///     AzureCliCredential::new()?,
+/// ];
+/// let credential = ChainedTokenCredential::new(sources, None)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChainedTokenCredential {
+    sources: Vec<(String, Arc<dyn TokenCredential>)>,
+    options: ChainedTokenCredentialOptions,
+    selected: Mutex<Option<usize>>,
+}
+
+impl ChainedTokenCredential {
+    /// Create a new `ChainedTokenCredential` that tries each of `sources` in order.
+    ///
+    /// Errors name each source by its type, taken from its `Debug` output. To name sources yourself,
+    /// use [`ChainedTokenCredential::with_named_sources`].
+    ///
+    /// Fails if there are no `sources`.
+    pub fn new(
+        sources: Vec<Arc<dyn TokenCredential>>,
+        options: Option<ChainedTokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        let sources = sources
+            .into_iter()
+            .map(|source| (type_name(source.as_ref()), source))
+            .collect();
+        Self::with_named_sources(sources, options)
+    }
+
+    /// Create a new `ChainedTokenCredential` that tries each of `sources` in order, naming each in logs and errors.
+    ///
+    /// Fails if there are no `sources`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use azure_core::credentials::TokenCredential;
+    /// # use azure_identity::{AzureCliCredential, ChainedTokenCredential, TokenCredentialOptions, WorkloadIdentityCredential};
+    /// # use std::sync::Arc;
+    /// # fn main() -> azure_core::Result<()> {
+    /// let sources: Vec<(String, Arc<dyn TokenCredential>)> = vec![
+    ///     ("workload identity".into(), WorkloadIdentityCredential::from_env(TokenCredentialOptions::default())?),
+    ///     ("Azure CLI".into(), AzureCliCredential::new()?),
+    /// ];
+    /// let credential = ChainedTokenCredential::with_named_sources(sources, None)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_named_sources(
+        sources: Vec<(String, Arc<dyn TokenCredential>)>,
+        options: Option<ChainedTokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        if sources.is_empty() {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "chained token credential requires at least one source",
+            ));
+        }
+        Ok(Arc::new(Self {
+            sources,
+            options: options.unwrap_or_default(),
+            selected: Mutex::new(None),
+        }))
+    }
+
+    /// The source that last returned a token, if any.
+    pub fn selected_source(&self) -> Option<Arc<dyn TokenCredential>> {
+        let selected = *self.selected.lock().expect("selected source lock poisoned");
+        selected.map(|index| self.sources[index].1.clone())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for ChainedTokenCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        if self.options.reuse_selected_source {
+            if let Some(source) = self.selected_source() {
+                return source.get_token(scopes).await;
+            }
+        }
+
+        let mut errors = Vec::new();
+        for (index, (name, source)) in self.sources.iter().enumerate() {
+            match source.get_token(scopes).await {
+                Ok(token) => {
+                    debug!("{name} returned a token");
+                    *self.selected.lock().expect("selected source lock poisoned") = Some(index);
+                    return Ok(token);
+                }
+                Err(error) => {
+                    debug!("{name} failed to return a token: {error}");
+                    let stop = self.options.stop_on_authentication_failure
+                        && is_authentication_failure(&error);
+                    errors.push((name.clone(), error));
+                    if stop {
+                        break;
+                    }
+                }
+            }
+        }
+        Err(Error::with_message(ErrorKind::Credential, || {
+            format!(
+                "Multiple errors were encountered while attempting to authenticate:\n{}",
+                format_aggregate_error(&errors)
+            )
+        }))
+    }
+
+    /// Clear the cache of each source, and try each source again when a token is next requested.
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        *self.selected.lock().expect("selected source lock poisoned") = None;
+        for (_, source) in &self.sources {
+            source.clear_cache().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Gets the type name of a credential, which its derived `Debug` output starts with.
+fn type_name(source: &dyn TokenCredential) -> String {
+    let mut name = format!("{source:?}");
+    let end = name
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(name.len());
+    name.truncate(end);
+    name
+}
+
+/// Returns `true` if `error` was caused by an error response, meaning the credential was able to reach its authority.
+fn is_authentication_failure(error: &Error) -> bool {
+    let mut current: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(error) = current {
+        if error
+            .downcast_ref::<Error>()
+            .is_some_and(|error| matches!(error.kind(), ErrorKind::HttpResponse { .. }))
+        {
+            return true;
+        }
+        current = error.source();
+    }
+    false
+}
+
+/// Formats the error from each named source on its own line, along with the errors that caused it.
+pub(crate) fn format_aggregate_error(errors: &[(String, Error)]) -> String {
+    use std::error::Error;
+    errors
+        .iter()
+        .map(|(name, e)| {
+            let mut current: Option<&dyn Error> = Some(e);
+            let mut stack = vec![];
+            while let Some(err) = current.take() {
+                stack.push(err.to_string());
+                current = err.source();
+            }
+            format!("{name}: {}", stack.join(" - "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{credentials::Secret, error::ResultExt, StatusCode};
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    use time::OffsetDateTime;
+
+    #[derive(Debug)]
+    enum Outcome {
+        Token,
+        Unavailable,
+        AuthenticationFailed,
+    }
+
+    #[derive(Debug)]
+    struct MockCredential {
+        outcome: Outcome,
+        calls: AtomicUsize,
+    }
+
+    impl MockCredential {
+        fn new(outcome: Outcome) -> Arc<Self> {
+            Arc::new(Self {
+                outcome,
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl TokenCredential for MockCredential {
+        async fn get_token(&self, _scopes: &[&str]) -> azure_core::Result<AccessToken> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.outcome {
+                Outcome::Token => Ok(AccessToken::new(
+                    Secret::new("token"),
+                    OffsetDateTime::now_utc() + Duration::from_secs(3600),
+                )),
+                Outcome::Unavailable => Err(Error::message(
+                    ErrorKind::Credential,
+                    "credential is not configured",
+                )),
+                Outcome::AuthenticationFailed => Err(Error::from(ErrorKind::http_response(
+                    StatusCode::Unauthorized,
+                    Some("invalid_client".to_owned()),
+                )))
+                .context(ErrorKind::Credential, "request token error"),
+            }
+        }
+
+        async fn clear_cache(&self) -> azure_core::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn tries_every_source_by_default() -> azure_core::Result<()> {
+        let unavailable = MockCredential::new(Outcome::Unavailable);
+        let token = MockCredential::new(Outcome::Token);
+        let credential =
+            ChainedTokenCredential::new(vec![unavailable.clone(), token.clone()], None)?;
+
+        credential.get_token(&["scope"]).await?;
+        credential.get_token(&["scope"]).await?;
+        assert_eq!(unavailable.calls(), 2);
+        assert_eq!(token.calls(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn can_reuse_source_that_returned_token() -> azure_core::Result<()> {
+        let unavailable = MockCredential::new(Outcome::Unavailable);
+        let token = MockCredential::new(Outcome::Token);
+        let mut options = ChainedTokenCredentialOptions::default();
+        options.set_reuse_selected_source(true);
+        let credential =
+            ChainedTokenCredential::new(vec![unavailable.clone(), token.clone()], Some(options))?;
+        assert!(credential.selected_source().is_none());
+
+        credential.get_token(&["scope"]).await?;
+        credential.get_token(&["scope"]).await?;
+        assert_eq!(unavailable.calls(), 1);
+        assert_eq!(token.calls(), 2);
+        assert!(credential.selected_source().is_some());
+
+        credential.clear_cache().await?;
+        assert!(credential.selected_source().is_none());
+        credential.get_token(&["scope"]).await?;
+        assert_eq!(unavailable.calls(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lists_every_source_failure() -> azure_core::Result<()> {
+        let credential = ChainedTokenCredential::new(
+            vec![
+                MockCredential::new(Outcome::AuthenticationFailed),
+                MockCredential::new(Outcome::Unavailable),
+            ],
+            None,
+        )?;
+        let err = credential.get_token(&["scope"]).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        let message = err.to_string();
+        let lines: Vec<&str> = message.lines().skip(1).collect();
+        assert_eq!(lines.len(), 2, "{message}");
+        assert!(
+            lines[0].starts_with("MockCredential: request token error"),
+            "{message}"
+        );
+        assert_eq!(lines[1], "MockCredential: credential is not configured");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn names_sources() -> azure_core::Result<()> {
+        let credential = ChainedTokenCredential::with_named_sources(
+            vec![
+                (
+                    "first".to_owned(),
+                    MockCredential::new(Outcome::Unavailable),
+                ),
+                (
+                    "second".to_owned(),
+                    MockCredential::new(Outcome::Unavailable),
+                ),
+            ],
+            None,
+        )?;
+        let err = credential.get_token(&["scope"]).await.unwrap_err();
+        let message = err.to_string();
+        let lines: Vec<&str> = message.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            [
+                "first: credential is not configured",
+                "second: credential is not configured"
+            ],
+            "{message}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn can_stop_on_authentication_failure() -> azure_core::Result<()> {
+        let unavailable = MockCredential::new(Outcome::Unavailable);
+        let failed = MockCredential::new(Outcome::AuthenticationFailed);
+        let token = MockCredential::new(Outcome::Token);
+        let mut options = ChainedTokenCredentialOptions::default();
+        options.set_stop_on_authentication_failure(true);
+        let credential = ChainedTokenCredential::new(
+            vec![unavailable.clone(), failed.clone(), token.clone()],
+            Some(options),
+        )?;
+
+        let err = credential.get_token(&["scope"]).await.unwrap_err();
+        assert_eq!(err.to_string().lines().count(), 3, "{err}");
+        assert_eq!(unavailable.calls(), 1);
+        assert_eq!(failed.calls(), 1);
+        assert_eq!(token.calls(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn requires_sources() {
+        let err = ChainedTokenCredential::new(Vec::new(), None).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+    }
+}
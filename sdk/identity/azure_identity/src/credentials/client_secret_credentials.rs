@@ -0,0 +1,248 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{credentials::cache::TokenCache, TokenCredentialOptions};
+use azure_core::{
+    content_type,
+    credentials::{AccessToken, Secret, TokenCredential},
+    error::{http_response_from_body, ErrorKind, ResultExt},
+    headers, HttpClient, Method, Request, Url,
+};
+use serde::Deserialize;
+use std::{str, sync::Arc, time::Duration};
+use time::OffsetDateTime;
+use typespec_client_core::http::Model;
+use url::form_urlencoded;
+
+const AZURE_TENANT_ID_ENV_KEY: &str = "AZURE_TENANT_ID";
+const AZURE_CLIENT_ID_ENV_KEY: &str = "AZURE_CLIENT_ID";
+const AZURE_CLIENT_SECRET_ENV_KEY: &str = "AZURE_CLIENT_SECRET";
+
+/// Enables authentication to Microsoft Entra ID using a client secret that was generated for an App Registration.
+///
+/// More information on how to configure a client secret can be found here:
+/// <https://learn.microsoft.com/entra/identity-platform/quickstart-register-app#add-a-client-secret>
+#[derive(Debug)]
+pub struct ClientSecretCredential {
+    http_client: Arc<dyn HttpClient>,
+    authority_host: Url,
+    tenant_id: String,
+    client_id: String,
+    client_secret: Secret,
+    cache: TokenCache,
+}
+
+impl ClientSecretCredential {
+    /// Create a new `ClientSecretCredential`.
+    pub fn new<S>(
+        tenant_id: String,
+        client_id: String,
+        client_secret: S,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<ClientSecretCredential>>
+    where
+        S: Into<Secret>,
+    {
+        let options = options.into();
+        Ok(Arc::new(ClientSecretCredential {
+            http_client: options.http_client(),
+            authority_host: options.authority_host()?,
+            tenant_id,
+            client_id,
+            client_secret: client_secret.into(),
+            cache: TokenCache::new(),
+        }))
+    }
+
+    /// Create a new `ClientSecretCredential` from environment variables.
+    ///
+    /// # Variables
+    ///
+    /// * `AZURE_TENANT_ID`
+    /// * `AZURE_CLIENT_ID`
+    /// * `AZURE_CLIENT_SECRET`
+    pub fn from_env(
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<ClientSecretCredential>> {
+        let options = options.into();
+        let env = options.env();
+        let tenant_id =
+            env.var(AZURE_TENANT_ID_ENV_KEY)
+                .with_context(ErrorKind::Credential, || {
+                    format!(
+                        "client secret credential requires {} environment variable",
+                        AZURE_TENANT_ID_ENV_KEY
+                    )
+                })?;
+        let client_id =
+            env.var(AZURE_CLIENT_ID_ENV_KEY)
+                .with_context(ErrorKind::Credential, || {
+                    format!(
+                        "client secret credential requires {} environment variable",
+                        AZURE_CLIENT_ID_ENV_KEY
+                    )
+                })?;
+        let client_secret =
+            env.var(AZURE_CLIENT_SECRET_ENV_KEY)
+                .with_context(ErrorKind::Credential, || {
+                    format!(
+                        "client secret credential requires {} environment variable",
+                        AZURE_CLIENT_SECRET_ENV_KEY
+                    )
+                })?;
+
+        ClientSecretCredential::new(tenant_id, client_id, client_secret, options)
+    }
+
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let url = self
+            .authority_host
+            .join(&format!("/{}/oauth2/v2.0/token", self.tenant_id))
+            .with_context(ErrorKind::DataConversion, || {
+                format!(
+                    "The supplied tenant id could not be url encoded: {}",
+                    self.tenant_id
+                )
+            })?;
+
+        let encoded = form_urlencoded::Serializer::new(String::new())
+            .append_pair("client_id", &self.client_id)
+            .append_pair("scope", &scopes.join(" "))
+            .append_pair("client_secret", self.client_secret.secret())
+            .append_pair("grant_type", "client_credentials")
+            .finish();
+
+        let mut req = Request::new(url, Method::Post);
+        req.insert_header(
+            headers::CONTENT_TYPE,
+            content_type::APPLICATION_X_WWW_FORM_URLENCODED,
+        );
+        req.set_body(encoded);
+
+        let rsp = self.http_client.execute_request(&req).await?;
+        let rsp_status = rsp.status();
+
+        if !rsp_status.is_success() {
+            let rsp_body = rsp.into_body().collect().await?;
+            return Err(http_response_from_body(rsp_status, &rsp_body).into_error())
+                .context(ErrorKind::Credential, "request token error");
+        }
+
+        let response: TokenResponse = rsp.deserialize_body_into().await?;
+        Ok(AccessToken::new(
+            response.access_token,
+            OffsetDateTime::now_utc() + Duration::from_secs(response.expires_in),
+        ))
+    }
+}
+
+#[derive(Model, Deserialize, Debug)]
+struct TokenResponse {
+    expires_in: u64,
+    access_token: Secret,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for ClientSecretCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache.get_token(scopes, self.get_token(scopes)).await
+    }
+
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        env::Env,
+        mock::{json_response, options, MockHttpClient},
+    };
+    use azure_core::StatusCode;
+
+    fn token_endpoint() -> Arc<MockHttpClient> {
+        MockHttpClient::new(|request| {
+            assert_eq!(request.method, Method::Post);
+            assert_eq!(request.path, "/tenant/oauth2/v2.0/token");
+            assert_eq!(request.field("grant_type"), "client_credentials");
+            assert_eq!(request.field("client_id"), "client");
+            assert_eq!(request.field("scope"), "https://vault.azure.net/.default");
+            if request.field("client_secret") != "secret" {
+                return json_response(
+                    StatusCode::Unauthorized,
+                    r#"{"error":"invalid_client","error_description":"AADSTS7000215: Invalid client secret provided."}"#,
+                );
+            }
+            json_response(
+                StatusCode::Ok,
+                r#"{"token_type":"Bearer","expires_in":3599,"ext_expires_in":3599,"access_token":"token"}"#,
+            )
+        })
+    }
+
+    #[tokio::test]
+    async fn gets_and_caches_token() -> azure_core::Result<()> {
+        let http_client = token_endpoint();
+        let credential: Arc<dyn TokenCredential> = ClientSecretCredential::new(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            "secret",
+            options(http_client.clone()),
+        )?;
+
+        let scopes = &["https://vault.azure.net/.default"];
+        let token = credential.get_token(scopes).await?;
+        assert_eq!(token.token.secret(), "token");
+        assert!(token.expires_on > OffsetDateTime::now_utc() + Duration::from_secs(3000));
+
+        credential.get_token(scopes).await?;
+        assert_eq!(http_client.requests(), 1);
+
+        credential.clear_cache().await?;
+        credential.get_token(scopes).await?;
+        assert_eq!(http_client.requests(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_secret_is_credential_error() -> azure_core::Result<()> {
+        let credential: Arc<dyn TokenCredential> = ClientSecretCredential::new(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            "wrong",
+            options(token_endpoint()),
+        )?;
+        let err = credential
+            .get_token(&["https://vault.azure.net/.default"])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn from_env() -> azure_core::Result<()> {
+        let mut options = options(token_endpoint());
+        options.set_env(Env::from(
+            &[
+                (AZURE_TENANT_ID_ENV_KEY, "tenant"),
+                (AZURE_CLIENT_ID_ENV_KEY, "client"),
+                (AZURE_CLIENT_SECRET_ENV_KEY, "secret"),
+            ][..],
+        ));
+        let credential: Arc<dyn TokenCredential> =
+            ClientSecretCredential::from_env(options.clone())?;
+        let token = credential
+            .get_token(&["https://vault.azure.net/.default"])
+            .await?;
+        assert_eq!(token.token.secret(), "token");
+
+        options.set_env(Env::from(&[(AZURE_TENANT_ID_ENV_KEY, "tenant")][..]));
+        let err = ClientSecretCredential::from_env(options).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        Ok(())
+    }
+}
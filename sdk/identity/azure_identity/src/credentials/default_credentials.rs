@@ -4,8 +4,10 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::AzureCliCredential;
 use crate::{
-    credentials::cache::TokenCache, timeout::TimeoutExt, AppServiceManagedIdentityCredential,
-    ImdsId, TokenCredentialOptions, VirtualMachineManagedIdentityCredential,
+    credentials::{cache::TokenCache, chained_token_credential::format_aggregate_error},
+    timeout::TimeoutExt,
+    AppServiceManagedIdentityCredential, ChainedTokenCredential, EnvironmentCredential, ImdsId,
+    TokenCredentialOptions, VirtualMachineManagedIdentityCredential, WorkloadIdentityCredential,
 };
 use azure_core::{
     credentials::{AccessToken, TokenCredential},
@@ -16,6 +18,8 @@ use std::{sync::Arc, time::Duration};
 /// Provides a mechanism of selectively disabling credentials used for a `DefaultAzureCredential` instance
 pub struct DefaultAzureCredentialBuilder {
     options: TokenCredentialOptions,
+    include_environment_credential: bool,
+    include_workload_identity_credential: bool,
     include_app_service_managed_identity_credential: bool,
     include_virtual_machine_managed_identity_credential: bool,
     #[cfg(not(target_arch = "wasm32"))]
@@ -26,6 +30,8 @@ impl Default for DefaultAzureCredentialBuilder {
     fn default() -> Self {
         Self {
             options: TokenCredentialOptions::default(),
+            include_environment_credential: true,
+            include_workload_identity_credential: true,
             include_app_service_managed_identity_credential: true,
             include_virtual_machine_managed_identity_credential: true,
             #[cfg(not(target_arch = "wasm32"))]
@@ -45,6 +51,18 @@ impl DefaultAzureCredentialBuilder {
         self
     }
 
+    /// Exclude using credential from the environment
+    pub fn exclude_environment_credential(&mut self) -> &mut Self {
+        self.include_environment_credential = false;
+        self
+    }
+
+    /// Exclude using workload identity credential
+    pub fn exclude_workload_identity_credential(&mut self) -> &mut Self {
+        self.include_workload_identity_credential = false;
+        self
+    }
+
     /// Exclude using any managed identity credential
     pub fn exclude_managed_identity_credential(&mut self) -> &mut Self {
         self.include_app_service_managed_identity_credential = false;
@@ -80,6 +98,12 @@ impl DefaultAzureCredentialBuilder {
     /// Get a list of the credential types to include.
     fn included(&self) -> Vec<DefaultAzureCredentialType> {
         let mut sources = Vec::new();
+        if self.include_environment_credential {
+            sources.push(DefaultAzureCredentialType::Environment);
+        }
+        if self.include_workload_identity_credential {
+            sources.push(DefaultAzureCredentialType::WorkloadIdentity);
+        }
         if self.include_app_service_managed_identity_credential {
             sources.push(DefaultAzureCredentialType::AppService);
         }
@@ -103,12 +127,28 @@ impl DefaultAzureCredentialBuilder {
         let mut errors = Vec::new();
         for source in included {
             match source {
+                DefaultAzureCredentialType::Environment => {
+                    match EnvironmentCredential::new(self.options.clone()) {
+                        Ok(credential) => {
+                            sources.push(DefaultAzureCredentialKind::Environment(credential))
+                        }
+                        Err(error) => errors.push((source.name().to_string(), error)),
+                    }
+                }
+                DefaultAzureCredentialType::WorkloadIdentity => {
+                    match WorkloadIdentityCredential::from_env(self.options.clone()) {
+                        Ok(credential) => {
+                            sources.push(DefaultAzureCredentialKind::WorkloadIdentity(credential))
+                        }
+                        Err(error) => errors.push((source.name().to_string(), error)),
+                    }
+                }
                 DefaultAzureCredentialType::AppService => {
                     match AppServiceManagedIdentityCredential::new(self.options.clone()) {
                         Ok(credential) => {
                             sources.push(DefaultAzureCredentialKind::AppService(credential))
                         }
-                        Err(error) => errors.push(error),
+                        Err(error) => errors.push((source.name().to_string(), error)),
                     }
                 }
                 DefaultAzureCredentialType::VirtualMachine => {
@@ -149,15 +189,35 @@ impl DefaultAzureCredentialBuilder {
 /// Types that may be enabled for use by `DefaultAzureCredential`.
 #[derive(Debug, PartialEq)]
 enum DefaultAzureCredentialType {
+    Environment,
+    WorkloadIdentity,
     AppService,
     VirtualMachine,
     #[cfg(not(target_arch = "wasm32"))]
     AzureCli,
 }
 
+impl DefaultAzureCredentialType {
+    /// The name of the credential type, used in logs and errors.
+    fn name(&self) -> &'static str {
+        match self {
+            DefaultAzureCredentialType::Environment => "EnvironmentCredential",
+            DefaultAzureCredentialType::WorkloadIdentity => "WorkloadIdentityCredential",
+            DefaultAzureCredentialType::AppService => "AppServiceManagedIdentityCredential",
+            DefaultAzureCredentialType::VirtualMachine => "VirtualMachineManagedIdentityCredential",
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialType::AzureCli => "AzureCliCredential",
+        }
+    }
+}
+
 /// Types of `TokenCredential` supported by `DefaultAzureCredential`
 #[derive(Debug)]
 pub(crate) enum DefaultAzureCredentialKind {
+    /// `TokenCredential` from a service principal configured by environment variables.
+    Environment(Arc<EnvironmentCredential>),
+    /// `TokenCredential` from a federated token, such as in Azure Kubernetes Service.
+    WorkloadIdentity(Arc<WorkloadIdentityCredential>),
     /// `TokenCredential` from managed identity that has been assigned to an App Service.
     AppService(Arc<AppServiceManagedIdentityCredential>),
     /// `TokenCredential` from managed identity that has been assigned to a virtual machine.
@@ -167,11 +227,46 @@ pub(crate) enum DefaultAzureCredentialKind {
     AzureCli(Arc<AzureCliCredential>),
 }
 
+impl DefaultAzureCredentialKind {
+    /// The type this credential was created from.
+    fn credential_type(&self) -> DefaultAzureCredentialType {
+        match self {
+            DefaultAzureCredentialKind::Environment(_) => DefaultAzureCredentialType::Environment,
+            DefaultAzureCredentialKind::WorkloadIdentity(_) => {
+                DefaultAzureCredentialType::WorkloadIdentity
+            }
+            DefaultAzureCredentialKind::AppService(_) => DefaultAzureCredentialType::AppService,
+            DefaultAzureCredentialKind::VirtualMachine(_) => {
+                DefaultAzureCredentialType::VirtualMachine
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialKind::AzureCli(_) => DefaultAzureCredentialType::AzureCli,
+        }
+    }
+
+    /// The name of the credential type, used in logs and errors.
+    fn name(&self) -> &'static str {
+        self.credential_type().name()
+    }
+}
+
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl TokenCredential for DefaultAzureCredentialKind {
     async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
         match self {
+            DefaultAzureCredentialKind::Environment(credential) => {
+                credential.get_token(scopes).await.context(
+                    ErrorKind::Credential,
+                    "error getting environment credential",
+                )
+            }
+            DefaultAzureCredentialKind::WorkloadIdentity(credential) => {
+                credential.get_token(scopes).await.context(
+                    ErrorKind::Credential,
+                    "error getting workload identity credential",
+                )
+            }
             DefaultAzureCredentialKind::AppService(credential) => {
                 credential.get_token(scopes).await.context(
                     ErrorKind::Credential,
@@ -206,6 +301,10 @@ impl TokenCredential for DefaultAzureCredentialKind {
     /// Clear the credential's cache.
     async fn clear_cache(&self) -> azure_core::Result<()> {
         match self {
+            DefaultAzureCredentialKind::Environment(credential) => credential.clear_cache().await,
+            DefaultAzureCredentialKind::WorkloadIdentity(credential) => {
+                credential.clear_cache().await
+            }
             DefaultAzureCredentialKind::AppService(credential) => credential.clear_cache().await,
             DefaultAzureCredentialKind::VirtualMachine(credential) => {
                 credential.clear_cache().await
@@ -220,13 +319,23 @@ impl TokenCredential for DefaultAzureCredentialKind {
 ///
 /// The following credential types if enabled will be tried, in order:
 ///
-/// * `ManagedIdentityCredential`
+/// * [`EnvironmentCredential`]
+/// * [`WorkloadIdentityCredential`]
+/// * [`AppServiceManagedIdentityCredential`]
+/// * [`VirtualMachineManagedIdentityCredential`]
 /// * `AzureCliCredential`
 ///
+/// This allows the same application to authenticate as a service principal or workload identity in Azure Kubernetes Service,
+/// as a managed identity in Azure App Service or on virtual machines, and as the developer when run locally.
+/// Credentials that are not configured, for example because their environment variables are not set, are skipped.
+///
+/// Each time a token is needed, the credentials are tried in order again, so a credential that becomes unavailable
+/// falls back to the next. To try credentials in a different order, or your own credentials, use a [`ChainedTokenCredential`].
+///
 /// Consult the documentation of these credential types for more information on how they attempt authentication.
 #[derive(Debug)]
 pub struct DefaultAzureCredential {
-    sources: Vec<DefaultAzureCredentialKind>,
+    chain: Arc<ChainedTokenCredential>,
     cache: TokenCache,
 }
 
@@ -252,69 +361,39 @@ impl DefaultAzureCredential {
 
     /// Creates a `DefaultAzureCredential` with specified sources.
     fn with_sources(sources: Vec<DefaultAzureCredentialKind>) -> azure_core::Result<Arc<Self>> {
+        let sources = sources
+            .into_iter()
+            .map(|source| {
+                (
+                    source.name().to_owned(),
+                    Arc::new(source) as Arc<dyn TokenCredential>,
+                )
+            })
+            .collect();
         Ok(Arc::new(DefaultAzureCredential {
-            sources,
+            chain: ChainedTokenCredential::with_named_sources(sources, None)?,
             cache: TokenCache::new(),
         }))
     }
-
-    /// Try to fetch a token using each of the credential sources until one succeeds
-    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
-        let mut errors = Vec::new();
-        for source in &self.sources {
-            let token_res = source.get_token(scopes).await;
-
-            match token_res {
-                Ok(token) => return Ok(token),
-                Err(error) => errors.push(error),
-            }
-        }
-        Err(Error::with_message(ErrorKind::Credential, || {
-            format!(
-                "Multiple errors were encountered while attempting to authenticate:\n{}",
-                format_aggregate_error(&errors)
-            )
-        }))
-    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl TokenCredential for DefaultAzureCredential {
     async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
-        self.cache.get_token(scopes, self.get_token(scopes)).await
+        self.cache
+            .get_token(scopes, self.chain.get_token(scopes))
+            .await
     }
 
     /// Clear the credential's cache.
     async fn clear_cache(&self) -> azure_core::Result<()> {
         // clear the internal cache as well as each of the underlying providers
         self.cache.clear().await?;
-
-        for source in &self.sources {
-            source.clear_cache().await?;
-        }
-
-        Ok(())
+        self.chain.clear_cache().await
     }
 }
 
-fn format_aggregate_error(errors: &[Error]) -> String {
-    use std::error::Error;
-    errors
-        .iter()
-        .map(|e| {
-            let mut current: Option<&dyn Error> = Some(e);
-            let mut stack = vec![];
-            while let Some(err) = current.take() {
-                stack.push(err.to_string());
-                current = err.source();
-            }
-            stack.join(" - ")
-        })
-        .collect::<Vec<String>>()
-        .join("\n")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -322,6 +401,8 @@ mod tests {
     #[test]
     fn test_builder_included_credential_flags() {
         let builder = DefaultAzureCredentialBuilder::new();
+        assert!(builder.include_environment_credential);
+        assert!(builder.include_workload_identity_credential);
         #[cfg(not(target_arch = "wasm32"))]
         assert!(builder.include_azure_cli_credential);
         assert!(builder.include_app_service_managed_identity_credential);
@@ -351,6 +432,40 @@ mod tests {
         assert_eq!(
             builder.included(),
             vec![
+                DefaultAzureCredentialType::Environment,
+                DefaultAzureCredentialType::WorkloadIdentity,
+                DefaultAzureCredentialType::AppService,
+                DefaultAzureCredentialType::VirtualMachine,
+                DefaultAzureCredentialType::AzureCli,
+            ]
+        );
+    }
+
+    /// test excluding environment credential
+    #[test]
+    fn test_exclude_environment_credential() {
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder.exclude_environment_credential();
+        assert_eq!(
+            builder.included(),
+            vec![
+                DefaultAzureCredentialType::WorkloadIdentity,
+                DefaultAzureCredentialType::AppService,
+                DefaultAzureCredentialType::VirtualMachine,
+                DefaultAzureCredentialType::AzureCli,
+            ]
+        );
+    }
+
+    /// test excluding workload identity credential
+    #[test]
+    fn test_exclude_workload_identity_credential() {
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder.exclude_workload_identity_credential();
+        assert_eq!(
+            builder.included(),
+            vec![
+                DefaultAzureCredentialType::Environment,
                 DefaultAzureCredentialType::AppService,
                 DefaultAzureCredentialType::VirtualMachine,
                 DefaultAzureCredentialType::AzureCli,
@@ -366,6 +481,8 @@ mod tests {
         assert_eq!(
             builder.included(),
             vec![
+                DefaultAzureCredentialType::Environment,
+                DefaultAzureCredentialType::WorkloadIdentity,
                 DefaultAzureCredentialType::AppService,
                 DefaultAzureCredentialType::AzureCli,
             ]
@@ -380,6 +497,8 @@ mod tests {
         assert_eq!(
             builder.included(),
             vec![
+                DefaultAzureCredentialType::Environment,
+                DefaultAzureCredentialType::WorkloadIdentity,
                 DefaultAzureCredentialType::AppService,
                 DefaultAzureCredentialType::VirtualMachine,
             ]
@@ -393,7 +512,33 @@ mod tests {
         builder.exclude_managed_identity_credential();
         assert_eq!(
             builder.included(),
-            vec![DefaultAzureCredentialType::AzureCli,]
+            vec![
+                DefaultAzureCredentialType::Environment,
+                DefaultAzureCredentialType::WorkloadIdentity,
+                DefaultAzureCredentialType::AzureCli,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unavailable_sources_are_named_like_attempts() {
+        let mut options = TokenCredentialOptions::default();
+        options.set_env(crate::env::Env::from(&[][..]));
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder
+            .with_options(options)
+            .exclude_workload_identity_credential()
+            .exclude_virtual_machine_managed_identity_credential();
+        #[cfg(not(target_arch = "wasm32"))]
+        builder.exclude_azure_cli_credential();
+
+        let error = builder.build().unwrap_err();
+
+        let message = error.to_string();
+        assert!(message.contains("\nEnvironmentCredential: "), "{message}");
+        assert!(
+            message.contains("\nAppServiceManagedIdentityCredential: "),
+            "{message}"
         );
     }
 }
@@ -0,0 +1,428 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{credentials::cache::TokenCache, refresh_token, TokenCredentialOptions};
+use azure_core::{
+    content_type,
+    credentials::{AccessToken, Secret, TokenCredential},
+    error::{http_response_from_body, Error, ErrorKind, ResultExt},
+    headers,
+    json::from_json,
+    sleep::sleep,
+    HttpClient, Method, Request, Response, Url,
+};
+use serde::Deserialize;
+use std::{
+    str,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use time::OffsetDateTime;
+use tracing::debug;
+use typespec_client_core::http::Model;
+use url::form_urlencoded;
+
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const OFFLINE_ACCESS_SCOPE: &str = "offline_access";
+const DEFAULT_POLLING_INTERVAL: u64 = 5;
+const SLOW_DOWN_INCREMENT: Duration = Duration::from_secs(5);
+
+type DeviceCodeCallback = dyn Fn(&DeviceCodeInfo) + Send + Sync;
+
+/// Enables authentication to Microsoft Entra ID using the device code flow, for machines without a web browser.
+///
+/// When a token is needed, the credential requests a device code and passes a [`DeviceCodeInfo`] to a callback,
+/// which should tell the user to visit the verification URL on another device and enter the user code.
+/// The credential then waits until the user has authenticated.
+///
+/// Access tokens are cached, and the refresh token is used to get new access tokens without asking the user to authenticate again.
+///
+/// More information on the device code flow can be found here:
+/// <https://learn.microsoft.com/entra/identity-platform/v2-oauth2-device-code>
+///
+/// # Example
+///
+/// ```no_run
+/// # use azure_identity::{DeviceCodeCredential, TokenCredentialOptions};
+/// # fn main() -> azure_core::Result<()> {
+/// let credential = DeviceCodeCredential::new(
+///     "organizations".to_owned(),
+///     "04b07795-8ddb-461a-bbee-02f9e1bf7b46".to_owned(),
+///     |info| eprintln!("{}", info.message()),
+///     TokenCredentialOptions::default(),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DeviceCodeCredential {
+    http_client: Arc<dyn HttpClient>,
+    authority_host: Url,
+    tenant_id: String,
+    client_id: String,
+    callback: Box<DeviceCodeCallback>,
+    refresh_token: Mutex<Option<Secret>>,
+    cache: TokenCache,
+    slow_down_increment: Duration,
+}
+
+impl DeviceCodeCredential {
+    /// Create a new `DeviceCodeCredential` that passes each device code to `callback`.
+    ///
+    /// The `client_id` must be that of an App Registration that allows public client flows.
+    pub fn new<F>(
+        tenant_id: String,
+        client_id: String,
+        callback: F,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<DeviceCodeCredential>>
+    where
+        F: Fn(&DeviceCodeInfo) + Send + Sync + 'static,
+    {
+        let options = options.into();
+        Ok(Arc::new(DeviceCodeCredential {
+            http_client: options.http_client(),
+            authority_host: options.authority_host()?,
+            tenant_id,
+            client_id,
+            callback: Box::new(callback),
+            refresh_token: Mutex::new(None),
+            cache: TokenCache::new(),
+            slow_down_increment: SLOW_DOWN_INCREMENT,
+        }))
+    }
+
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let refresh_token = self
+            .refresh_token
+            .lock()
+            .expect("refresh token lock poisoned")
+            .clone();
+        if let Some(refresh_token) = refresh_token {
+            match refresh_token::exchange(
+                self.http_client.clone(),
+                &self.authority_host,
+                &self.tenant_id,
+                &self.client_id,
+                None,
+                &refresh_token,
+                scopes,
+            )
+            .await
+            {
+                Ok(response) => {
+                    self.set_refresh_token(Some(response.refresh_token().clone()));
+                    return Ok(AccessToken::new(
+                        response.access_token().clone(),
+                        OffsetDateTime::now_utc() + Duration::from_secs(response.expires_in()),
+                    ));
+                }
+                Err(error) => debug!("failed to refresh token, requesting a device code: {error}"),
+            }
+        }
+
+        self.authenticate(scopes).await
+    }
+
+    /// Request a device code, and poll the token endpoint until the user has authenticated.
+    async fn authenticate(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let mut scope = scopes.join(" ");
+        if !scopes.contains(&OFFLINE_ACCESS_SCOPE) {
+            scope = format!("{scope} {OFFLINE_ACCESS_SCOPE}");
+        }
+        let encoded = form_urlencoded::Serializer::new(String::new())
+            .append_pair("client_id", &self.client_id)
+            .append_pair("scope", &scope)
+            .finish();
+        let rsp = self.post("devicecode", encoded).await?;
+        let rsp_status = rsp.status();
+        if !rsp_status.is_success() {
+            let rsp_body = rsp.into_body().collect().await?;
+            return Err(http_response_from_body(rsp_status, &rsp_body).into_error())
+                .context(ErrorKind::Credential, "request device code error");
+        }
+        let device_code: DeviceCodeResponse = rsp.deserialize_body_into().await?;
+        (self.callback)(&device_code.info);
+
+        let expires_on = OffsetDateTime::now_utc() + Duration::from_secs(device_code.expires_in);
+        let mut interval = Duration::from_secs(device_code.interval);
+        let encoded = form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", DEVICE_CODE_GRANT_TYPE)
+            .append_pair("client_id", &self.client_id)
+            .append_pair("device_code", device_code.device_code.secret())
+            .finish();
+        loop {
+            sleep(interval).await;
+
+            let rsp = self.post("token", encoded.clone()).await?;
+            let rsp_status = rsp.status();
+            if rsp_status.is_success() {
+                let response: TokenResponse = rsp.deserialize_body_into().await?;
+                self.set_refresh_token(response.refresh_token);
+                return Ok(AccessToken::new(
+                    response.access_token,
+                    OffsetDateTime::now_utc() + Duration::from_secs(response.expires_in),
+                ));
+            }
+
+            let rsp_body = rsp.into_body().collect().await?;
+            let error = from_json::<_, TokenErrorResponse>(&rsp_body).ok();
+            match error.as_ref().map(|error| error.error.as_str()) {
+                Some("authorization_pending") => {}
+                Some("slow_down") => interval += self.slow_down_increment,
+                _ => {
+                    return Err(http_response_from_body(rsp_status, &rsp_body).into_error())
+                        .context(ErrorKind::Credential, "device code authentication error")
+                }
+            }
+
+            if OffsetDateTime::now_utc() >= expires_on {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "device code expired before the user authenticated",
+                ));
+            }
+        }
+    }
+
+    async fn post(&self, endpoint: &str, encoded: String) -> azure_core::Result<Response> {
+        let url = self
+            .authority_host
+            .join(&format!("/{}/oauth2/v2.0/{endpoint}", self.tenant_id))
+            .with_context(ErrorKind::DataConversion, || {
+                format!(
+                    "The supplied tenant id could not be url encoded: {}",
+                    self.tenant_id
+                )
+            })?;
+
+        let mut req = Request::new(url, Method::Post);
+        req.insert_header(
+            headers::CONTENT_TYPE,
+            content_type::APPLICATION_X_WWW_FORM_URLENCODED,
+        );
+        req.set_body(encoded);
+        self.http_client.execute_request(&req).await
+    }
+
+    fn set_refresh_token(&self, refresh_token: Option<Secret>) {
+        *self
+            .refresh_token
+            .lock()
+            .expect("refresh token lock poisoned") = refresh_token;
+    }
+}
+
+impl std::fmt::Debug for DeviceCodeCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceCodeCredential")
+            .field("authority_host", &self.authority_host)
+            .field("tenant_id", &self.tenant_id)
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The details a user needs to authenticate a [`DeviceCodeCredential`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceCodeInfo {
+    user_code: String,
+    verification_uri: String,
+    message: String,
+}
+
+impl DeviceCodeInfo {
+    /// The code the user enters at the verification URL.
+    pub fn user_code(&self) -> &str {
+        &self.user_code
+    }
+    /// The URL the user visits to authenticate.
+    pub fn verification_uri(&self) -> &str {
+        &self.verification_uri
+    }
+    /// Instructions for the user, including the user code and verification URL.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[derive(Model, Deserialize, Debug)]
+struct DeviceCodeResponse {
+    device_code: Secret,
+    expires_in: u64,
+    #[serde(default = "default_polling_interval")]
+    interval: u64,
+    #[serde(flatten)]
+    info: DeviceCodeInfo,
+}
+
+fn default_polling_interval() -> u64 {
+    DEFAULT_POLLING_INTERVAL
+}
+
+#[derive(Model, Deserialize, Debug)]
+struct TokenResponse {
+    expires_in: u64,
+    access_token: Secret,
+    refresh_token: Option<Secret>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenErrorResponse {
+    error: String,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for DeviceCodeCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache.get_token(scopes, self.get_token(scopes)).await
+    }
+
+    /// Clear cached access tokens. The refresh token is kept, so the user need not authenticate again.
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{json_response, options, MockHttpClient, MockRequest};
+    use azure_core::StatusCode;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Instant,
+    };
+
+    const SCOPE: &str = "https://vault.azure.net/.default";
+
+    /// Answers device code requests, and answers polls of the token endpoint with each of `errors` in turn before
+    /// returning a token that expires in `expires_in` seconds.
+    fn authority(errors: &'static [&'static str], expires_in: u64) -> Arc<MockHttpClient> {
+        let polls = AtomicUsize::new(0);
+        MockHttpClient::new(move |request: &MockRequest| {
+            assert_eq!(request.method, Method::Post);
+            assert_eq!(request.field("client_id"), "client");
+            match request.path.as_str() {
+                "/tenant/oauth2/v2.0/devicecode" => {
+                    assert_eq!(request.field("scope"), format!("{SCOPE} offline_access"));
+                    json_response(
+                        StatusCode::Ok,
+                        r#"{"device_code":"device","user_code":"ABC123","verification_uri":"https://microsoft.com/devicelogin","expires_in":900,"interval":0,"message":"To sign in, enter the code ABC123"}"#,
+                    )
+                }
+                "/tenant/oauth2/v2.0/token" if request.field("grant_type") == "refresh_token" => {
+                    assert_eq!(request.field("refresh_token"), "refresh");
+                    assert_eq!(request.field("scope"), SCOPE);
+                    json_response(
+                        StatusCode::Ok,
+                        r#"{"token_type":"Bearer","scope":"https://vault.azure.net/.default","expires_in":3599,"ext_expires_in":3599,"access_token":"refreshed","refresh_token":"refresh"}"#,
+                    )
+                }
+                "/tenant/oauth2/v2.0/token" => {
+                    assert_eq!(request.field("grant_type"), DEVICE_CODE_GRANT_TYPE);
+                    assert_eq!(request.field("device_code"), "device");
+                    match errors.get(polls.fetch_add(1, Ordering::SeqCst)) {
+                        Some(error) => json_response(
+                            StatusCode::BadRequest,
+                            format!(r#"{{"error":"{error}","error_description":"{error}"}}"#),
+                        ),
+                        None => json_response(
+                            StatusCode::Ok,
+                            format!(
+                                r#"{{"token_type":"Bearer","expires_in":{expires_in},"access_token":"token","refresh_token":"refresh"}}"#
+                            ),
+                        ),
+                    }
+                }
+                path => panic!("unexpected request to {path}"),
+            }
+        })
+    }
+
+    fn credential(
+        http_client: Arc<MockHttpClient>,
+        infos: Arc<Mutex<Vec<DeviceCodeInfo>>>,
+    ) -> azure_core::Result<Arc<dyn TokenCredential>> {
+        Ok(DeviceCodeCredential::new(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            move |info| infos.lock().unwrap().push(info.clone()),
+            options(http_client),
+        )?)
+    }
+
+    #[tokio::test]
+    async fn polls_until_authenticated() -> azure_core::Result<()> {
+        let http_client = authority(&["authorization_pending", "authorization_pending"], 3599);
+        let infos = Arc::new(Mutex::new(Vec::new()));
+        let credential = credential(http_client.clone(), infos.clone())?;
+
+        let token = credential.get_token(&[SCOPE]).await?;
+        assert_eq!(token.token.secret(), "token");
+        assert_eq!(http_client.requests(), 4);
+        {
+            let infos = infos.lock().unwrap();
+            assert_eq!(infos.len(), 1);
+            assert_eq!(infos[0].user_code(), "ABC123");
+            assert_eq!(
+                infos[0].verification_uri(),
+                "https://microsoft.com/devicelogin"
+            );
+        }
+
+        credential.get_token(&[SCOPE]).await?;
+        assert_eq!(http_client.requests(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refreshes_expired_token() -> azure_core::Result<()> {
+        let http_client = authority(&[], 0);
+        let infos = Arc::new(Mutex::new(Vec::new()));
+        let credential = credential(http_client.clone(), infos.clone())?;
+
+        assert_eq!(
+            credential.get_token(&[SCOPE]).await?.token.secret(),
+            "token"
+        );
+        let token = credential.get_token(&[SCOPE]).await?;
+        assert_eq!(token.token.secret(), "refreshed");
+        assert_eq!(http_client.requests(), 3);
+        assert_eq!(infos.lock().unwrap().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn slows_down_polling() -> azure_core::Result<()> {
+        let http_client = authority(&["slow_down", "slow_down"], 3599);
+        let mut credential = DeviceCodeCredential::new(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            |_| {},
+            options(http_client.clone()),
+        )?;
+        // The authority's interval is zero, so polls are only delayed by the increments.
+        let increment = Duration::from_millis(50);
+        Arc::get_mut(&mut credential).unwrap().slow_down_increment = increment;
+        let credential: Arc<dyn TokenCredential> = credential;
+
+        let start = Instant::now();
+        credential.get_token(&[SCOPE]).await?;
+        // The first slow_down adds one increment before the second poll, and the second adds another before the third.
+        assert!(start.elapsed() >= increment * 3);
+        assert_eq!(http_client.requests(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn declined_is_credential_error() -> azure_core::Result<()> {
+        let credential = credential(
+            authority(&["authorization_declined"], 3599),
+            Arc::new(Mutex::new(Vec::new())),
+        )?;
+        let err = credential.get_token(&[SCOPE]).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        Ok(())
+    }
+}
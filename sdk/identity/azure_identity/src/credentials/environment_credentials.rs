@@ -0,0 +1,173 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+#[cfg(feature = "client_certificate")]
+use crate::ClientCertificateCredential;
+use crate::{ClientSecretCredential, TokenCredentialOptions};
+use azure_core::{
+    credentials::{AccessToken, TokenCredential},
+    error::{Error, ErrorKind},
+};
+use std::sync::Arc;
+
+const AZURE_CLIENT_SECRET_ENV_KEY: &str = "AZURE_CLIENT_SECRET";
+#[cfg(feature = "client_certificate")]
+const AZURE_CLIENT_CERTIFICATE_PATH_ENV_KEY: &str = "AZURE_CLIENT_CERTIFICATE_PATH";
+
+/// Enables authentication to Microsoft Entra ID as a service principal configured by environment variables.
+///
+/// The credential used depends on which variables are set, in order:
+///
+/// * `AZURE_CLIENT_SECRET` - [`ClientSecretCredential`], which also requires `AZURE_TENANT_ID` and `AZURE_CLIENT_ID`.
+/// * `AZURE_CLIENT_CERTIFICATE_PATH` - `ClientCertificateCredential`, which also requires `AZURE_TENANT_ID`, `AZURE_CLIENT_ID`,
+///   and `AZURE_CLIENT_CERTIFICATE_PASSWORD`. Requires the `client_certificate` feature.
+#[derive(Debug)]
+pub struct EnvironmentCredential {
+    source: EnvironmentCredentialKind,
+}
+
+/// Types of `TokenCredential` supported by `EnvironmentCredential`
+#[derive(Debug)]
+enum EnvironmentCredentialKind {
+    ClientSecret(Arc<ClientSecretCredential>),
+    #[cfg(feature = "client_certificate")]
+    ClientCertificate(Arc<ClientCertificateCredential>),
+}
+
+impl EnvironmentCredential {
+    /// Create a new `EnvironmentCredential` from environment variables.
+    ///
+    /// Fails if no service principal is configured, or its configuration is incomplete.
+    pub fn new(options: impl Into<TokenCredentialOptions>) -> azure_core::Result<Arc<Self>> {
+        let options = options.into();
+        let env = options.env();
+
+        if env.var(AZURE_CLIENT_SECRET_ENV_KEY).is_ok() {
+            return Ok(Arc::new(Self {
+                source: EnvironmentCredentialKind::ClientSecret(ClientSecretCredential::from_env(
+                    options,
+                )?),
+            }));
+        }
+
+        #[cfg(feature = "client_certificate")]
+        if env.var(AZURE_CLIENT_CERTIFICATE_PATH_ENV_KEY).is_ok() {
+            return Ok(Arc::new(Self {
+                source: EnvironmentCredentialKind::ClientCertificate(
+                    ClientCertificateCredential::from_env(options)?,
+                ),
+            }));
+        }
+
+        #[cfg(feature = "client_certificate")]
+        let variables =
+            "AZURE_CLIENT_SECRET or AZURE_CLIENT_CERTIFICATE_PATH environment variables";
+        #[cfg(not(feature = "client_certificate"))]
+        let variables = "AZURE_CLIENT_SECRET environment variable";
+        Err(Error::with_message(ErrorKind::Credential, || {
+            format!("environment credential requires {variables}")
+        }))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for EnvironmentCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        match &self.source {
+            EnvironmentCredentialKind::ClientSecret(credential) => {
+                credential.get_token(scopes).await
+            }
+            #[cfg(feature = "client_certificate")]
+            EnvironmentCredentialKind::ClientCertificate(credential) => {
+                credential.get_token(scopes).await
+            }
+        }
+    }
+
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        match &self.source {
+            EnvironmentCredentialKind::ClientSecret(credential) => credential.clear_cache().await,
+            #[cfg(feature = "client_certificate")]
+            EnvironmentCredentialKind::ClientCertificate(credential) => {
+                credential.clear_cache().await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        env::Env,
+        mock::{self, json_response, MockHttpClient},
+    };
+    use azure_core::StatusCode;
+
+    fn options(env: &[(&str, &str)]) -> TokenCredentialOptions {
+        let mut options = mock::options(MockHttpClient::new(|request| {
+            assert_eq!(request.field("client_secret"), "secret");
+            json_response(
+                StatusCode::Ok,
+                r#"{"token_type":"Bearer","expires_in":3599,"ext_expires_in":3599,"access_token":"token"}"#,
+            )
+        }));
+        options.set_env(Env::from(env));
+        options
+    }
+
+    #[tokio::test]
+    async fn uses_client_secret() -> azure_core::Result<()> {
+        let credential = EnvironmentCredential::new(options(&[
+            ("AZURE_TENANT_ID", "tenant"),
+            ("AZURE_CLIENT_ID", "client"),
+            ("AZURE_CLIENT_SECRET", "secret"),
+            ("AZURE_CLIENT_CERTIFICATE_PATH", "/no/such/cert.pfx"),
+        ]))?;
+        assert!(matches!(
+            credential.source,
+            EnvironmentCredentialKind::ClientSecret(_)
+        ));
+        let token = credential
+            .get_token(&["https://vault.azure.net/.default"])
+            .await?;
+        assert_eq!(token.token.secret(), "token");
+        Ok(())
+    }
+
+    #[test]
+    fn requires_service_principal() {
+        let err = EnvironmentCredential::new(options(&[
+            ("AZURE_TENANT_ID", "tenant"),
+            ("AZURE_CLIENT_ID", "client"),
+        ]))
+        .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+
+        // An incomplete service principal is an error, rather than falling back to another.
+        let err = EnvironmentCredential::new(options(&[
+            ("AZURE_CLIENT_ID", "client"),
+            ("AZURE_CLIENT_SECRET", "secret"),
+        ]))
+        .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+    }
+
+    #[cfg(feature = "client_certificate")]
+    #[test]
+    fn uses_client_certificate() {
+        let err = EnvironmentCredential::new(options(&[
+            ("AZURE_TENANT_ID", "tenant"),
+            ("AZURE_CLIENT_ID", "client"),
+            ("AZURE_CLIENT_CERTIFICATE_PATH", "/no/such/cert.pfx"),
+            ("AZURE_CLIENT_CERTIFICATE_PASSWORD", "password"),
+        ]))
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("failed to read client certificate"),
+            "{err}"
+        );
+    }
+}
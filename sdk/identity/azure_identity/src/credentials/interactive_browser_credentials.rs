@@ -0,0 +1,445 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    authorization_code_flow, credentials::cache::TokenCache, refresh_token, TokenCredentialOptions,
+};
+use azure_core::{
+    credentials::{AccessToken, Secret, TokenCredential},
+    error::{Error, ErrorKind, ResultExt},
+    HttpClient, Url,
+};
+use futures::channel::oneshot;
+use oauth2::{AuthorizationCode, ClientId, TokenResponse};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use time::OffsetDateTime;
+use tracing::debug;
+
+const OFFLINE_ACCESS_SCOPE: &str = "offline_access";
+const AUTHORIZATION_TIMEOUT: Duration = Duration::from_secs(300);
+const LISTENER_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+type OpenUrl = dyn Fn(&Url) + Send + Sync;
+
+/// Enables authentication to Microsoft Entra ID by signing in with a web browser.
+///
+/// When a token is needed, the credential listens for a redirect on a random port of the loopback interface,
+/// and opens the authorization URL in the user's web browser. Once the user has signed in, the authorization code
+/// the browser is redirected with is exchanged for tokens using Proof Key for Code Exchange (PKCE).
+///
+/// Access tokens are cached, and the refresh token is used to get new access tokens without asking the user to sign in again.
+///
+/// The App Registration must allow public client flows and have `http://127.0.0.1` as a redirect URI.
+/// The listener's port is added to the redirect URI, which Microsoft Entra ID ignores when matching loopback redirect URIs.
+/// More information on the authorization code flow can be found here:
+/// <https://learn.microsoft.com/entra/identity-platform/v2-oauth2-auth-code-flow>
+pub struct InteractiveBrowserCredential {
+    http_client: Arc<dyn HttpClient>,
+    authority_host: Url,
+    tenant_id: String,
+    client_id: String,
+    open_url: Box<OpenUrl>,
+    refresh_token: Mutex<Option<Secret>>,
+    cache: TokenCache,
+}
+
+impl InteractiveBrowserCredential {
+    /// Create a new `InteractiveBrowserCredential` that opens the authorization URL in the system's default web browser.
+    ///
+    /// If the browser cannot be started, the authorization URL is printed to stderr instead.
+    pub fn new(
+        tenant_id: String,
+        client_id: String,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<InteractiveBrowserCredential>> {
+        Self::with_browser(
+            tenant_id,
+            client_id,
+            |url| {
+                if let Err(error) = open_browser(url) {
+                    debug!("failed to open a web browser: {error}");
+                    eprintln!("To sign in, open this URL in a web browser: {url}");
+                }
+            },
+            options,
+        )
+    }
+
+    /// Create a new `InteractiveBrowserCredential` that passes the authorization URL to `open_url`,
+    /// which should open it in a web browser.
+    pub fn with_browser<F>(
+        tenant_id: String,
+        client_id: String,
+        open_url: F,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<InteractiveBrowserCredential>>
+    where
+        F: Fn(&Url) + Send + Sync + 'static,
+    {
+        let options = options.into();
+        Ok(Arc::new(InteractiveBrowserCredential {
+            http_client: options.http_client(),
+            authority_host: options.authority_host()?,
+            tenant_id,
+            client_id,
+            open_url: Box::new(open_url),
+            refresh_token: Mutex::new(None),
+            cache: TokenCache::new(),
+        }))
+    }
+
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let refresh_token = self
+            .refresh_token
+            .lock()
+            .expect("refresh token lock poisoned")
+            .clone();
+        if let Some(refresh_token) = refresh_token {
+            match refresh_token::exchange(
+                self.http_client.clone(),
+                &self.authority_host,
+                &self.tenant_id,
+                &self.client_id,
+                None,
+                &refresh_token,
+                scopes,
+            )
+            .await
+            {
+                Ok(response) => {
+                    self.set_refresh_token(Some(response.refresh_token().clone()));
+                    return Ok(AccessToken::new(
+                        response.access_token().clone(),
+                        OffsetDateTime::now_utc() + Duration::from_secs(response.expires_in()),
+                    ));
+                }
+                Err(error) => debug!("failed to refresh token, signing in again: {error}"),
+            }
+        }
+
+        self.authenticate(scopes).await
+    }
+
+    /// Open the authorization URL, and exchange the authorization code the browser is redirected with for tokens.
+    async fn authenticate(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .context(ErrorKind::Io, "failed to start redirect listener")?;
+        listener
+            .set_nonblocking(true)
+            .context(ErrorKind::Io, "failed to start redirect listener")?;
+        let port = listener
+            .local_addr()
+            .context(ErrorKind::Io, "failed to start redirect listener")?
+            .port();
+        // Redirect to the address the listener is bound to, rather than `localhost`, which may resolve to `::1` first.
+        let redirect_url = Url::parse(&format!("http://{}:{port}", Ipv4Addr::LOCALHOST))?;
+
+        let mut scopes = scopes.to_vec();
+        if !scopes.contains(&OFFLINE_ACCESS_SCOPE) {
+            scopes.push(OFFLINE_ACCESS_SCOPE);
+        }
+        let flow = authorization_code_flow::authorize(
+            ClientId::new(self.client_id.clone()),
+            None,
+            &self.authority_host,
+            &self.tenant_id,
+            redirect_url,
+            &scopes,
+        )?;
+
+        let redirect = listen(listener, AUTHORIZATION_TIMEOUT);
+        (self.open_url)(&flow.authorize_url);
+        let query = redirect.await.map_err(|_| {
+            Error::message(
+                ErrorKind::Credential,
+                "redirect listener stopped unexpectedly",
+            )
+        })??;
+
+        if query.get("state") != Some(flow.csrf_state.secret()) {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "authorization response state does not match the request",
+            ));
+        }
+        if let Some(error) = query.get("error") {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                let description = query.get("error_description").map_or("", String::as_str);
+                format!("authorization failed: {error}: {description}")
+            }));
+        }
+        let code = query.get("code").cloned().unwrap_or_default();
+
+        let response = flow
+            .exchange(self.http_client.clone(), AuthorizationCode::new(code))
+            .await?;
+        self.set_refresh_token(
+            response
+                .refresh_token()
+                .map(|refresh_token| Secret::new(refresh_token.secret().clone())),
+        );
+        Ok(AccessToken::new(
+            Secret::new(response.access_token().secret().clone()),
+            OffsetDateTime::now_utc() + response.expires_in().unwrap_or_default(),
+        ))
+    }
+
+    fn set_refresh_token(&self, refresh_token: Option<Secret>) {
+        *self
+            .refresh_token
+            .lock()
+            .expect("refresh token lock poisoned") = refresh_token;
+    }
+}
+
+impl std::fmt::Debug for InteractiveBrowserCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InteractiveBrowserCredential")
+            .field("authority_host", &self.authority_host)
+            .field("tenant_id", &self.tenant_id)
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for InteractiveBrowserCredential {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.cache.get_token(scopes, self.get_token(scopes)).await
+    }
+
+    /// Clear cached access tokens. The refresh token is kept, so the user need not sign in again.
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        self.cache.clear().await
+    }
+}
+
+/// Open `url` in the system's default web browser.
+fn open_browser(url: &Url) -> io::Result<()> {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "windows") {
+        ("rundll32", &["url.dll,FileProtocolHandler"])
+    } else if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else {
+        ("xdg-open", &[])
+    };
+    std::process::Command::new(program)
+        .args(args)
+        .arg(url.as_str())
+        .spawn()
+        .map(|_| ())
+}
+
+type RedirectQuery = HashMap<String, String>;
+
+/// Wait on another thread for a browser to be redirected to `listener` with an authorization response,
+/// and receive the query parameters of the redirect.
+///
+/// The thread stops once the response is received, `timeout` passes, or the receiver is dropped.
+fn listen(
+    listener: TcpListener,
+    timeout: Duration,
+) -> oneshot::Receiver<azure_core::Result<RedirectQuery>> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let deadline = Instant::now() + timeout;
+        let result = loop {
+            if sender.is_canceled() {
+                return;
+            }
+            if Instant::now() >= deadline {
+                break Err(Error::message(
+                    ErrorKind::Credential,
+                    "timed out waiting for the browser to sign in",
+                ));
+            }
+            match listener.accept() {
+                Ok((mut stream, _)) => match read_redirect(&mut stream) {
+                    Ok(Some(query)) => break Ok(query),
+                    Ok(None) => {}
+                    Err(error) => debug!("failed to read request to redirect listener: {error}"),
+                },
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(LISTENER_POLL_INTERVAL);
+                }
+                Err(error) => {
+                    break Err(Error::full(
+                        ErrorKind::Io,
+                        error,
+                        "redirect listener failed",
+                    ))
+                }
+            }
+        };
+        let _ = sender.send(result);
+    });
+    receiver
+}
+
+/// Read a request from `stream`, and respond to it.
+///
+/// Returns the query parameters if the request is an authorization response, which has either a `code` or an `error`.
+fn read_redirect(stream: &mut TcpStream) -> io::Result<Option<RedirectQuery>> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    // Read the whole header, so that closing the connection does not reset it before the response is read.
+    let mut reader = BufReader::new(&*stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header != "\r\n" {
+        header.clear();
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let query: RedirectQuery = Url::parse("http://127.0.0.1")
+        .and_then(|url| url.join(target))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default();
+    let redirected = query.contains_key("code") || query.contains_key("error");
+
+    let (status, body) = if redirected {
+        (
+            "200 OK",
+            "Authentication complete. You can close this window.",
+        )
+    } else {
+        ("404 Not Found", "Not found.")
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(redirected.then_some(query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{json_response, options, MockHttpClient, MockRequest};
+    use azure_core::{Method, StatusCode};
+    use std::{
+        io::Read,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    const SCOPE: &str = "https://vault.azure.net/.default";
+
+    /// Exchanges the authorization code `code` for a token that expires immediately, and refresh tokens for a token that does not.
+    fn authority() -> Arc<MockHttpClient> {
+        MockHttpClient::new(|request: &MockRequest| {
+            assert_eq!(request.method, Method::Post);
+            assert_eq!(request.path, "/tenant/oauth2/v2.0/token");
+            assert_eq!(request.field("client_id"), "client");
+            match request.field("grant_type") {
+                "authorization_code" => {
+                    assert_eq!(request.field("code"), "code");
+                    assert!(!request.field("code_verifier").is_empty());
+                    assert!(request
+                        .field("redirect_uri")
+                        .starts_with("http://127.0.0.1:"));
+                    json_response(
+                        StatusCode::Ok,
+                        r#"{"token_type":"Bearer","expires_in":0,"access_token":"token","refresh_token":"refresh"}"#,
+                    )
+                }
+                "refresh_token" => {
+                    assert_eq!(request.field("refresh_token"), "refresh");
+                    json_response(
+                        StatusCode::Ok,
+                        r#"{"token_type":"Bearer","scope":"https://vault.azure.net/.default","expires_in":3599,"ext_expires_in":3599,"access_token":"refreshed","refresh_token":"refresh"}"#,
+                    )
+                }
+                grant_type => panic!("unexpected grant type {grant_type}"),
+            }
+        })
+    }
+
+    /// A browser that signs in by redirecting to the redirect listener with the query returned by `respond`,
+    /// which is passed the `state` of the authorization request.
+    fn browser(
+        respond: fn(&str) -> String,
+        opened: Arc<AtomicUsize>,
+    ) -> impl Fn(&Url) + Send + Sync + 'static {
+        move |url: &Url| {
+            opened.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(url.path(), "/tenant/oauth2/v2.0/authorize");
+            let query: RedirectQuery = url.query_pairs().into_owned().collect();
+            assert_eq!(query["client_id"], "client");
+            assert_eq!(query["code_challenge_method"], "S256");
+            assert_eq!(query["scope"], format!("{SCOPE} offline_access"));
+            let redirect_uri = Url::parse(&query["redirect_uri"]).unwrap();
+            assert_eq!(redirect_uri.host_str(), Some("127.0.0.1"));
+            let port = redirect_uri.port().unwrap();
+            let redirect = respond(&query["state"]);
+            thread::spawn(move || {
+                let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+                write!(
+                    stream,
+                    "GET /?{redirect} HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\n\r\n"
+                )
+                .unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).unwrap();
+                assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn signs_in_and_refreshes_token() -> azure_core::Result<()> {
+        let http_client = authority();
+        let opened = Arc::new(AtomicUsize::new(0));
+        let credential: Arc<dyn TokenCredential> = InteractiveBrowserCredential::with_browser(
+            "tenant".to_owned(),
+            "client".to_owned(),
+            browser(|state| format!("code=code&state={state}"), opened.clone()),
+            options(http_client.clone()),
+        )?;
+
+        assert_eq!(
+            credential.get_token(&[SCOPE]).await?.token.secret(),
+            "token"
+        );
+        assert_eq!(
+            credential.get_token(&[SCOPE]).await?.token.secret(),
+            "refreshed"
+        );
+        credential.get_token(&[SCOPE]).await?;
+        assert_eq!(opened.load(Ordering::SeqCst), 1);
+        assert_eq!(http_client.requests(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_failed_authorization() -> azure_core::Result<()> {
+        let responses: [fn(&str) -> String; 2] = [
+            |_| "code=code&state=forged".to_owned(),
+            |state| format!("error=access_denied&error_description=denied&state={state}"),
+        ];
+        for respond in responses {
+            let http_client = authority();
+            let credential: Arc<dyn TokenCredential> = InteractiveBrowserCredential::with_browser(
+                "tenant".to_owned(),
+                "client".to_owned(),
+                browser(respond, Arc::new(AtomicUsize::new(0))),
+                options(http_client.clone()),
+            )?;
+            let err = credential.get_token(&[SCOPE]).await.unwrap_err();
+            assert_eq!(err.kind(), &ErrorKind::Credential);
+            assert_eq!(http_client.requests(), 0);
+        }
+        Ok(())
+    }
+}
@@ -5,17 +5,26 @@
 //!
 //! Supported means currently include:
 //! * The environment
+//! * Workload identity
 //! * Azure CLI credentials cache
 //! * Managed identity
 //! * Client secret
+//! * Device code
+//! * Interactive browser sign-in
 mod app_service_managed_identity_credential;
 #[cfg(not(target_arch = "wasm32"))]
 mod azure_cli_credentials;
 mod cache;
+mod chained_token_credential;
 #[cfg(feature = "client_certificate")]
 mod client_certificate_credentials;
+mod client_secret_credentials;
 mod default_credentials;
+mod device_code_credentials;
+mod environment_credentials;
 mod imds_managed_identity_credentials;
+#[cfg(not(target_arch = "wasm32"))]
+mod interactive_browser_credentials;
 mod options;
 mod virtual_machine_managed_identity_credential;
 mod workload_identity_credentials;
@@ -23,11 +32,17 @@ mod workload_identity_credentials;
 pub use app_service_managed_identity_credential::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use azure_cli_credentials::*;
+pub use chained_token_credential::*;
 #[cfg(feature = "client_certificate")]
 pub use client_certificate_credentials::*;
+pub use client_secret_credentials::*;
 pub use default_credentials::*;
+pub use device_code_credentials::*;
+pub use environment_credentials::*;
 pub use imds_managed_identity_credentials::ImdsId;
 pub(crate) use imds_managed_identity_credentials::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use interactive_browser_credentials::*;
 pub use options::*;
 pub use virtual_machine_managed_identity_credential::*;
 pub use workload_identity_credentials::*;
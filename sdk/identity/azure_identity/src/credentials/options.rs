@@ -58,6 +58,11 @@ impl TokenCredentialOptions {
     pub(crate) fn env(&self) -> &Env {
         &self.env
     }
+
+    #[cfg(test)]
+    pub(crate) fn set_env(&mut self, env: Env) {
+        self.env = env;
+    }
 }
 
 impl From<Arc<dyn azure_core::HttpClient>> for TokenCredentialOptions {
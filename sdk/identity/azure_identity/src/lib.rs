@@ -3,10 +3,14 @@
 
 #![doc = include_str!("../README.md")]
 
+#[cfg(not(target_arch = "wasm32"))]
 mod authorization_code_flow;
 mod credentials;
 mod env;
 mod federated_credentials_flow;
+#[cfg(test)]
+mod mock;
+#[cfg(not(target_arch = "wasm32"))]
 mod oauth2_http_client;
 mod refresh_token;
 mod timeout;
@@ -0,0 +1,94 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! A mock authority for testing credentials without connecting to Microsoft Entra ID.
+
+use crate::TokenCredentialOptions;
+use azure_core::{headers::Headers, Body, HttpClient, Method, Request, Response, StatusCode};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use url::form_urlencoded;
+
+type Handler = dyn Fn(&MockRequest) -> Response + Send + Sync;
+
+/// An [`HttpClient`] that answers every request by calling a handler instead of sending it.
+pub(crate) struct MockHttpClient {
+    handler: Box<Handler>,
+    requests: AtomicUsize,
+}
+
+impl MockHttpClient {
+    pub(crate) fn new(
+        handler: impl Fn(&MockRequest) -> Response + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            handler: Box::new(handler),
+            requests: AtomicUsize::new(0),
+        })
+    }
+
+    /// The number of requests answered.
+    pub(crate) fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+impl std::fmt::Debug for MockHttpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockHttpClient")
+            .field("requests", &self.requests)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl HttpClient for MockHttpClient {
+    async fn execute_request(&self, request: &Request) -> azure_core::Result<Response> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        let form = match request.body() {
+            Body::Bytes(bytes) => form_urlencoded::parse(bytes).into_owned().collect(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Body::SeekableStream(_) => HashMap::new(),
+        };
+        Ok((self.handler)(&MockRequest {
+            method: *request.method(),
+            path: request.url().path().to_owned(),
+            form,
+        }))
+    }
+}
+
+/// A request sent to a [`MockHttpClient`], with its form-encoded body parsed.
+#[derive(Debug)]
+pub(crate) struct MockRequest {
+    pub(crate) method: Method,
+    pub(crate) path: String,
+    pub(crate) form: HashMap<String, String>,
+}
+
+impl MockRequest {
+    /// Gets the value of the form field `name`, or an empty string if it was not sent.
+    pub(crate) fn field(&self, name: &str) -> &str {
+        self.form.get(name).map_or("", String::as_str)
+    }
+}
+
+/// Creates a JSON response.
+pub(crate) fn json_response(status: StatusCode, body: impl Into<String>) -> Response {
+    let mut headers = Headers::new();
+    headers.insert("content-type", "application/json");
+    Response::from_bytes(status, headers, body.into())
+}
+
+/// Creates options that send every request to `http_client`, with a local authority host.
+pub(crate) fn options(http_client: Arc<MockHttpClient>) -> TokenCredentialOptions {
+    let mut options = TokenCredentialOptions::from(http_client as Arc<dyn HttpClient>);
+    options.set_authority_host("http://localhost:8400".to_owned());
+    options
+}
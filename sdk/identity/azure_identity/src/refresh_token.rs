@@ -17,20 +17,22 @@ use std::sync::Arc;
 use url::form_urlencoded;
 
 /// Exchange a refresh token for a new access token and refresh token.
-#[allow(dead_code)]
 pub async fn exchange(
     http_client: Arc<dyn HttpClient>,
+    authority_host: &Url,
     tenant_id: &str,
     client_id: &str,
     client_secret: Option<&str>,
     refresh_token: &Secret,
+    scopes: &[&str],
 ) -> azure_core::Result<RefreshTokenResponse> {
     let encoded = {
         let mut encoded = &mut form_urlencoded::Serializer::new(String::new());
         encoded = encoded
             .append_pair("grant_type", "refresh_token")
             .append_pair("client_id", client_id)
-            .append_pair("refresh_token", refresh_token.secret());
+            .append_pair("refresh_token", refresh_token.secret())
+            .append_pair("scope", &scopes.join(" "));
         // optionally add the client secret
         if let Some(client_secret) = client_secret {
             encoded = encoded.append_pair("client_secret", client_secret);
@@ -38,9 +40,11 @@ pub async fn exchange(
         encoded.finish()
     };
 
-    let url = Url::parse(&format!(
-        "https://login.microsoftonline.com/{tenant_id}/oauth2/v2.0/token"
-    ))?;
+    let url = authority_host
+        .join(&format!("/{tenant_id}/oauth2/v2.0/token"))
+        .with_context(ErrorKind::DataConversion, || {
+            format!("The supplied tenant id could not be url encoded: {tenant_id}")
+        })?;
 
     let mut req = Request::new(url, Method::Post);
     req.insert_header(
@@ -154,10 +158,12 @@ mod tests {
     fn ensure_that_exchange_is_send() {
         require_send(exchange(
             azure_core::new_http_client(),
+            &Url::parse("https://login.microsoftonline.com").unwrap(),
             "UNUSED",
             "UNUSED",
             None,
             &Secret::new("UNUSED"),
+            &["UNUSED"],
         ));
     }
 }
@@ -1,2 +1,1177 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
+
+use crate::{
+    models::{BlobProperties, BlobType, BlockLookupList},
+    pipeline::{BlobPipeline, SasUrl},
+    BlobClientOptions, CommitBlockListOptions, DeleteBlobOptions, DownloadBlobOptions,
+    DownloadToOptions, GetBlobPropertiesOptions, SharedKeyCredential, StageBlockOptions,
+    TransferProgress, UploadBlobOptions, UploadBlocksOptions,
+};
+use azure_core::{
+    base64,
+    credentials::TokenCredential,
+    error::{Error, ErrorKind, ResultExt},
+    headers::{BLOB_CONTENT_TYPE, BLOB_TYPE, CONTENT_LENGTH, CONTENT_TYPE},
+    request_options::{IfMatchCondition, Range},
+    xml, AppendToUrlQuery, Body, Etag, Method, Request, Response, SeekableStream, Url,
+};
+use bytes::{Bytes, BytesMut};
+use futures::{AsyncReadExt, AsyncWrite, AsyncWriteExt, StreamExt, TryStreamExt};
+use std::{
+    ops::Range as StdRange,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tracing::debug;
+
+/// The default size of each block staged by [`BlobClient::upload_blocks()`].
+const DEFAULT_BLOCK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// The default number of blocks staged concurrently by [`BlobClient::upload_blocks()`].
+const DEFAULT_PARALLELISM: usize = 4;
+
+/// The maximum number of blocks a block blob can contain.
+const MAX_BLOCKS: u64 = 50_000;
+
+/// The default size of each range requested by [`BlobClient::download_to()`].
+const DEFAULT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+/// The default number of times [`BlobClient::download_to()`] resumes a range after a connection failure.
+const DEFAULT_MAX_RESUME_ATTEMPTS: u32 = 5;
+
+/// A client for working with a specific blob in a storage account.
+///
+/// You can get a `BlobClient` by calling [`BlobContainerClient::blob_client()`](crate::clients::BlobContainerClient::blob_client()).
+#[derive(Debug, Clone)]
+pub struct BlobClient {
+    container_name: String,
+    blob_name: String,
+    pipeline: BlobPipeline,
+}
+
+impl BlobClient {
+    /// Creates a new BlobClient, using Entra ID authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The full URL of the Blob service, for example `https://myaccount.blob.core.windows.net/`.
+    /// * `container_name` - The name of the container containing the blob.
+    /// * `blob_name` - The name of the blob.
+    /// * `credential` - An implementation of [`TokenCredential`](azure_core::credentials::TokenCredential) that can provide an Entra ID token to use when authenticating.
+    /// * `options` - Optional configuration for the client.
+    pub fn new(
+        endpoint: &str,
+        container_name: &str,
+        blob_name: &str,
+        credential: Arc<dyn TokenCredential>,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        Ok(Self::from_pipeline(
+            BlobPipeline::from_token_credential(
+                endpoint.parse()?,
+                credential,
+                options.client_options,
+            ),
+            container_name,
+            blob_name,
+        ))
+    }
+
+    /// Creates a new BlobClient, using Shared Key authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The full URL of the Blob service, for example `https://myaccount.blob.core.windows.net/`.
+    /// * `container_name` - The name of the container containing the blob.
+    /// * `blob_name` - The name of the blob.
+    /// * `credential` - The account name and key used to sign each request.
+    /// * `options` - Optional configuration for the client.
+    pub fn with_shared_key(
+        endpoint: &str,
+        container_name: &str,
+        blob_name: &str,
+        credential: SharedKeyCredential,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        Ok(Self::from_pipeline(
+            BlobPipeline::from_shared_key(endpoint.parse()?, credential, options.client_options),
+            container_name,
+            blob_name,
+        ))
+    }
+
+    /// Creates a new BlobClient from a URL that carries a Shared Access Signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `sas_url` - The URL of the blob followed by the SAS, for example `https://myaccount.blob.core.windows.net/mycontainer/myblob?sv=...&sig=...`.
+    /// * `options` - Optional configuration for the client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// use azure_storage_blob::clients::BlobClient;
+    ///
+    /// # let sas_url: String = panic!("this is a non-running example");
+    /// let blob_client = BlobClient::from_sas_url(&sas_url, None).unwrap();
+    /// blob_client.upload("hello, world", None).await.unwrap();
+    /// # }
+    /// ```
+    pub fn from_sas_url(
+        sas_url: &str,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        let sas_url = SasUrl::parse(sas_url)?;
+        let container_name = sas_url.container_name()?.to_string();
+        let blob_name = sas_url.blob_name()?;
+        Ok(Self::from_pipeline(
+            BlobPipeline::from_sas(sas_url.endpoint, sas_url.token, options.client_options),
+            &container_name,
+            &blob_name,
+        ))
+    }
+
+    pub(crate) fn from_pipeline(
+        pipeline: BlobPipeline,
+        container_name: &str,
+        blob_name: &str,
+    ) -> Self {
+        Self {
+            container_name: container_name.to_string(),
+            blob_name: blob_name.to_string(),
+            pipeline,
+        }
+    }
+
+    /// Returns the name of the container containing the blob.
+    pub fn container_name(&self) -> &str {
+        &self.container_name
+    }
+
+    /// Returns the name of the blob.
+    pub fn blob_name(&self) -> &str {
+        &self.blob_name
+    }
+
+    /// Gets the URL of the blob.
+    pub fn url(&self) -> Url {
+        self.pipeline.url(&[&self.container_name, &self.blob_name])
+    }
+
+    /// Uploads data to a block blob, replacing the blob if it already exists.
+    ///
+    /// The data is sent in a single request. Use this for small and medium-sized blobs.
+    ///
+    /// # Arguments
+    /// * `data` - The content of the blob.
+    /// * `options` - Optional parameters for the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::{clients::BlobClient, UploadBlobOptions};
+    /// # let blob_client: BlobClient = panic!("this is a non-running example");
+    /// let options = UploadBlobOptions {
+    ///     content_type: Some("text/plain".into()),
+    ///     ..Default::default()
+    /// };
+    /// blob_client.upload("hello, world", Some(options)).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn upload(
+        &self,
+        data: impl Into<Body>,
+        options: Option<UploadBlobOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let mut url = self.url();
+        options.timeout.append_to_url_query(&mut url);
+
+        let body: Body = data.into();
+        let mut req = Request::new(url, Method::Put);
+        req.insert_header(BLOB_TYPE, BlobType::BlockBlob.as_ref());
+        req.insert_header(CONTENT_LENGTH, body.len().to_string());
+        if let Some(content_type) = options.content_type {
+            req.insert_header(CONTENT_TYPE, content_type);
+        }
+        if let Some(metadata) = &options.metadata {
+            for m in metadata.iter() {
+                req.add_mandatory_header(&m);
+            }
+        }
+        req.add_optional_header(&options.lease_id);
+        req.add_optional_header(&options.if_match);
+        req.set_body(body);
+
+        self.pipeline
+            .send(options.method_options.context, &mut req)
+            .await
+    }
+
+    /// Downloads the blob.
+    ///
+    /// The returned [`Response`] streams the content of the blob as it is received.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::clients::BlobClient;
+    /// # let blob_client: BlobClient = panic!("this is a non-running example");
+    /// let content = blob_client
+    ///     .download(None)
+    ///     .await.unwrap()
+    ///     .into_body()
+    ///     .collect()
+    ///     .await.unwrap();
+    /// # }
+    /// ```
+    pub async fn download(
+        &self,
+        options: Option<DownloadBlobOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let mut url = self.url();
+        options.timeout.append_to_url_query(&mut url);
+
+        let mut req = Request::new(url, Method::Get);
+        if let Some(range) = &options.range {
+            req.insert_headers(range)?;
+        }
+        req.add_optional_header(&options.lease_id);
+        req.add_optional_header(&options.if_match);
+
+        self.pipeline
+            .send(options.method_options.context, &mut req)
+            .await
+    }
+
+    /// Deletes the blob.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    pub async fn delete(
+        &self,
+        options: Option<DeleteBlobOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let mut url = self.url();
+        options.timeout.append_to_url_query(&mut url);
+
+        let mut req = Request::new(url, Method::Delete);
+        req.add_optional_header(&options.lease_id);
+
+        self.pipeline
+            .send(options.method_options.context, &mut req)
+            .await
+    }
+
+    /// Reads the properties and metadata of the blob.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_properties(
+        &self,
+        options: Option<GetBlobPropertiesOptions<'_>>,
+    ) -> azure_core::Result<BlobProperties> {
+        let options = options.unwrap_or_default();
+        let mut url = self.url();
+        options.timeout.append_to_url_query(&mut url);
+
+        let mut req = Request::new(url, Method::Head);
+        req.add_optional_header(&options.lease_id);
+
+        let response: Response = self
+            .pipeline
+            .send(options.method_options.context, &mut req)
+            .await?;
+        BlobProperties::from_headers(response.headers())
+    }
+
+    /// Uploads a block that can later be committed as part of the blob with [`BlobClient::commit_block_list()`].
+    ///
+    /// # Arguments
+    /// * `block_id` - The base64-encoded ID of the block. All block IDs of a blob must have the same length before encoding.
+    /// * `data` - The content of the block.
+    /// * `options` - Optional parameters for the request.
+    pub async fn stage_block(
+        &self,
+        block_id: &str,
+        data: impl Into<Body>,
+        options: Option<StageBlockOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let mut url = self.url();
+        url.query_pairs_mut()
+            .append_pair("comp", "block")
+            .append_pair("blockid", block_id);
+        options.timeout.append_to_url_query(&mut url);
+
+        let body: Body = data.into();
+        let mut req = Request::new(url, Method::Put);
+        req.insert_header(CONTENT_LENGTH, body.len().to_string());
+        req.add_optional_header(&options.lease_id);
+        req.set_body(body);
+
+        self.pipeline
+            .send(options.method_options.context, &mut req)
+            .await
+    }
+
+    /// Writes the blob from a list of staged blocks, replacing the blob if it already exists.
+    ///
+    /// # Arguments
+    /// * `blocks` - The IDs of the blocks that make up the blob, in order.
+    /// * `options` - Optional parameters for the request.
+    pub async fn commit_block_list(
+        &self,
+        blocks: &BlockLookupList,
+        options: Option<CommitBlockListOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let mut url = self.url();
+        url.query_pairs_mut().append_pair("comp", "blocklist");
+        options.timeout.append_to_url_query(&mut url);
+
+        let body = xml::to_xml_with_root("BlockList", blocks)?;
+        let mut req = Request::new(url, Method::Put);
+        req.insert_header(CONTENT_TYPE, "application/xml");
+        req.insert_header(CONTENT_LENGTH, body.len().to_string());
+        if let Some(content_type) = options.content_type {
+            req.insert_header(BLOB_CONTENT_TYPE, content_type);
+        }
+        if let Some(metadata) = &options.metadata {
+            for m in metadata.iter() {
+                req.add_mandatory_header(&m);
+            }
+        }
+        req.add_optional_header(&options.lease_id);
+        req.add_optional_header(&options.if_match);
+        req.set_body(body);
+
+        self.pipeline
+            .send(options.method_options.context, &mut req)
+            .await
+    }
+
+    /// Uploads the content of a stream to a block blob, replacing the blob if it already exists.
+    ///
+    /// The stream is split into blocks that are staged concurrently and then committed with a single
+    /// [`BlobClient::commit_block_list()`]. Each block is buffered in memory, so a failed request only
+    /// resends that block, according to the client's retry options. Use this for blobs that are too large
+    /// to upload in a single request.
+    ///
+    /// # Arguments
+    /// * `stream` - The content of the blob, such as a `FileStream` over a local file.
+    /// * `options` - Optional parameters for the upload.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::{clients::BlobClient, ProgressCallback, UploadBlocksOptions};
+    /// # let blob_client: BlobClient = panic!("this is a non-running example");
+    /// # let stream: azure_core::BytesStream = panic!("this is a non-running example");
+    /// let options = UploadBlocksOptions {
+    ///     parallelism: Some(8),
+    ///     progress: Some(ProgressCallback::new(|progress| {
+    ///         println!("{} of {} bytes", progress.bytes_transferred, progress.total_bytes);
+    ///     })),
+    ///     ..Default::default()
+    /// };
+    /// blob_client.upload_blocks(stream, Some(options)).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn upload_blocks(
+        &self,
+        mut stream: impl SeekableStream,
+        options: Option<UploadBlocksOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let block_size = options.block_size.unwrap_or(DEFAULT_BLOCK_SIZE);
+        let parallelism = options.parallelism.unwrap_or(DEFAULT_PARALLELISM).max(1);
+        if block_size == 0 {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "the block size must be greater than zero",
+            ));
+        }
+
+        stream.reset().await?;
+        let total_bytes = stream.len() as u64;
+        if total_bytes.div_ceil(block_size) > MAX_BLOCKS {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "uploading {total_bytes} bytes in blocks of {block_size} bytes exceeds the limit of {MAX_BLOCKS} blocks"
+                )
+            }));
+        }
+
+        // Blocks are read one at a time, and only as fast as they can be staged,
+        // so at most `parallelism` blocks are held in memory.
+        let blocks = futures::stream::try_unfold(
+            (stream, 0usize, 0u64),
+            move |(mut stream, index, offset)| async move {
+                if offset >= total_bytes {
+                    return Ok(None);
+                }
+                let mut data = vec![0; (total_bytes - offset).min(block_size) as usize];
+                stream
+                    .read_exact(&mut data)
+                    .await
+                    .context(ErrorKind::Io, "failed to read a block from the stream")?;
+                let next_offset = offset + data.len() as u64;
+                Ok::<_, Error>(Some((
+                    (index, Bytes::from(data)),
+                    (stream, index + 1, next_offset),
+                )))
+            },
+        );
+
+        let bytes_transferred = AtomicU64::new(0);
+        let mut block_ids: Vec<(usize, String)> = blocks
+            .map_ok(|(index, data)| {
+                let stage_options = StageBlockOptions {
+                    method_options: options.method_options.clone(),
+                    lease_id: options.lease_id,
+                    timeout: options.timeout,
+                };
+                let bytes_transferred = &bytes_transferred;
+                let progress = &options.progress;
+                async move {
+                    let block_id = block_id(index);
+                    let len = data.len() as u64;
+                    self.stage_block(&block_id, data, Some(stage_options))
+                        .await?;
+                    let bytes_transferred =
+                        bytes_transferred.fetch_add(len, Ordering::SeqCst) + len;
+                    if let Some(progress) = progress {
+                        progress.report(TransferProgress {
+                            bytes_transferred,
+                            total_bytes,
+                        });
+                    }
+                    Ok((index, block_id))
+                }
+            })
+            .try_buffer_unordered(parallelism)
+            .try_collect()
+            .await?;
+        block_ids.sort_unstable_by_key(|(index, _)| *index);
+
+        let blocks = BlockLookupList {
+            latest: block_ids.into_iter().map(|(_, id)| id).collect(),
+        };
+        let commit_options = CommitBlockListOptions {
+            method_options: options.method_options,
+            content_type: options.content_type,
+            metadata: options.metadata,
+            lease_id: options.lease_id,
+            if_match: options.if_match,
+            timeout: options.timeout,
+        };
+        self.commit_block_list(&blocks, Some(commit_options)).await
+    }
+
+    /// Downloads the blob into `writer` using concurrent ranged requests.
+    ///
+    /// Ranges are requested in parallel but written in order. Every range is requested with
+    /// `If-Match` set to the ETag the blob had when the download started, so the download fails
+    /// instead of mixing two versions of the blob if it is modified in the meantime. If the connection
+    /// fails while a range is being read, the request is resumed from the last byte received.
+    ///
+    /// Returns the properties of the blob, including the ETag to pass as `if_match` when resuming
+    /// an interrupted download with [`DownloadToOptions::offset`].
+    ///
+    /// # Arguments
+    /// * `writer` - Where the content of the blob is written.
+    /// * `options` - Optional parameters for the download.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::{clients::BlobClient, DownloadToOptions};
+    /// # let blob_client: BlobClient = panic!("this is a non-running example");
+    /// let mut content = Vec::new();
+    /// let options = DownloadToOptions {
+    ///     parallelism: Some(8),
+    ///     ..Default::default()
+    /// };
+    /// let properties = blob_client
+    ///     .download_to(&mut content, Some(options))
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(properties.content_length, content.len() as u64);
+    /// # }
+    /// ```
+    pub async fn download_to(
+        &self,
+        writer: impl AsyncWrite + Unpin + Send,
+        options: Option<DownloadToOptions<'_>>,
+    ) -> azure_core::Result<BlobProperties> {
+        self.download_to_sink(WriterSink(writer), options.unwrap_or_default())
+            .await
+    }
+
+    /// Downloads the blob into a local file using concurrent ranged requests.
+    ///
+    /// The file is created or truncated. If [`DownloadToOptions::offset`] is set, the existing file is
+    /// instead truncated to that length and the download continues from there, so an interrupted
+    /// download can be resumed. It is an error for the offset to be past the end of the file.
+    /// See [`BlobClient::download_to()`] for details.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file to write.
+    /// * `options` - Optional parameters for the download.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::{clients::BlobClient, DownloadToOptions};
+    /// # use azure_core::request_options::IfMatchCondition;
+    /// # let blob_client: BlobClient = panic!("this is a non-running example");
+    /// let properties = match blob_client.download_to_file("large.bin", None).await {
+    ///     Ok(properties) => properties,
+    ///     Err(_) => {
+    ///         # let etag: String = panic!("this is a non-running example");
+    ///         // Continue from what was written, as long as the blob is unchanged.
+    ///         let written = std::fs::metadata("large.bin").unwrap().len();
+    ///         let options = DownloadToOptions {
+    ///             offset: Some(written),
+    ///             if_match: Some(IfMatchCondition::Match(etag)),
+    ///             ..Default::default()
+    ///         };
+    ///         blob_client.download_to_file("large.bin", Some(options)).await.unwrap()
+    ///     }
+    /// };
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio_fs")]
+    pub async fn download_to_file(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        options: Option<DownloadToOptions<'_>>,
+    ) -> azure_core::Result<BlobProperties> {
+        use tokio::io::AsyncSeekExt;
+
+        let options = options.unwrap_or_default();
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(options.offset.is_none())
+            .open(path)
+            .await?;
+        if let Some(offset) = options.offset {
+            // Extending the file would leave zeros where the skipped bytes should be.
+            let len = file.metadata().await?.len();
+            if offset > len {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("cannot resume at offset {offset} past the end of the file, which is {len} bytes")
+                }));
+            }
+            file.set_len(offset).await?;
+            file.seek(std::io::SeekFrom::Start(offset)).await?;
+        }
+        self.download_to_sink(FileSink(file), options).await
+    }
+
+    async fn download_to_sink(
+        &self,
+        mut sink: impl ChunkSink,
+        options: DownloadToOptions<'_>,
+    ) -> azure_core::Result<BlobProperties> {
+        let chunk_size = options.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+        let parallelism = options.parallelism.unwrap_or(DEFAULT_PARALLELISM).max(1);
+        if chunk_size == 0 {
+            return Err(Error::message(
+                ErrorKind::DataConversion,
+                "the chunk size must be greater than zero",
+            ));
+        }
+        if let Some(IfMatchCondition::NotMatch(_)) = &options.if_match {
+            return Err(Error::message(
+                ErrorKind::DataConversion,
+                "only IfMatchCondition::Match is supported when downloading in ranges",
+            ));
+        }
+
+        let properties = self
+            .get_properties(Some(GetBlobPropertiesOptions {
+                method_options: options.method_options.clone(),
+                lease_id: options.lease_id,
+                timeout: options.timeout,
+            }))
+            .await?;
+        if let Some(IfMatchCondition::Match(etag)) = &options.if_match {
+            if *etag != properties.etag.to_string() {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!(
+                        "the blob has changed: expected ETag {etag}, found {}",
+                        properties.etag
+                    )
+                }));
+            }
+        }
+
+        let total_bytes = properties.content_length;
+        let offset = options.offset.unwrap_or(0).min(total_bytes);
+        let ranges = (offset..total_bytes)
+            .step_by(chunk_size as usize)
+            .map(|start| start..(start + chunk_size).min(total_bytes));
+
+        let etag = properties.etag.clone();
+        let mut bytes_transferred = offset;
+        let mut chunks = futures::stream::iter(ranges)
+            .map(|range| self.download_range(range, &etag, &options))
+            .buffered(parallelism);
+        while let Some(chunk) = chunks.try_next().await? {
+            sink.write_chunk(&chunk).await?;
+            bytes_transferred += chunk.len() as u64;
+            if let Some(progress) = &options.progress {
+                progress.report(TransferProgress {
+                    bytes_transferred,
+                    total_bytes,
+                });
+            }
+        }
+        sink.flush().await?;
+
+        Ok(properties)
+    }
+
+    /// Downloads a range of the blob, resuming from the last byte received if the connection fails.
+    async fn download_range(
+        &self,
+        range: StdRange<u64>,
+        etag: &Etag,
+        options: &DownloadToOptions<'_>,
+    ) -> azure_core::Result<Bytes> {
+        let len = (range.end - range.start) as usize;
+        let max_resume_attempts = options
+            .max_resume_attempts
+            .unwrap_or(DEFAULT_MAX_RESUME_ATTEMPTS);
+        let mut data = BytesMut::with_capacity(len);
+        let mut resume_attempts = 0;
+
+        loop {
+            let start = range.start + data.len() as u64;
+            let download_options = DownloadBlobOptions {
+                method_options: options.method_options.clone(),
+                range: Some(Range::new(start, range.end)),
+                lease_id: options.lease_id,
+                if_match: Some(IfMatchCondition::Match(etag.to_string())),
+                timeout: options.timeout,
+            };
+            let mut body = self.download(Some(download_options)).await?.into_body();
+
+            let error = loop {
+                match body.next().await {
+                    Some(Ok(bytes)) => data.extend_from_slice(&bytes),
+                    Some(Err(error)) => break error,
+                    None if data.len() >= len => {
+                        data.truncate(len);
+                        return Ok(data.freeze());
+                    }
+                    None => {
+                        break Error::with_message(ErrorKind::Io, || {
+                            format!(
+                                "the response for range {}..{} ended after {} bytes",
+                                range.start,
+                                range.end,
+                                data.len()
+                            )
+                        })
+                    }
+                }
+            };
+
+            if error.kind() != &ErrorKind::Io || resume_attempts >= max_resume_attempts {
+                return Err(error);
+            }
+            resume_attempts += 1;
+            debug!(
+                "resuming range {}..{} at offset {} after error: {}",
+                range.start,
+                range.end,
+                range.start + data.len() as u64,
+                error
+            );
+        }
+    }
+}
+
+/// Where [`BlobClient::download_to_sink()`] writes each downloaded range.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+trait ChunkSink: Send {
+    async fn write_chunk(&mut self, chunk: &[u8]) -> azure_core::Result<()>;
+    async fn flush(&mut self) -> azure_core::Result<()>;
+}
+
+struct WriterSink<W>(W);
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<W: AsyncWrite + Unpin + Send> ChunkSink for WriterSink<W> {
+    async fn write_chunk(&mut self, chunk: &[u8]) -> azure_core::Result<()> {
+        Ok(self.0.write_all(chunk).await?)
+    }
+
+    async fn flush(&mut self) -> azure_core::Result<()> {
+        Ok(self.0.flush().await?)
+    }
+}
+
+#[cfg(feature = "tokio_fs")]
+struct FileSink(tokio::fs::File);
+
+#[cfg(feature = "tokio_fs")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl ChunkSink for FileSink {
+    async fn write_chunk(&mut self, chunk: &[u8]) -> azure_core::Result<()> {
+        use tokio::io::AsyncWriteExt;
+        Ok(self.0.write_all(chunk).await?)
+    }
+
+    async fn flush(&mut self) -> azure_core::Result<()> {
+        use tokio::io::AsyncWriteExt;
+        Ok(self.0.flush().await?)
+    }
+}
+
+/// Creates the ID of the block at `index`. Every ID encodes to the same length, as the service requires.
+fn block_id(index: usize) -> String {
+    base64::encode(format!("{index:08}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::ProgressCallback;
+    use azure_core::{
+        credentials::Secret,
+        headers::{Headers, ETAG, IF_MATCH, LAST_MODIFIED, MS_RANGE},
+        BytesStream, ClientOptions, Context, FixedRetryOptions, Policy, PolicyResult, RetryOptions,
+        StatusCode, TransportOptions,
+    };
+    use std::{sync::Mutex, time::Duration};
+
+    /// Records every request and fails the first attempt to stage the block with the given ID.
+    #[derive(Debug)]
+    struct MockTransport {
+        fail_once: Mutex<Option<String>>,
+        requests: Mutex<Vec<Request>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for MockTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.requests.lock().unwrap().push(request.clone());
+            let block_id = request
+                .url()
+                .query_pairs()
+                .find(|(name, _)| name == "blockid")
+                .map(|(_, value)| value.into_owned());
+            let mut fail_once = self.fail_once.lock().unwrap();
+            let status = if block_id.is_some() && *fail_once == block_id {
+                *fail_once = None;
+                StatusCode::ServiceUnavailable
+            } else {
+                StatusCode::Created
+            };
+            Ok(Response::from_bytes(status, Default::default(), Vec::new()))
+        }
+    }
+
+    fn blob_client(transport: Arc<dyn Policy>) -> BlobClient {
+        let mut client_options = ClientOptions::default();
+        client_options.set_transport(TransportOptions::new_custom_policy(transport));
+        client_options.set_retry(RetryOptions::fixed(
+            FixedRetryOptions::default().delay(Duration::from_millis(1)),
+        ));
+        let pipeline = BlobPipeline::from_sas(
+            "https://myaccount.blob.core.windows.net/".parse().unwrap(),
+            Secret::new("sig=test"),
+            client_options,
+        );
+        BlobClient::from_pipeline(pipeline, "container", "blob")
+    }
+
+    fn body(request: &Request) -> Bytes {
+        match request.body() {
+            Body::Bytes(bytes) => bytes.clone(),
+            body => panic!("unexpected body {body:?}"),
+        }
+    }
+
+    #[tokio::test]
+    pub async fn upload_blocks_stages_and_commits_in_order() {
+        let transport = Arc::new(MockTransport {
+            fail_once: Mutex::new(Some(block_id(1))),
+            requests: Mutex::new(Vec::new()),
+        });
+        let client = blob_client(transport.clone());
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let recorded = progress.clone();
+        let options = UploadBlocksOptions {
+            block_size: Some(4),
+            parallelism: Some(2),
+            progress: Some(ProgressCallback::new(move |p| {
+                recorded.lock().unwrap().push(p)
+            })),
+            content_type: Some("text/plain".to_string()),
+            ..Default::default()
+        };
+
+        client
+            .upload_blocks(BytesStream::new("hello, world!"), Some(options))
+            .await
+            .unwrap();
+
+        let requests = transport.requests.lock().unwrap();
+        // Four blocks, one of which is retried, then the commit.
+        assert_eq!(6, requests.len());
+
+        let mut staged: Vec<(String, Bytes)> = requests[..5]
+            .iter()
+            .map(|r| {
+                let id = r
+                    .url()
+                    .query_pairs()
+                    .find(|(name, _)| name == "blockid")
+                    .unwrap()
+                    .1
+                    .into_owned();
+                (id, body(r))
+            })
+            .collect();
+        staged.sort();
+        staged.dedup();
+        assert_eq!(
+            vec![
+                (block_id(0), Bytes::from("hell")),
+                (block_id(1), Bytes::from("o, w")),
+                (block_id(2), Bytes::from("orld")),
+                (block_id(3), Bytes::from("!")),
+            ],
+            staged
+        );
+
+        let commit = &requests[5];
+        assert!(commit.url().query().unwrap().contains("comp=blocklist"));
+        assert_eq!(
+            Some("text/plain"),
+            commit.headers().get_optional_str(&BLOB_CONTENT_TYPE)
+        );
+        assert_eq!(
+            format!(
+                "<BlockList><Latest>{}</Latest><Latest>{}</Latest><Latest>{}</Latest><Latest>{}</Latest></BlockList>",
+                block_id(0),
+                block_id(1),
+                block_id(2),
+                block_id(3)
+            ),
+            std::str::from_utf8(&body(commit)).unwrap()
+        );
+
+        let mut progress = progress.lock().unwrap().clone();
+        progress.sort_by_key(|p| p.bytes_transferred);
+        assert_eq!(4, progress.len());
+        assert_eq!(
+            TransferProgress {
+                bytes_transferred: 13,
+                total_bytes: 13
+            },
+            progress[3]
+        );
+    }
+
+    #[tokio::test]
+    pub async fn upload_blocks_commits_empty_blob() {
+        let transport = Arc::new(MockTransport {
+            fail_once: Mutex::new(None),
+            requests: Mutex::new(Vec::new()),
+        });
+        let client = blob_client(transport.clone());
+
+        client
+            .upload_blocks(BytesStream::new_empty(), None)
+            .await
+            .unwrap();
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "<BlockList/>",
+            std::str::from_utf8(&body(&requests[0])).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn block_ids_have_equal_length() {
+        assert_eq!(block_id(0).len(), block_id(49_999).len());
+    }
+
+    const CONTENT: &[u8] = b"0123456789abcdefghij";
+
+    /// Serves `CONTENT` as a blob. The first request for the range starting at `fail_at`
+    /// breaks the connection after two bytes of the body.
+    #[derive(Debug)]
+    struct MockBlob {
+        etag: &'static str,
+        fail_at: Mutex<Option<u64>>,
+        ranges: Mutex<Vec<String>>,
+    }
+
+    impl MockBlob {
+        fn new(fail_at: Option<u64>) -> Self {
+            Self {
+                etag: "\"0x8D1\"",
+                fail_at: Mutex::new(fail_at),
+                ranges: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for MockBlob {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let mut headers = Headers::new();
+            headers.insert(ETAG, self.etag);
+            headers.insert(LAST_MODIFIED, "Wed, 02 Oct 2024 17:32:15 GMT");
+
+            if *request.method() == Method::Head {
+                headers.insert(CONTENT_LENGTH, CONTENT.len().to_string());
+                return Ok(Response::from_bytes(StatusCode::Ok, headers, Vec::new()));
+            }
+
+            if request.headers().get_optional_str(&IF_MATCH) != Some(self.etag) {
+                return Ok(Response::from_bytes(
+                    StatusCode::PreconditionFailed,
+                    headers,
+                    Vec::new(),
+                ));
+            }
+
+            let range = request.headers().get_str(&MS_RANGE).unwrap().to_string();
+            self.ranges.lock().unwrap().push(range.clone());
+            let (start, end) = range
+                .strip_prefix("bytes=")
+                .and_then(|r| r.split_once('-'))
+                .unwrap();
+            let (start, end): (u64, u64) = (start.parse().unwrap(), end.parse().unwrap());
+            let data = Bytes::from_static(&CONTENT[start as usize..=end as usize]);
+
+            let mut fail_at = self.fail_at.lock().unwrap();
+            if *fail_at == Some(start) {
+                *fail_at = None;
+                let stream = futures::stream::iter(vec![
+                    Ok(data.slice(..2)),
+                    Err(Error::message(ErrorKind::Io, "connection reset")),
+                ]);
+                return Ok(Response::new(
+                    StatusCode::PartialContent,
+                    headers,
+                    Box::pin(stream),
+                ));
+            }
+            Ok(Response::from_bytes(
+                StatusCode::PartialContent,
+                headers,
+                data,
+            ))
+        }
+    }
+
+    #[tokio::test]
+    pub async fn download_to_resumes_interrupted_range() {
+        let transport = Arc::new(MockBlob::new(Some(8)));
+        let client = blob_client(transport.clone());
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let recorded = progress.clone();
+        let options = DownloadToOptions {
+            chunk_size: Some(8),
+            parallelism: Some(2),
+            progress: Some(ProgressCallback::new(move |p| {
+                recorded.lock().unwrap().push(p)
+            })),
+            ..Default::default()
+        };
+
+        let mut content = Vec::new();
+        let properties = client
+            .download_to(&mut content, Some(options))
+            .await
+            .unwrap();
+
+        assert_eq!(CONTENT, content.as_slice());
+        assert_eq!("\"0x8D1\"", properties.etag.to_string());
+
+        let mut ranges = transport.ranges.lock().unwrap().clone();
+        ranges.sort();
+        assert_eq!(
+            vec!["bytes=0-7", "bytes=10-15", "bytes=16-19", "bytes=8-15"],
+            ranges
+        );
+
+        let progress = progress.lock().unwrap();
+        assert_eq!(
+            vec![8, 16, 20],
+            progress
+                .iter()
+                .map(|p| p.bytes_transferred)
+                .collect::<Vec<_>>()
+        );
+        assert!(progress.iter().all(|p| p.total_bytes == 20));
+    }
+
+    #[tokio::test]
+    pub async fn download_to_starts_at_offset() {
+        let transport = Arc::new(MockBlob::new(None));
+        let client = blob_client(transport.clone());
+        let options = DownloadToOptions {
+            offset: Some(12),
+            if_match: Some(IfMatchCondition::Match("\"0x8D1\"".to_string())),
+            ..Default::default()
+        };
+
+        let mut content = Vec::new();
+        client
+            .download_to(&mut content, Some(options))
+            .await
+            .unwrap();
+
+        assert_eq!(&CONTENT[12..], content.as_slice());
+        assert_eq!(vec!["bytes=12-19"], *transport.ranges.lock().unwrap());
+    }
+
+    #[tokio::test]
+    pub async fn download_to_fails_when_blob_changed() {
+        let transport = Arc::new(MockBlob::new(None));
+        let client = blob_client(transport.clone());
+        let options = DownloadToOptions {
+            offset: Some(12),
+            if_match: Some(IfMatchCondition::Match("\"0x8D0\"".to_string())),
+            ..Default::default()
+        };
+
+        let mut content = Vec::new();
+        let error = client
+            .download_to(&mut content, Some(options))
+            .await
+            .unwrap_err();
+
+        assert_eq!(&ErrorKind::Other, error.kind());
+        assert!(content.is_empty());
+        assert!(transport.ranges.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn download_to_rejects_if_none_match() {
+        let transport = Arc::new(MockBlob::new(None));
+        let client = blob_client(transport.clone());
+        let options = DownloadToOptions {
+            if_match: Some(IfMatchCondition::NotMatch("\"0x8D1\"".to_string())),
+            ..Default::default()
+        };
+
+        let mut content = Vec::new();
+        let error = client
+            .download_to(&mut content, Some(options))
+            .await
+            .unwrap_err();
+
+        assert_eq!(&ErrorKind::DataConversion, error.kind());
+        assert!(content.is_empty());
+        assert!(transport.ranges.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn download_to_rejects_zero_chunk_size() {
+        let transport = Arc::new(MockBlob::new(None));
+        let client = blob_client(transport.clone());
+        let options = DownloadToOptions {
+            chunk_size: Some(0),
+            ..Default::default()
+        };
+
+        let mut content = Vec::new();
+        let error = client
+            .download_to(&mut content, Some(options))
+            .await
+            .unwrap_err();
+
+        assert_eq!(&ErrorKind::DataConversion, error.kind());
+        assert!(transport.ranges.lock().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "tokio_fs")]
+    #[tokio::test]
+    pub async fn download_to_file_rejects_offset_past_end_of_file() {
+        let path = std::env::temp_dir().join(format!(
+            "azure_storage_blob-{}-offset_past_end.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, &CONTENT[..4]).unwrap();
+        let transport = Arc::new(MockBlob::new(None));
+        let client = blob_client(transport.clone());
+        let options = DownloadToOptions {
+            offset: Some(12),
+            ..Default::default()
+        };
+
+        let error = client
+            .download_to_file(&path, Some(options))
+            .await
+            .unwrap_err();
+
+        assert_eq!(&ErrorKind::DataConversion, error.kind());
+        assert_eq!(&CONTENT[..4], std::fs::read(&path).unwrap().as_slice());
+        assert!(transport.ranges.lock().unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn download_to_gives_up_after_max_resume_attempts() {
+        let transport = Arc::new(MockBlob::new(Some(0)));
+        let client = blob_client(transport.clone());
+        let options = DownloadToOptions {
+            max_resume_attempts: Some(0),
+            ..Default::default()
+        };
+
+        let mut content = Vec::new();
+        let error = client
+            .download_to(&mut content, Some(options))
+            .await
+            .unwrap_err();
+
+        assert_eq!(&ErrorKind::Io, error.kind());
+    }
+}
@@ -1,2 +1,260 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
+
+use crate::{
+    clients::BlobClient,
+    models::{ContainerProperties, ListBlobsResult},
+    pipeline::{BlobPipeline, SasUrl},
+    BlobClientOptions, CreateContainerOptions, DeleteContainerOptions,
+    GetContainerPropertiesOptions, ListBlobsOptions, SharedKeyCredential,
+};
+use azure_core::{
+    credentials::TokenCredential, headers::BLOB_PUBLIC_ACCESS, request_options::Prefix,
+    AppendToUrlQuery, Method, Pager, Request, Response, Url,
+};
+use std::sync::Arc;
+
+/// A client for working with a specific container in a storage account.
+///
+/// You can get a `BlobContainerClient` by calling [`BlobServiceClient::blob_container_client()`](crate::BlobServiceClient::blob_container_client()).
+#[derive(Debug, Clone)]
+pub struct BlobContainerClient {
+    container_name: String,
+    pipeline: BlobPipeline,
+}
+
+impl BlobContainerClient {
+    /// Creates a new BlobContainerClient, using Entra ID authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The full URL of the Blob service, for example `https://myaccount.blob.core.windows.net/`.
+    /// * `container_name` - The name of the container.
+    /// * `credential` - An implementation of [`TokenCredential`](azure_core::credentials::TokenCredential) that can provide an Entra ID token to use when authenticating.
+    /// * `options` - Optional configuration for the client.
+    pub fn new(
+        endpoint: &str,
+        container_name: &str,
+        credential: Arc<dyn TokenCredential>,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        Ok(Self::from_pipeline(
+            BlobPipeline::from_token_credential(
+                endpoint.parse()?,
+                credential,
+                options.client_options,
+            ),
+            container_name,
+        ))
+    }
+
+    /// Creates a new BlobContainerClient, using Shared Key authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The full URL of the Blob service, for example `https://myaccount.blob.core.windows.net/`.
+    /// * `container_name` - The name of the container.
+    /// * `credential` - The account name and key used to sign each request.
+    /// * `options` - Optional configuration for the client.
+    pub fn with_shared_key(
+        endpoint: &str,
+        container_name: &str,
+        credential: SharedKeyCredential,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        Ok(Self::from_pipeline(
+            BlobPipeline::from_shared_key(endpoint.parse()?, credential, options.client_options),
+            container_name,
+        ))
+    }
+
+    /// Creates a new BlobContainerClient from a URL that carries a Shared Access Signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `sas_url` - The URL of the container followed by the SAS, for example `https://myaccount.blob.core.windows.net/mycontainer?sv=...&sig=...`.
+    /// * `options` - Optional configuration for the client.
+    pub fn from_sas_url(
+        sas_url: &str,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        let sas_url = SasUrl::parse(sas_url)?;
+        let container_name = sas_url.container_name()?.to_string();
+        Ok(Self::from_pipeline(
+            BlobPipeline::from_sas(sas_url.endpoint, sas_url.token, options.client_options),
+            &container_name,
+        ))
+    }
+
+    pub(crate) fn from_pipeline(pipeline: BlobPipeline, container_name: &str) -> Self {
+        Self {
+            container_name: container_name.to_string(),
+            pipeline,
+        }
+    }
+
+    /// Gets a [`BlobClient`] that can be used to access the blob with the specified name.
+    ///
+    /// # Arguments
+    /// * `blob_name` - The name of the blob. Forward slashes (`/`) separate virtual directories.
+    pub fn blob_client(&self, blob_name: &str) -> BlobClient {
+        BlobClient::from_pipeline(self.pipeline.clone(), &self.container_name, blob_name)
+    }
+
+    /// Returns the name of the container.
+    pub fn container_name(&self) -> &str {
+        &self.container_name
+    }
+
+    /// Gets the URL of the container.
+    pub fn url(&self) -> Url {
+        self.pipeline.url(&[&self.container_name])
+    }
+
+    fn container_url(&self) -> Url {
+        let mut url = self.url();
+        url.query_pairs_mut().append_pair("restype", "container");
+        url
+    }
+
+    /// Creates the container.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::clients::BlobContainerClient;
+    /// # let container_client: BlobContainerClient = panic!("this is a non-running example");
+    /// container_client.create(None).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn create(
+        &self,
+        options: Option<CreateContainerOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let mut url = self.container_url();
+        options.timeout.append_to_url_query(&mut url);
+
+        let mut req = Request::new(url, Method::Put);
+        if let Some(metadata) = &options.metadata {
+            for m in metadata.iter() {
+                req.add_mandatory_header(&m);
+            }
+        }
+        if let Some(public_access) = options.public_access {
+            req.insert_header(BLOB_PUBLIC_ACCESS, public_access.to_string());
+        }
+
+        self.pipeline
+            .send(options.method_options.context, &mut req)
+            .await
+    }
+
+    /// Deletes the container.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    pub async fn delete(
+        &self,
+        options: Option<DeleteContainerOptions<'_>>,
+    ) -> azure_core::Result<Response> {
+        let options = options.unwrap_or_default();
+        let mut url = self.container_url();
+        options.timeout.append_to_url_query(&mut url);
+
+        let mut req = Request::new(url, Method::Delete);
+        req.add_optional_header(&options.lease_id);
+
+        self.pipeline
+            .send(options.method_options.context, &mut req)
+            .await
+    }
+
+    /// Reads the properties and metadata of the container.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::clients::BlobContainerClient;
+    /// # let container_client: BlobContainerClient = panic!("this is a non-running example");
+    /// let properties = container_client.get_properties(None).await.unwrap();
+    /// println!("Last modified: {}", properties.last_modified);
+    /// # }
+    /// ```
+    pub async fn get_properties(
+        &self,
+        options: Option<GetContainerPropertiesOptions<'_>>,
+    ) -> azure_core::Result<ContainerProperties> {
+        let options = options.unwrap_or_default();
+        let mut url = self.container_url();
+        options.timeout.append_to_url_query(&mut url);
+
+        let mut req = Request::new(url, Method::Get);
+        req.add_optional_header(&options.lease_id);
+
+        let response: Response = self
+            .pipeline
+            .send(options.method_options.context, &mut req)
+            .await?;
+        ContainerProperties::from_headers(response.headers())
+    }
+
+    /// Lists the blobs in the container.
+    ///
+    /// # Arguments
+    /// * `options` - Optional parameters for the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::{clients::BlobContainerClient, ListBlobsOptions};
+    /// use futures::StreamExt;
+    ///
+    /// # let container_client: BlobContainerClient = panic!("this is a non-running example");
+    /// let options = ListBlobsOptions {
+    ///     prefix: Some("logs/".into()),
+    ///     ..Default::default()
+    /// };
+    /// let mut pager = container_client.list_blobs(Some(options)).unwrap();
+    /// while let Some(page) = pager.next().await {
+    ///     for blob in page.unwrap().deserialize_body().await.unwrap().blobs {
+    ///         println!("{} ({} bytes)", blob.name, blob.properties.content_length);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn list_blobs(
+        &self,
+        options: Option<ListBlobsOptions<'_>>,
+    ) -> azure_core::Result<Pager<ListBlobsResult>> {
+        let options = options.unwrap_or_default();
+        let mut url = self.container_url();
+        url.query_pairs_mut().append_pair("comp", "list");
+        options
+            .prefix
+            .map(Prefix::new)
+            .append_to_url_query(&mut url);
+        options.max_results.append_to_url_query(&mut url);
+        if options.include_metadata {
+            url.query_pairs_mut().append_pair("include", "metadata");
+        }
+        options.timeout.append_to_url_query(&mut url);
+
+        let base_request = Request::new(url, Method::Get);
+        Ok(self
+            .pipeline
+            .send_list_request(options.method_options.context, base_request))
+    }
+}
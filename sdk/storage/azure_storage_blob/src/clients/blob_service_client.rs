@@ -1,2 +1,173 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
+
+use crate::{
+    clients::BlobContainerClient,
+    models::ListContainersResult,
+    pipeline::{BlobPipeline, SasUrl},
+    BlobClientOptions, ListContainersOptions, SharedKeyCredential,
+};
+use azure_core::{
+    credentials::TokenCredential, request_options::Prefix, AppendToUrlQuery, Method, Pager,
+    Request, Url,
+};
+use std::sync::Arc;
+
+/// Client for the Azure Blob Storage service.
+///
+/// A `BlobServiceClient` works with the storage account as a whole.
+/// Use [`BlobServiceClient::blob_container_client()`] to get a client for a specific container.
+#[derive(Debug, Clone)]
+pub struct BlobServiceClient {
+    pipeline: BlobPipeline,
+}
+
+impl BlobServiceClient {
+    /// Creates a new BlobServiceClient, using Entra ID authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The full URL of the Blob service, for example `https://myaccount.blob.core.windows.net/`.
+    /// * `credential` - An implementation of [`TokenCredential`](azure_core::credentials::TokenCredential) that can provide an Entra ID token to use when authenticating.
+    /// * `options` - Optional configuration for the client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use azure_core::credentials::TokenCredential;
+    /// use azure_storage_blob::BlobServiceClient;
+    ///
+    /// # let credential: Arc<dyn TokenCredential> = panic!("this is a non-running example");
+    /// let client = BlobServiceClient::new("https://myaccount.blob.core.windows.net/", credential, None).unwrap();
+    /// ```
+    pub fn new(
+        endpoint: &str,
+        credential: Arc<dyn TokenCredential>,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        Ok(Self {
+            pipeline: BlobPipeline::from_token_credential(
+                endpoint.parse()?,
+                credential,
+                options.client_options,
+            ),
+        })
+    }
+
+    /// Creates a new BlobServiceClient, using Shared Key authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The full URL of the Blob service, for example `https://myaccount.blob.core.windows.net/`.
+    /// * `credential` - The account name and key used to sign each request.
+    /// * `options` - Optional configuration for the client.
+    ///
+    /// # Examples
+    ///
+    /// Connecting to a local Azurite instance with the well-known development account:
+    ///
+    /// ```rust,no_run
+    /// use azure_storage_blob::{BlobServiceClient, SharedKeyCredential};
+    ///
+    /// let client = BlobServiceClient::with_shared_key(
+    ///     "http://127.0.0.1:10000/devstoreaccount1",
+    ///     SharedKeyCredential::development(),
+    ///     None,
+    /// ).unwrap();
+    /// ```
+    pub fn with_shared_key(
+        endpoint: &str,
+        credential: SharedKeyCredential,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        Ok(Self {
+            pipeline: BlobPipeline::from_shared_key(
+                endpoint.parse()?,
+                credential,
+                options.client_options,
+            ),
+        })
+    }
+
+    /// Creates a new BlobServiceClient from a URL that carries an account Shared Access Signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `sas_url` - The URL of the Blob service followed by the SAS, for example `https://myaccount.blob.core.windows.net/?sv=...&sig=...`.
+    /// * `options` - Optional configuration for the client.
+    pub fn from_sas_url(
+        sas_url: &str,
+        options: Option<BlobClientOptions>,
+    ) -> azure_core::Result<Self> {
+        let options = options.unwrap_or_default();
+        let sas_url = SasUrl::parse(sas_url)?;
+        Ok(Self {
+            pipeline: BlobPipeline::from_sas(
+                sas_url.endpoint,
+                sas_url.token,
+                options.client_options,
+            ),
+        })
+    }
+
+    /// Gets a [`BlobContainerClient`] that can be used to access the container with the specified name.
+    ///
+    /// # Arguments
+    /// * `container_name` - The name of the container.
+    pub fn blob_container_client(&self, container_name: &str) -> BlobContainerClient {
+        BlobContainerClient::from_pipeline(self.pipeline.clone(), container_name)
+    }
+
+    /// Gets the endpoint of the Blob service this client is connected to.
+    pub fn endpoint(&self) -> &Url {
+        &self.pipeline.endpoint
+    }
+
+    /// Lists the containers in the storage account.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn doc() {
+    /// # use azure_storage_blob::BlobServiceClient;
+    /// use futures::StreamExt;
+    ///
+    /// # let client: BlobServiceClient = panic!("this is a non-running example");
+    /// let mut pager = client.list_containers(None).unwrap();
+    /// while let Some(page) = pager.next().await {
+    ///     for container in page.unwrap().deserialize_body().await.unwrap().containers {
+    ///         println!("{}", container.name);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn list_containers(
+        &self,
+        options: Option<ListContainersOptions<'_>>,
+    ) -> azure_core::Result<Pager<ListContainersResult>> {
+        let options = options.unwrap_or_default();
+        let mut url = self.pipeline.url(&[]);
+        url.query_pairs_mut().append_pair("comp", "list");
+        options
+            .prefix
+            .map(Prefix::new)
+            .append_to_url_query(&mut url);
+        options.max_results.append_to_url_query(&mut url);
+        if options.include_metadata {
+            url.query_pairs_mut().append_pair("include", "metadata");
+        }
+        options.timeout.append_to_url_query(&mut url);
+
+        let base_request = Request::new(url, Method::Get);
+        Ok(self
+            .pipeline
+            .send_list_request(options.method_options.context, base_request))
+    }
+}
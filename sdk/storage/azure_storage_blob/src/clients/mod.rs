@@ -1,6 +1,12 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+//! Clients used to communicate with Azure Blob Storage.
+
 mod blob_client;
 mod blob_container_client;
 mod blob_service_client;
+
+pub use blob_client::BlobClient;
+pub use blob_container_client::BlobContainerClient;
+pub use blob_service_client::BlobServiceClient;
@@ -1,4 +1,19 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-mod clients;
+//! Azure Blob Storage client library.
+//!
+//! Use [`BlobServiceClient`] to work with the storage account, [`BlobContainerClient`](clients::BlobContainerClient)
+//! to work with a single container, and [`BlobClient`](clients::BlobClient) to work with a single blob.
+
+pub mod clients;
+pub mod models;
+mod options;
+pub(crate) mod pipeline;
+
+#[doc(inline)]
+pub use clients::BlobServiceClient;
+
+pub use azure_storage_common::{credentials::SharedKeyCredential, sas};
+
+pub use options::*;
@@ -0,0 +1,230 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::metadata_from_headers;
+use azure_core::Model;
+use azure_core::{
+    date::{self, OffsetDateTime},
+    headers::{
+        Headers, BLOB_TYPE, CONTENT_LENGTH, CONTENT_TYPE, CREATION_TIME, ETAG, LAST_MODIFIED,
+        LEASE_STATE, LEASE_STATUS,
+    },
+    Etag, LeaseState, LeaseStatus,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use typespec_client_core::create_enum;
+
+create_enum!(
+    #[doc = "The type of a blob."]
+    BlobType,
+    (BlockBlob, "BlockBlob"),
+    (PageBlob, "PageBlob"),
+    (AppendBlob, "AppendBlob")
+);
+
+/// Properties of a blob.
+///
+/// Returned by [`BlobClient::get_properties()`](crate::clients::BlobClient::get_properties())
+/// and as part of each [`BlobItem`] when listing blobs.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct BlobProperties {
+    /// The entity tag associated with the blob.
+    #[serde(rename = "Etag")]
+    pub etag: Etag,
+
+    /// The time the blob, or its properties, were last modified.
+    #[serde(rename = "Last-Modified", with = "date::rfc1123")]
+    pub last_modified: OffsetDateTime,
+
+    /// The time the blob was created.
+    #[serde(rename = "Creation-Time", default, with = "date::rfc1123::option")]
+    pub creation_time: Option<OffsetDateTime>,
+
+    /// The size of the blob in bytes.
+    #[serde(rename = "Content-Length")]
+    pub content_length: u64,
+
+    /// The content type of the blob, if one was set.
+    #[serde(rename = "Content-Type", default)]
+    pub content_type: Option<String>,
+
+    /// The type of the blob.
+    #[serde(rename = "BlobType", default)]
+    pub blob_type: Option<BlobType>,
+
+    /// Whether the blob is leased.
+    #[serde(rename = "LeaseStatus", default)]
+    pub lease_status: Option<LeaseStatus>,
+
+    /// The lease state of the blob.
+    #[serde(rename = "LeaseState", default)]
+    pub lease_state: Option<LeaseState>,
+
+    /// User-defined metadata.
+    ///
+    /// When listing blobs, metadata is returned on the [`BlobItem`] instead.
+    #[serde(skip)]
+    pub metadata: HashMap<String, String>,
+}
+
+impl BlobProperties {
+    /// Reads blob properties from the headers of a "Get Blob Properties" or "Get Blob" response.
+    ///
+    /// For ranged downloads, `Content-Length` is the length of the range rather than the blob.
+    pub fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            etag: headers.get_as(&ETAG)?,
+            last_modified: headers.get_with(&LAST_MODIFIED, |v| date::parse_rfc1123(v.as_str()))?,
+            creation_time: headers
+                .get_optional_with(&CREATION_TIME, |v| date::parse_rfc1123(v.as_str()))?,
+            content_length: headers.get_as(&CONTENT_LENGTH)?,
+            content_type: headers.get_optional_string(&CONTENT_TYPE),
+            blob_type: headers.get_optional_as(&BLOB_TYPE)?,
+            lease_status: headers.get_optional_as(&LEASE_STATUS)?,
+            lease_state: headers.get_optional_as(&LEASE_STATE)?,
+            metadata: metadata_from_headers(headers),
+        })
+    }
+}
+
+/// A blob returned by [`BlobContainerClient::list_blobs()`](crate::clients::BlobContainerClient::list_blobs()).
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct BlobItem {
+    /// The name of the blob.
+    pub name: String,
+
+    /// The properties of the blob.
+    pub properties: BlobProperties,
+
+    /// User-defined metadata, present when requested with [`ListBlobsOptions::include_metadata`](crate::ListBlobsOptions::include_metadata).
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// A page of results from [`BlobContainerClient::list_blobs()`](crate::clients::BlobContainerClient::list_blobs()).
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Model)]
+#[typespec(format = "xml")]
+#[serde(rename_all = "PascalCase")]
+pub struct ListBlobsResult {
+    /// The prefix the results were filtered by, if any.
+    #[serde(default)]
+    pub prefix: Option<String>,
+
+    /// The blobs in this page.
+    #[serde(default, deserialize_with = "deserialize_blobs")]
+    pub blobs: Vec<BlobItem>,
+
+    /// The marker to request the next page, if any.
+    #[serde(default)]
+    pub next_marker: Option<String>,
+}
+
+fn deserialize_blobs<'de, D>(deserializer: D) -> Result<Vec<BlobItem>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Blobs {
+        #[serde(default, rename = "Blob")]
+        items: Vec<BlobItem>,
+    }
+
+    Ok(Blobs::deserialize(deserializer)?.items)
+}
+
+/// The blocks to commit with [`BlobClient::commit_block_list()`](crate::clients::BlobClient::commit_block_list()).
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct BlockLookupList {
+    /// The base64-encoded IDs of the blocks, in the order they appear in the blob.
+    ///
+    /// The most recently staged version of each block is committed.
+    #[serde(rename = "Latest")]
+    pub latest: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    pub fn deserialize_list_blobs() {
+        let xml = br#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://myaccount.blob.core.windows.net/" ContainerName="test">
+  <Blobs>
+    <Blob>
+      <Name>dir/file.txt</Name>
+      <Properties>
+        <Creation-Time>Wed, 02 Oct 2024 17:32:15 GMT</Creation-Time>
+        <Last-Modified>Wed, 02 Oct 2024 17:35:00 GMT</Last-Modified>
+        <Etag>0x8DCE3072B36A1B2</Etag>
+        <Content-Length>11</Content-Length>
+        <Content-Type>text/plain</Content-Type>
+        <Content-Encoding />
+        <BlobType>BlockBlob</BlobType>
+        <LeaseStatus>unlocked</LeaseStatus>
+        <LeaseState>available</LeaseState>
+        <ServerEncrypted>true</ServerEncrypted>
+      </Properties>
+      <Metadata>
+        <origin>upload</origin>
+      </Metadata>
+    </Blob>
+  </Blobs>
+  <NextMarker />
+</EnumerationResults>"#;
+
+        let result: ListBlobsResult = read_xml(xml).unwrap();
+        assert_eq!(1, result.blobs.len());
+
+        let blob = &result.blobs[0];
+        assert_eq!("dir/file.txt", blob.name);
+        assert_eq!("0x8DCE3072B36A1B2", blob.properties.etag.to_string());
+        assert_eq!(11, blob.properties.content_length);
+        assert_eq!(Some("text/plain"), blob.properties.content_type.as_deref());
+        assert_eq!(Some(BlobType::BlockBlob), blob.properties.blob_type);
+        assert!(blob.properties.creation_time.is_some());
+        assert_eq!(
+            Some("upload"),
+            blob.metadata.get("origin").map(String::as_str)
+        );
+    }
+
+    #[test]
+    pub fn blob_properties_from_headers() {
+        let mut headers = Headers::new();
+        headers.insert(ETAG, "\"0x8DCE3072B36A1B2\"");
+        headers.insert(LAST_MODIFIED, "Wed, 02 Oct 2024 17:32:15 GMT");
+        headers.insert(CONTENT_LENGTH, "1024");
+        headers.insert(CONTENT_TYPE, "application/octet-stream");
+        headers.insert(BLOB_TYPE, "BlockBlob");
+        headers.insert("x-ms-meta-origin", "upload");
+
+        let properties = BlobProperties::from_headers(&headers).unwrap();
+        assert_eq!(1024, properties.content_length);
+        assert_eq!(Some(BlobType::BlockBlob), properties.blob_type);
+        assert_eq!(None, properties.creation_time);
+        assert_eq!(
+            Some("upload"),
+            properties.metadata.get("origin").map(String::as_str)
+        );
+    }
+
+    #[test]
+    pub fn serialize_block_lookup_list() {
+        let blocks = BlockLookupList {
+            latest: vec!["AAAA".to_string(), "AAAB".to_string()],
+        };
+
+        let xml = azure_core::xml::to_xml_with_root("BlockList", &blocks).unwrap();
+        assert_eq!(
+            "<BlockList><Latest>AAAA</Latest><Latest>AAAB</Latest></BlockList>",
+            std::str::from_utf8(&xml).unwrap()
+        );
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::metadata_from_headers;
+use azure_core::Model;
+use azure_core::{
+    date::{self, OffsetDateTime},
+    headers::{Headers, BLOB_PUBLIC_ACCESS, ETAG, LAST_MODIFIED, LEASE_STATE, LEASE_STATUS},
+    Etag, LeaseState, LeaseStatus,
+};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use typespec_client_core::create_enum;
+
+create_enum!(
+    #[doc = "The level of anonymous read access allowed on a container."]
+    PublicAccessType,
+    (Container, "container"),
+    (Blob, "blob")
+);
+
+/// Properties of a blob container.
+///
+/// Returned by [`BlobContainerClient::get_properties()`](crate::clients::BlobContainerClient::get_properties())
+/// and as part of each [`ContainerItem`] when listing containers.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ContainerProperties {
+    /// The entity tag associated with the container.
+    pub etag: Etag,
+
+    /// The time the container, or its properties, were last modified.
+    #[serde(rename = "Last-Modified", with = "date::rfc1123")]
+    pub last_modified: OffsetDateTime,
+
+    /// Whether the container is leased.
+    #[serde(default)]
+    pub lease_status: Option<LeaseStatus>,
+
+    /// The lease state of the container.
+    #[serde(default)]
+    pub lease_state: Option<LeaseState>,
+
+    /// The level of anonymous read access allowed on the container, if any.
+    #[serde(default)]
+    pub public_access: Option<PublicAccessType>,
+
+    /// User-defined metadata.
+    ///
+    /// When listing containers, metadata is returned on the [`ContainerItem`] instead.
+    #[serde(skip)]
+    pub metadata: HashMap<String, String>,
+}
+
+impl ContainerProperties {
+    /// Reads container properties from the headers of a "Get Container Properties" response.
+    pub fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            etag: headers.get_as(&ETAG)?,
+            last_modified: headers.get_with(&LAST_MODIFIED, |v| date::parse_rfc1123(v.as_str()))?,
+            lease_status: headers.get_optional_as(&LEASE_STATUS)?,
+            lease_state: headers.get_optional_as(&LEASE_STATE)?,
+            public_access: headers.get_optional_as(&BLOB_PUBLIC_ACCESS)?,
+            metadata: metadata_from_headers(headers),
+        })
+    }
+}
+
+/// A container returned by [`BlobServiceClient::list_containers()`](crate::BlobServiceClient::list_containers()).
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ContainerItem {
+    /// The name of the container.
+    pub name: String,
+
+    /// The properties of the container.
+    pub properties: ContainerProperties,
+
+    /// User-defined metadata, present when requested with [`ListContainersOptions::include_metadata`](crate::ListContainersOptions::include_metadata).
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// A page of results from [`BlobServiceClient::list_containers()`](crate::BlobServiceClient::list_containers()).
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Model)]
+#[typespec(format = "xml")]
+#[serde(rename_all = "PascalCase")]
+pub struct ListContainersResult {
+    /// The prefix the results were filtered by, if any.
+    #[serde(default)]
+    pub prefix: Option<String>,
+
+    /// The containers in this page.
+    #[serde(default, deserialize_with = "deserialize_containers")]
+    pub containers: Vec<ContainerItem>,
+
+    /// The marker to request the next page, if any.
+    #[serde(default)]
+    pub next_marker: Option<String>,
+}
+
+fn deserialize_containers<'de, D>(deserializer: D) -> Result<Vec<ContainerItem>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Containers {
+        #[serde(default, rename = "Container")]
+        items: Vec<ContainerItem>,
+    }
+
+    Ok(Containers::deserialize(deserializer)?.items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    pub fn deserialize_list_containers() {
+        let xml = br#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://myaccount.blob.core.windows.net/">
+  <Prefix>test</Prefix>
+  <MaxResults>2</MaxResults>
+  <Containers>
+    <Container>
+      <Name>test-1</Name>
+      <Properties>
+        <Last-Modified>Wed, 02 Oct 2024 17:32:15 GMT</Last-Modified>
+        <Etag>"0x8DCE3072B36A1B2"</Etag>
+        <LeaseStatus>unlocked</LeaseStatus>
+        <LeaseState>available</LeaseState>
+        <HasImmutabilityPolicy>false</HasImmutabilityPolicy>
+        <HasLegalHold>false</HasLegalHold>
+      </Properties>
+      <Metadata>
+        <owner>contoso</owner>
+      </Metadata>
+    </Container>
+    <Container>
+      <Name>test-2</Name>
+      <Properties>
+        <Last-Modified>Thu, 03 Oct 2024 08:00:00 GMT</Last-Modified>
+        <Etag>"0x8DCE3072B36A1B3"</Etag>
+        <PublicAccess>blob</PublicAccess>
+      </Properties>
+    </Container>
+  </Containers>
+  <NextMarker>/myaccount/test-3</NextMarker>
+</EnumerationResults>"#;
+
+        let result: ListContainersResult = read_xml(xml).unwrap();
+        assert_eq!(Some("test"), result.prefix.as_deref());
+        assert_eq!(Some("/myaccount/test-3"), result.next_marker.as_deref());
+        assert_eq!(2, result.containers.len());
+
+        let first = &result.containers[0];
+        assert_eq!("test-1", first.name);
+        assert_eq!("\"0x8DCE3072B36A1B2\"", first.properties.etag.to_string());
+        assert_eq!(Some(LeaseStatus::Unlocked), first.properties.lease_status);
+        assert_eq!(Some(LeaseState::Available), first.properties.lease_state);
+        assert_eq!(None, first.properties.public_access);
+        assert_eq!(
+            Some("contoso"),
+            first.metadata.get("owner").map(String::as_str)
+        );
+
+        let second = &result.containers[1];
+        assert_eq!("test-2", second.name);
+        assert_eq!(
+            Some(PublicAccessType::Blob),
+            second.properties.public_access
+        );
+        assert!(second.metadata.is_empty());
+    }
+
+    #[test]
+    pub fn deserialize_empty_list_containers() {
+        let xml = br#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://myaccount.blob.core.windows.net/">
+  <Containers />
+  <NextMarker />
+</EnumerationResults>"#;
+
+        let result: ListContainersResult = read_xml(xml).unwrap();
+        assert!(result.containers.is_empty());
+        assert!(result.next_marker.unwrap_or_default().is_empty());
+    }
+
+    #[test]
+    pub fn container_properties_from_headers() {
+        let mut headers = Headers::new();
+        headers.insert(ETAG, "\"0x8DCE3072B36A1B2\"");
+        headers.insert(LAST_MODIFIED, "Wed, 02 Oct 2024 17:32:15 GMT");
+        headers.insert(LEASE_STATE, "leased");
+        headers.insert("x-ms-meta-owner", "contoso");
+
+        let properties = ContainerProperties::from_headers(&headers).unwrap();
+        assert_eq!("\"0x8DCE3072B36A1B2\"", properties.etag.to_string());
+        assert_eq!(Some(LeaseState::Leased), properties.lease_state);
+        assert_eq!(None, properties.lease_status);
+        assert_eq!(
+            Some("contoso"),
+            properties.metadata.get("owner").map(String::as_str)
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Model types sent to and received from the Azure Blob Storage API.
+
+use azure_core::headers::Headers;
+use std::collections::HashMap;
+
+mod blob;
+mod container;
+
+pub use blob::*;
+pub use container::*;
+
+/// Prefix used by the service for user-defined metadata headers.
+const META_PREFIX: &str = "x-ms-meta-";
+
+/// Collects the user-defined metadata (`x-ms-meta-*`) headers from a response.
+fn metadata_from_headers(headers: &Headers) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            name.as_str()
+                .strip_prefix(META_PREFIX)
+                .map(|key| (key.to_string(), value.as_str().to_string()))
+        })
+        .collect()
+}
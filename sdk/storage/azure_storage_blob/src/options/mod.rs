@@ -0,0 +1,186 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{
+    request_options::{IfMatchCondition, LeaseId, MaxResults, Metadata, Range, Timeout},
+    ClientMethodOptions, ClientOptions,
+};
+
+use crate::models::PublicAccessType;
+
+mod transfer;
+
+pub use transfer::*;
+
+/// Options used when creating a [`BlobServiceClient`](crate::BlobServiceClient),
+/// [`BlobContainerClient`](crate::clients::BlobContainerClient) or [`BlobClient`](crate::clients::BlobClient).
+#[derive(Clone, Debug, Default)]
+pub struct BlobClientOptions {
+    pub client_options: ClientOptions,
+}
+
+/// Options to be passed to [`BlobClient::commit_block_list()`](crate::clients::BlobClient::commit_block_list()).
+#[derive(Clone, Debug, Default)]
+pub struct CommitBlockListOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    /// The content type stored with the blob and returned when it is downloaded.
+    pub content_type: Option<String>,
+    pub metadata: Option<Metadata>,
+    pub lease_id: Option<LeaseId>,
+    pub if_match: Option<IfMatchCondition>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobContainerClient::create()`](crate::clients::BlobContainerClient::create()).
+#[derive(Clone, Debug, Default)]
+pub struct CreateContainerOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    pub metadata: Option<Metadata>,
+    pub public_access: Option<PublicAccessType>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobClient::delete()`](crate::clients::BlobClient::delete()).
+#[derive(Clone, Debug, Default)]
+pub struct DeleteBlobOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    pub lease_id: Option<LeaseId>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobContainerClient::delete()`](crate::clients::BlobContainerClient::delete()).
+#[derive(Clone, Debug, Default)]
+pub struct DeleteContainerOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    pub lease_id: Option<LeaseId>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobClient::download()`](crate::clients::BlobClient::download()).
+#[derive(Clone, Debug, Default)]
+pub struct DownloadBlobOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    /// Download only the bytes in this range of the blob.
+    pub range: Option<Range>,
+    pub lease_id: Option<LeaseId>,
+    pub if_match: Option<IfMatchCondition>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobClient::download_to()`](crate::clients::BlobClient::download_to()).
+#[derive(Clone, Debug, Default)]
+pub struct DownloadToOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    /// The size of each range that is requested, in bytes.
+    ///
+    /// The default is 4 MiB.
+    pub chunk_size: Option<u64>,
+    /// The maximum number of ranges that are downloaded at the same time.
+    ///
+    /// The default is 4. Up to `chunk_size * parallelism` bytes are held in memory.
+    pub parallelism: Option<usize>,
+    /// Start downloading at this offset of the blob instead of at the beginning.
+    ///
+    /// Use this to resume an earlier download, with `if_match` set to the ETag that download returned,
+    /// so the blob cannot have changed in between.
+    pub offset: Option<u64>,
+    /// The number of times a range is resumed after the connection fails while reading it.
+    ///
+    /// The default is 5.
+    pub max_resume_attempts: Option<u32>,
+    /// Called after each range is written.
+    pub progress: Option<ProgressCallback>,
+    pub lease_id: Option<LeaseId>,
+    /// Fail if the blob's ETag differs from this one.
+    ///
+    /// Only [`IfMatchCondition::Match`] is supported, because every range is already requested
+    /// with `If-Match` set to the ETag of the blob when the download started.
+    pub if_match: Option<IfMatchCondition>,
+    /// The timeout applied to each request.
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobClient::get_properties()`](crate::clients::BlobClient::get_properties()).
+#[derive(Clone, Debug, Default)]
+pub struct GetBlobPropertiesOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    pub lease_id: Option<LeaseId>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobContainerClient::get_properties()`](crate::clients::BlobContainerClient::get_properties()).
+#[derive(Clone, Debug, Default)]
+pub struct GetContainerPropertiesOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    pub lease_id: Option<LeaseId>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobContainerClient::list_blobs()`](crate::clients::BlobContainerClient::list_blobs()).
+#[derive(Clone, Debug, Default)]
+pub struct ListBlobsOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    /// Only return blobs whose names begin with this prefix.
+    pub prefix: Option<String>,
+    /// The maximum number of blobs to return in each page.
+    pub max_results: Option<MaxResults>,
+    /// Include user-defined metadata with each blob.
+    pub include_metadata: bool,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobServiceClient::list_containers()`](crate::BlobServiceClient::list_containers()).
+#[derive(Clone, Debug, Default)]
+pub struct ListContainersOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    /// Only return containers whose names begin with this prefix.
+    pub prefix: Option<String>,
+    /// The maximum number of containers to return in each page.
+    pub max_results: Option<MaxResults>,
+    /// Include user-defined metadata with each container.
+    pub include_metadata: bool,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobClient::stage_block()`](crate::clients::BlobClient::stage_block()).
+#[derive(Clone, Debug, Default)]
+pub struct StageBlockOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    pub lease_id: Option<LeaseId>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobClient::upload()`](crate::clients::BlobClient::upload()).
+#[derive(Clone, Debug, Default)]
+pub struct UploadBlobOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    /// The content type stored with the blob and returned when it is downloaded.
+    pub content_type: Option<String>,
+    pub metadata: Option<Metadata>,
+    pub lease_id: Option<LeaseId>,
+    pub if_match: Option<IfMatchCondition>,
+    pub timeout: Option<Timeout>,
+}
+
+/// Options to be passed to [`BlobClient::upload_blocks()`](crate::clients::BlobClient::upload_blocks()).
+#[derive(Clone, Debug, Default)]
+pub struct UploadBlocksOptions<'a> {
+    pub method_options: ClientMethodOptions<'a>,
+    /// The size of each block, in bytes.
+    ///
+    /// The default is 8 MiB. The service accepts blocks of up to 4000 MiB and at most 50,000 blocks per blob.
+    pub block_size: Option<u64>,
+    /// The maximum number of blocks that are buffered and uploaded at the same time.
+    ///
+    /// The default is 4. Up to `block_size * parallelism` bytes are held in memory.
+    pub parallelism: Option<usize>,
+    /// Called after each block is uploaded.
+    pub progress: Option<ProgressCallback>,
+    /// The content type stored with the blob and returned when it is downloaded.
+    pub content_type: Option<String>,
+    pub metadata: Option<Metadata>,
+    pub lease_id: Option<LeaseId>,
+    pub if_match: Option<IfMatchCondition>,
+    /// The timeout applied to each request.
+    pub timeout: Option<Timeout>,
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::{fmt, sync::Arc};
+
+/// The progress of a multi-request transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferProgress {
+    /// The number of bytes transferred so far.
+    pub bytes_transferred: u64,
+    /// The total number of bytes to transfer.
+    pub total_bytes: u64,
+}
+
+/// A callback that is invoked as a multi-request transfer makes progress.
+///
+/// Requests run concurrently, so the callback may be invoked from several tasks and
+/// reports are not guaranteed to arrive in increasing order.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(TransferProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    pub fn new(callback: impl Fn(TransferProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub(crate) fn report(&self, progress: TransferProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
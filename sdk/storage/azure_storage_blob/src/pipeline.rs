@@ -0,0 +1,298 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{
+    credentials::{Secret, TokenCredential},
+    error::{Error, ErrorKind},
+    headers::VERSION,
+    request_options::NextMarker,
+    xml, AppendToUrlQuery, BearerTokenCredentialPolicy, ClientOptions, Context, Pager, Policy,
+    Request, Response, Url,
+};
+use azure_storage_common::{
+    credentials::SharedKeyCredential,
+    policies::{SasAuthorizationPolicy, SharedKeyAuthorizationPolicy},
+};
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use std::sync::Arc;
+use typespec_client_core::http::PagerResult;
+
+/// The version of the Blob service REST API used by this crate.
+pub const API_VERSION: &str = "2024-08-04";
+
+/// The OAuth scope requested when authenticating with Entra ID.
+const STORAGE_SCOPE: &str = "https://storage.azure.com/.default";
+
+/// Newtype that wraps an Azure Core pipeline to provide a Blob-specific pipeline which configures our authorization policy and sets the service version on every request.
+#[derive(Debug, Clone)]
+pub struct BlobPipeline {
+    pub endpoint: Url,
+    pipeline: azure_core::Pipeline,
+}
+
+impl BlobPipeline {
+    pub fn new(endpoint: Url, auth_policy: Arc<dyn Policy>, client_options: ClientOptions) -> Self {
+        BlobPipeline {
+            endpoint,
+            pipeline: azure_core::Pipeline::new_with_namespace(
+                Some("Microsoft.Storage"),
+                option_env!("CARGO_PKG_NAME"),
+                option_env!("CARGO_PKG_VERSION"),
+                client_options,
+                Vec::new(),
+                vec![auth_policy],
+            ),
+        }
+    }
+
+    /// Creates a pipeline that authenticates using an Entra ID token.
+    pub fn from_token_credential(
+        endpoint: Url,
+        credential: Arc<dyn TokenCredential>,
+        client_options: ClientOptions,
+    ) -> Self {
+        Self::new(
+            endpoint,
+            Arc::new(BearerTokenCredentialPolicy::new(
+                credential,
+                [STORAGE_SCOPE],
+            )),
+            client_options,
+        )
+    }
+
+    /// Creates a pipeline that signs requests with a storage account key.
+    pub fn from_shared_key(
+        endpoint: Url,
+        credential: SharedKeyCredential,
+        client_options: ClientOptions,
+    ) -> Self {
+        Self::new(
+            endpoint,
+            Arc::new(SharedKeyAuthorizationPolicy::new(credential)),
+            client_options,
+        )
+    }
+
+    /// Creates a pipeline that appends a Shared Access Signature to every request.
+    pub fn from_sas(endpoint: Url, token: Secret, client_options: ClientOptions) -> Self {
+        Self::new(
+            endpoint,
+            Arc::new(SasAuthorizationPolicy::new(token)),
+            client_options,
+        )
+    }
+
+    /// Creates a [`Url`] by appending the provided path segments to the account endpoint.
+    ///
+    /// Each segment is percent-encoded. A `/` inside a segment is kept as a path separator,
+    /// so blob names with virtual directories map to the expected URL.
+    pub fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.endpoint.clone();
+        {
+            let mut path = url
+                .path_segments_mut()
+                .expect("storage endpoints are always base URLs");
+            path.pop_if_empty();
+            for segment in segments {
+                path.extend(segment.split('/'));
+            }
+        }
+        url
+    }
+
+    pub async fn send<T>(
+        &self,
+        ctx: Context<'_>,
+        request: &mut Request,
+    ) -> azure_core::Result<Response<T>> {
+        request.insert_header(VERSION, API_VERSION);
+        self.pipeline.send(&ctx, request).await
+    }
+
+    /// Sends a "List" request, following the `NextMarker` returned in each XML page.
+    pub fn send_list_request<T: Send + 'static>(
+        &self,
+        ctx: Context<'_>,
+        base_request: Request,
+    ) -> Pager<T> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct ListMarker {
+            #[serde(default)]
+            next_marker: Option<String>,
+        }
+
+        let pipeline = self.clone();
+        let ctx = ctx.into_owned();
+        Pager::from_callback(move |marker: Option<NextMarker>| {
+            let pipeline = pipeline.clone();
+            let mut req = base_request.clone();
+            let ctx = ctx.clone();
+            async move {
+                if let Some(marker) = marker {
+                    marker.append_to_url_query(req.url_mut());
+                }
+
+                let resp: Response<T> = pipeline.send(ctx, &mut req).await?;
+
+                // The continuation is in the body, so we have to read it before handing the page to the caller.
+                let raw_status = resp.raw_status();
+                let (status, headers, body) = resp.deconstruct();
+                let body = body.collect().await?;
+                let marker: ListMarker = xml::read_xml(&body)?;
+                let response =
+                    Response::from_bytes(status, headers, body).with_raw_status(raw_status);
+
+                Ok(
+                    match NextMarker::from_possibly_empty_string(marker.next_marker) {
+                        Some(continuation) => PagerResult::Continue {
+                            response,
+                            continuation,
+                        },
+                        None => PagerResult::Complete { response },
+                    },
+                )
+            }
+        })
+    }
+}
+
+/// A resource URL that carries a Shared Access Signature, split into its parts.
+#[derive(Debug)]
+pub(crate) struct SasUrl {
+    /// The URL of the Blob service, without the resource path or the SAS.
+    pub endpoint: Url,
+
+    /// The percent-decoded path segments after the endpoint: the container name and then the blob name, if any.
+    pub path: Vec<String>,
+
+    /// The SAS, without a leading `?`.
+    pub token: Secret,
+}
+
+impl SasUrl {
+    /// Parses a SAS URL such as `https://myaccount.blob.core.windows.net/container/blob?sv=...&sig=...`.
+    ///
+    /// When the host is an IP address or `localhost`, as with Azurite, the first path segment is the
+    /// account name and is kept as part of the endpoint.
+    pub fn parse(url: &str) -> azure_core::Result<Self> {
+        let mut endpoint: Url = url.parse()?;
+        let token = match endpoint.query() {
+            Some(query) if !query.is_empty() => Secret::new(query.to_string()),
+            _ => {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "the URL does not contain a Shared Access Signature",
+                ))
+            }
+        };
+        endpoint.set_query(None);
+
+        let mut path = endpoint
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| Ok(percent_decode_str(segment).decode_utf8()?.into_owned()))
+            .collect::<azure_core::Result<Vec<String>>>()?;
+
+        let is_path_style = matches!(
+            endpoint.host(),
+            Some(url::Host::Ipv4(_) | url::Host::Ipv6(_))
+        ) || endpoint.host_str() == Some("localhost");
+        let account = if is_path_style && !path.is_empty() {
+            Some(path.remove(0))
+        } else {
+            None
+        };
+        endpoint
+            .path_segments_mut()
+            .expect("storage endpoints are always base URLs")
+            .clear()
+            .extend(account);
+
+        Ok(Self {
+            endpoint,
+            path,
+            token,
+        })
+    }
+
+    /// Returns the container name, failing if the URL does not contain one.
+    pub fn container_name(&self) -> azure_core::Result<&str> {
+        self.path.first().map(String::as_str).ok_or_else(|| {
+            Error::message(
+                ErrorKind::DataConversion,
+                "the URL does not contain a container name",
+            )
+        })
+    }
+
+    /// Returns the blob name, failing if the URL does not contain one.
+    pub fn blob_name(&self) -> azure_core::Result<String> {
+        match self.path.get(1..) {
+            Some(segments) if !segments.is_empty() => Ok(segments.join("/")),
+            _ => Err(Error::message(
+                ErrorKind::DataConversion,
+                "the URL does not contain a blob name",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_sas_url() {
+        let sas_url = SasUrl::parse(
+            "https://myaccount.blob.core.windows.net/my-container/dir/my%20blob.txt?sv=2024-08-04&sig=abc%3D",
+        )
+        .unwrap();
+
+        assert_eq!(
+            sas_url.endpoint.as_str(),
+            "https://myaccount.blob.core.windows.net/"
+        );
+        assert_eq!(sas_url.container_name().unwrap(), "my-container");
+        assert_eq!(sas_url.blob_name().unwrap(), "dir/my blob.txt");
+        assert_eq!(sas_url.token.secret(), "sv=2024-08-04&sig=abc%3D");
+    }
+
+    #[test]
+    pub fn parse_azurite_sas_url() {
+        let sas_url = SasUrl::parse(
+            "http://127.0.0.1:10000/devstoreaccount1/my-container?sv=2024-08-04&sig=abc",
+        )
+        .unwrap();
+
+        assert_eq!(
+            sas_url.endpoint.as_str(),
+            "http://127.0.0.1:10000/devstoreaccount1"
+        );
+        assert_eq!(sas_url.container_name().unwrap(), "my-container");
+        assert!(sas_url.blob_name().is_err());
+    }
+
+    #[test]
+    pub fn parse_sas_url_requires_token() {
+        let err =
+            SasUrl::parse("https://myaccount.blob.core.windows.net/my-container").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+    }
+
+    #[test]
+    pub fn url_keeps_endpoint_path() {
+        let sas_url =
+            SasUrl::parse("http://127.0.0.1:10000/devstoreaccount1/my-container?sig=abc").unwrap();
+        let pipeline = BlobPipeline::from_sas(sas_url.endpoint, sas_url.token, Default::default());
+
+        assert_eq!(
+            pipeline.url(&["my-container", "dir/my blob.txt"]).as_str(),
+            "http://127.0.0.1:10000/devstoreaccount1/my-container/dir/my%20blob.txt"
+        );
+    }
+}
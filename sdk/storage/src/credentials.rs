@@ -0,0 +1,54 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Credentials specific to Azure Storage.
+
+use azure_core::credentials::Secret;
+
+/// The name of the storage account emulated by Azurite and the legacy Storage Emulator.
+pub const DEVELOPMENT_ACCOUNT_NAME: &str = "devstoreaccount1";
+
+/// The well-known key of the storage account emulated by Azurite and the legacy Storage Emulator.
+///
+/// This key is public and only grants access to a local emulator.
+pub const DEVELOPMENT_ACCOUNT_KEY: &str =
+    "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+
+/// A storage account name and one of its access keys, used to sign requests with Shared Key authorization.
+///
+/// See [Authorize with Shared Key](https://learn.microsoft.com/rest/api/storageservices/authorize-with-shared-key).
+#[derive(Clone, Debug)]
+pub struct SharedKeyCredential {
+    account_name: String,
+    account_key: Secret,
+}
+
+impl SharedKeyCredential {
+    /// Creates a new `SharedKeyCredential`.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_name` - The name of the storage account.
+    /// * `account_key` - The base64-encoded access key of the storage account.
+    pub fn new(account_name: impl Into<String>, account_key: impl Into<Secret>) -> Self {
+        Self {
+            account_name: account_name.into(),
+            account_key: account_key.into(),
+        }
+    }
+
+    /// Creates a `SharedKeyCredential` for the development account used by Azurite.
+    pub fn development() -> Self {
+        Self::new(DEVELOPMENT_ACCOUNT_NAME, DEVELOPMENT_ACCOUNT_KEY)
+    }
+
+    /// Returns the name of the storage account.
+    pub fn account_name(&self) -> &str {
+        &self.account_name
+    }
+
+    /// Returns the access key of the storage account.
+    pub fn account_key(&self) -> &Secret {
+        &self.account_key
+    }
+}
@@ -1,2 +1,8 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
+
+//! Types shared by the Azure Storage client libraries.
+
+pub mod credentials;
+pub mod policies;
+pub mod sas;
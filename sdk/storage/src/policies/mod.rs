@@ -0,0 +1,10 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Pipeline policies specific to Azure Storage.
+
+mod sas_authorization_policy;
+mod shared_key_authorization_policy;
+
+pub use sas_authorization_policy::*;
+pub use shared_key_authorization_policy::*;
@@ -0,0 +1,131 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{credentials::Secret, Context, Policy, PolicyResult, Request};
+use std::sync::Arc;
+
+/// A [`Policy`] that authorizes requests by appending a Shared Access Signature to the query string.
+#[derive(Debug, Clone)]
+pub struct SasAuthorizationPolicy {
+    token: Secret,
+}
+
+impl SasAuthorizationPolicy {
+    /// Creates a new `SasAuthorizationPolicy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The SAS token, with or without a leading `?`.
+    pub fn new(token: Secret) -> Self {
+        let token = match token.secret().strip_prefix('?') {
+            Some(stripped) => Secret::new(stripped.to_string()),
+            None => token,
+        };
+        Self { token }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for SasAuthorizationPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        assert!(
+            !next.is_empty(),
+            "Authorization policies cannot be the last policy of a pipeline"
+        );
+
+        // Retries send the same request again, so only append the token once.
+        let url = request.url_mut();
+        if !url.query_pairs().any(|(name, _)| name == "sig") {
+            let query = match url.query() {
+                Some(query) if !query.is_empty() => format!("{}&{}", query, self.token.secret()),
+                _ => self.token.secret().to_string(),
+            };
+            url.set_query(Some(&query));
+        }
+
+        // next[0] will not panic, because we checked at the beginning of the function
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{Method, Response, StatusCode, Url};
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct UrlRecorder(Mutex<Vec<String>>);
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for UrlRecorder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.0.lock().unwrap().push(request.url().to_string());
+            Ok(Response::from_bytes(
+                StatusCode::Ok,
+                Default::default(),
+                Vec::new(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    pub async fn appends_token_once() {
+        let policy = SasAuthorizationPolicy::new(Secret::new("?sv=2024-08-04&sp=r&sig=abc%3D"));
+        let recorder = Arc::new(UrlRecorder::default());
+        let next: Vec<Arc<dyn Policy>> = vec![recorder.clone()];
+        let mut request = Request::new(
+            Url::parse("https://myaccount.blob.core.windows.net/c/b?timeout=30").unwrap(),
+            Method::Get,
+        );
+
+        for _ in 0..2 {
+            policy
+                .send(&Context::default(), &mut request, &next)
+                .await
+                .unwrap();
+        }
+
+        let urls = recorder.0.lock().unwrap();
+        assert_eq!(
+            *urls,
+            vec![
+                "https://myaccount.blob.core.windows.net/c/b?timeout=30&sv=2024-08-04&sp=r&sig=abc%3D";
+                2
+            ]
+        );
+    }
+
+    #[tokio::test]
+    pub async fn appends_token_without_query() {
+        let policy = SasAuthorizationPolicy::new(Secret::new("sv=2024-08-04&sig=abc"));
+        let recorder = Arc::new(UrlRecorder::default());
+        let next: Vec<Arc<dyn Policy>> = vec![recorder.clone()];
+        let mut request = Request::new(
+            Url::parse("https://myaccount.blob.core.windows.net/c").unwrap(),
+            Method::Get,
+        );
+
+        policy
+            .send(&Context::default(), &mut request, &next)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            recorder.0.lock().unwrap()[0],
+            "https://myaccount.blob.core.windows.net/c?sv=2024-08-04&sig=abc"
+        );
+    }
+}
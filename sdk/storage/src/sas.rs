@@ -0,0 +1,656 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Shared Access Signature (SAS) generation.
+//!
+//! A SAS grants time-limited, scoped access to storage resources without sharing the account key.
+//! Use [`BlobSasValues`] to create a service SAS for a container or a blob, and [`AccountSasValues`]
+//! to create an account SAS. Both are signed with a [`SharedKeyCredential`].
+//!
+//! See [Create a service SAS](https://learn.microsoft.com/rest/api/storageservices/create-service-sas)
+//! and [Create an account SAS](https://learn.microsoft.com/rest/api/storageservices/create-account-sas).
+
+use crate::credentials::SharedKeyCredential;
+use azure_core::{
+    credentials::Secret,
+    date::{self, OffsetDateTime},
+    hmac::hmac_sha256,
+};
+use std::{fmt, net::IpAddr};
+use time::UtcOffset;
+use typespec_client_core::create_enum;
+
+/// The signed version used when none is specified.
+///
+/// The string-to-sign formats implemented here are valid for version 2020-12-06 and later.
+pub const SAS_VERSION: &str = "2024-08-04";
+
+create_enum!(
+    #[doc = "The protocols permitted for a request made with a SAS."]
+    SasProtocol,
+    #[doc = "Only HTTPS requests are permitted."]
+    (Https, "https"),
+    #[doc = "Both HTTPS and HTTP requests are permitted."]
+    (HttpsAndHttp, "https,http")
+);
+
+/// An IP address or an inclusive range of IP addresses from which requests are accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SasIpRange {
+    pub start: IpAddr,
+    pub end: Option<IpAddr>,
+}
+
+impl SasIpRange {
+    /// Creates a range that only accepts a single IP address.
+    pub fn single(address: IpAddr) -> Self {
+        Self {
+            start: address,
+            end: None,
+        }
+    }
+
+    /// Creates a range that accepts every IP address from `start` to `end`, inclusive.
+    pub fn new(start: IpAddr, end: IpAddr) -> Self {
+        Self {
+            start,
+            end: Some(end),
+        }
+    }
+}
+
+impl fmt::Display for SasIpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "{}-{}", self.start, end),
+            None => write!(f, "{}", self.start),
+        }
+    }
+}
+
+/// Writes the letter for each enabled permission, in the order the service requires.
+macro_rules! permission_letters {
+    ($(#[$meta:meta])* $name:ident { $($(#[$field_meta:meta])* $field:ident => $letter:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct $name {
+            $(
+                $(#[$field_meta])*
+                pub $field: bool,
+            )*
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                $(
+                    if self.$field {
+                        f.write_str($letter)?;
+                    }
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+permission_letters!(
+    /// The permissions granted by a [`BlobSasValues`].
+    BlobSasPermissions {
+        /// Read the content, properties and metadata of a blob.
+        read => "r",
+        /// Add a block to an append blob.
+        add => "a",
+        /// Write a new blob.
+        create => "c",
+        /// Create or overwrite a blob.
+        write => "w",
+        /// Delete a blob.
+        delete => "d",
+        /// Delete a blob version.
+        delete_version => "x",
+        /// Permanently delete a blob snapshot or version.
+        permanent_delete => "y",
+        /// List blobs in a container.
+        list => "l",
+        /// Read or write the tags of a blob.
+        tags => "t",
+        /// Find blobs by their tags.
+        find => "f",
+        /// Move a blob or directory.
+        r#move => "m",
+        /// Get the system properties of a blob.
+        execute => "e",
+        /// Set or delete the immutability policy or legal hold of a blob.
+        set_immutability_policy => "i",
+    }
+);
+
+permission_letters!(
+    /// The permissions granted by an [`AccountSasValues`].
+    AccountSasPermissions {
+        /// Read resources.
+        read => "r",
+        /// Write resources.
+        write => "w",
+        /// Delete resources.
+        delete => "d",
+        /// Delete blob versions.
+        delete_version => "x",
+        /// Permanently delete blob snapshots and versions.
+        permanent_delete => "y",
+        /// List resources.
+        list => "l",
+        /// Add messages, table entities and append blocks.
+        add => "a",
+        /// Create resources.
+        create => "c",
+        /// Update messages and table entities.
+        update => "u",
+        /// Process messages.
+        process => "p",
+        /// Read or write blob tags.
+        tags => "t",
+        /// Find blobs by their tags.
+        filter => "f",
+        /// Set or delete immutability policies and legal holds.
+        set_immutability_policy => "i",
+    }
+);
+
+permission_letters!(
+    /// The services an [`AccountSasValues`] grants access to.
+    AccountSasServices {
+        /// The Blob service.
+        blob => "b",
+        /// The File service.
+        file => "f",
+        /// The Queue service.
+        queue => "q",
+        /// The Table service.
+        table => "t",
+    }
+);
+
+permission_letters!(
+    /// The resource types an [`AccountSasValues`] grants access to.
+    AccountSasResourceTypes {
+        /// Service-level APIs, such as listing containers.
+        service => "s",
+        /// Container-level APIs, such as creating a container or listing blobs.
+        container => "c",
+        /// Object-level APIs, such as uploading a blob.
+        object => "o",
+    }
+);
+
+/// The values of a service SAS for a container or a blob.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use azure_core::date::{self, OffsetDateTime};
+/// use azure_storage_common::{
+///     credentials::SharedKeyCredential,
+///     sas::{BlobSasPermissions, BlobSasValues},
+/// };
+///
+/// let credential = SharedKeyCredential::new("myaccount", "bXkga2V5");
+/// let permissions = BlobSasPermissions {
+///     create: true,
+///     write: true,
+///     ..Default::default()
+/// };
+/// let expiry = OffsetDateTime::now_utc() + date::duration_from_minutes(15);
+/// let token = BlobSasValues::new("uploads", Some("photo.jpg"), permissions, expiry)
+///     .sign(&credential)
+///     .unwrap();
+/// let url = format!(
+///     "https://myaccount.blob.core.windows.net/uploads/photo.jpg?{}",
+///     token.secret()
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct BlobSasValues {
+    /// The name of the container.
+    pub container_name: String,
+
+    /// The name of the blob, or `None` to grant access to the whole container.
+    pub blob_name: Option<String>,
+
+    /// The permissions granted by the SAS.
+    pub permissions: BlobSasPermissions,
+
+    /// When the SAS becomes valid. If `None`, it is valid immediately.
+    pub start: Option<OffsetDateTime>,
+
+    /// When the SAS expires.
+    pub expiry: OffsetDateTime,
+
+    /// The IP addresses from which requests are accepted.
+    pub ip_range: Option<SasIpRange>,
+
+    /// The protocols permitted for requests.
+    pub protocol: Option<SasProtocol>,
+
+    /// The identifier of a stored access policy on the container.
+    pub identifier: Option<String>,
+
+    /// The signed storage service version.
+    pub version: String,
+}
+
+impl BlobSasValues {
+    /// Creates the values of a service SAS.
+    ///
+    /// # Arguments
+    ///
+    /// * `container_name` - The name of the container.
+    /// * `blob_name` - The name of the blob, or `None` for a container SAS.
+    /// * `permissions` - The permissions granted by the SAS.
+    /// * `expiry` - When the SAS expires.
+    pub fn new(
+        container_name: impl Into<String>,
+        blob_name: Option<&str>,
+        permissions: BlobSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> Self {
+        Self {
+            container_name: container_name.into(),
+            blob_name: blob_name.map(Into::into),
+            permissions,
+            start: None,
+            expiry,
+            ip_range: None,
+            protocol: None,
+            identifier: None,
+            version: SAS_VERSION.to_string(),
+        }
+    }
+
+    /// Signs the values with the account key and returns the SAS token, without a leading `?`.
+    pub fn sign(&self, credential: &SharedKeyCredential) -> azure_core::Result<Secret> {
+        let signature = hmac_sha256(
+            &self.string_to_sign(credential.account_name()),
+            credential.account_key(),
+        )?;
+
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("sv", &self.version);
+        if let Some(start) = &self.start {
+            query.append_pair("st", &format_time(start));
+        }
+        query.append_pair("se", &format_time(&self.expiry));
+        query.append_pair("sr", self.signed_resource());
+        query.append_pair("sp", &self.permissions.to_string());
+        if let Some(ip_range) = &self.ip_range {
+            query.append_pair("sip", &ip_range.to_string());
+        }
+        if let Some(protocol) = self.protocol {
+            query.append_pair("spr", protocol.into());
+        }
+        if let Some(identifier) = &self.identifier {
+            query.append_pair("si", identifier);
+        }
+        query.append_pair("sig", &signature);
+        Ok(Secret::new(query.finish()))
+    }
+
+    fn signed_resource(&self) -> &'static str {
+        match self.blob_name {
+            Some(_) => "b",
+            None => "c",
+        }
+    }
+
+    fn string_to_sign(&self, account_name: &str) -> String {
+        let mut canonicalized_resource = format!("/blob/{}/{}", account_name, self.container_name);
+        if let Some(blob_name) = &self.blob_name {
+            canonicalized_resource.push('/');
+            canonicalized_resource.push_str(blob_name);
+        }
+
+        // The trailing fields are the snapshot time, encryption scope and the five
+        // response header overrides, none of which are supported yet.
+        [
+            self.permissions.to_string(),
+            self.start.as_ref().map(format_time).unwrap_or_default(),
+            format_time(&self.expiry),
+            canonicalized_resource,
+            self.identifier.clone().unwrap_or_default(),
+            self.ip_range.map(|r| r.to_string()).unwrap_or_default(),
+            self.protocol
+                .map(|p| <&str>::from(p).to_string())
+                .unwrap_or_default(),
+            self.version.clone(),
+            self.signed_resource().to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ]
+        .join("\n")
+    }
+}
+
+/// The values of an account SAS.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use azure_core::date::{self, OffsetDateTime};
+/// use azure_storage_common::{
+///     credentials::SharedKeyCredential,
+///     sas::{AccountSasPermissions, AccountSasResourceTypes, AccountSasServices, AccountSasValues},
+/// };
+///
+/// let credential = SharedKeyCredential::new("myaccount", "bXkga2V5");
+/// let token = AccountSasValues::new(
+///     AccountSasServices { blob: true, ..Default::default() },
+///     AccountSasResourceTypes { container: true, object: true, ..Default::default() },
+///     AccountSasPermissions { read: true, list: true, ..Default::default() },
+///     OffsetDateTime::now_utc() + date::duration_from_hours(1),
+/// )
+/// .sign(&credential)
+/// .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct AccountSasValues {
+    /// The services the SAS grants access to.
+    pub services: AccountSasServices,
+
+    /// The resource types the SAS grants access to.
+    pub resource_types: AccountSasResourceTypes,
+
+    /// The permissions granted by the SAS.
+    pub permissions: AccountSasPermissions,
+
+    /// When the SAS becomes valid. If `None`, it is valid immediately.
+    pub start: Option<OffsetDateTime>,
+
+    /// When the SAS expires.
+    pub expiry: OffsetDateTime,
+
+    /// The IP addresses from which requests are accepted.
+    pub ip_range: Option<SasIpRange>,
+
+    /// The protocols permitted for requests.
+    pub protocol: Option<SasProtocol>,
+
+    /// The signed storage service version.
+    pub version: String,
+}
+
+impl AccountSasValues {
+    /// Creates the values of an account SAS.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The services the SAS grants access to.
+    /// * `resource_types` - The resource types the SAS grants access to.
+    /// * `permissions` - The permissions granted by the SAS.
+    /// * `expiry` - When the SAS expires.
+    pub fn new(
+        services: AccountSasServices,
+        resource_types: AccountSasResourceTypes,
+        permissions: AccountSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> Self {
+        Self {
+            services,
+            resource_types,
+            permissions,
+            start: None,
+            expiry,
+            ip_range: None,
+            protocol: None,
+            version: SAS_VERSION.to_string(),
+        }
+    }
+
+    /// Signs the values with the account key and returns the SAS token, without a leading `?`.
+    pub fn sign(&self, credential: &SharedKeyCredential) -> azure_core::Result<Secret> {
+        let signature = hmac_sha256(
+            &self.string_to_sign(credential.account_name()),
+            credential.account_key(),
+        )?;
+
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("sv", &self.version);
+        query.append_pair("ss", &self.services.to_string());
+        query.append_pair("srt", &self.resource_types.to_string());
+        query.append_pair("sp", &self.permissions.to_string());
+        if let Some(start) = &self.start {
+            query.append_pair("st", &format_time(start));
+        }
+        query.append_pair("se", &format_time(&self.expiry));
+        if let Some(ip_range) = &self.ip_range {
+            query.append_pair("sip", &ip_range.to_string());
+        }
+        if let Some(protocol) = self.protocol {
+            query.append_pair("spr", protocol.into());
+        }
+        query.append_pair("sig", &signature);
+        Ok(Secret::new(query.finish()))
+    }
+
+    fn string_to_sign(&self, account_name: &str) -> String {
+        // The last field is the encryption scope, which is not supported yet. The string ends with a newline.
+        format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n\n",
+            account_name,
+            self.permissions,
+            self.services,
+            self.resource_types,
+            self.start.as_ref().map(format_time).unwrap_or_default(),
+            format_time(&self.expiry),
+            self.ip_range.map(|r| r.to_string()).unwrap_or_default(),
+            self.protocol.map(<&str>::from).unwrap_or_default(),
+            self.version,
+        )
+    }
+}
+
+/// Formats a time as the service expects: UTC, ISO 8601, without fractional seconds.
+fn format_time(time: &OffsetDateTime) -> String {
+    let time = time
+        .to_offset(UtcOffset::UTC)
+        .replace_nanosecond(0)
+        .expect("zero is a valid nanosecond");
+    date::to_rfc3339(&time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn credential() -> SharedKeyCredential {
+        SharedKeyCredential::development()
+    }
+
+    fn time(s: &str) -> OffsetDateTime {
+        date::parse_rfc3339(s).unwrap()
+    }
+
+    #[test]
+    pub fn permissions_are_written_in_order() {
+        let permissions = BlobSasPermissions {
+            write: true,
+            read: true,
+            list: true,
+            create: true,
+            ..Default::default()
+        };
+        assert_eq!(permissions.to_string(), "rcwl");
+
+        let permissions = AccountSasPermissions {
+            process: true,
+            read: true,
+            update: true,
+            ..Default::default()
+        };
+        assert_eq!(permissions.to_string(), "rup");
+    }
+
+    #[test]
+    pub fn format_time_drops_fractional_seconds_and_offset() {
+        assert_eq!(
+            format_time(&time("2024-03-04T05:06:07.891+02:00")),
+            "2024-03-04T03:06:07Z"
+        );
+    }
+
+    #[test]
+    pub fn blob_sas_string_to_sign() {
+        let mut values = BlobSasValues::new(
+            "my-container",
+            Some("dir/my blob.txt"),
+            BlobSasPermissions {
+                read: true,
+                write: true,
+                ..Default::default()
+            },
+            time("2024-01-02T00:00:00Z"),
+        );
+        values.start = Some(time("2024-01-01T00:00:00Z"));
+        values.ip_range = Some(SasIpRange::new(
+            Ipv4Addr::new(168, 1, 5, 60).into(),
+            Ipv4Addr::new(168, 1, 5, 70).into(),
+        ));
+        values.protocol = Some(SasProtocol::Https);
+
+        assert_eq!(
+            values.string_to_sign("devstoreaccount1"),
+            "rw\n\
+             2024-01-01T00:00:00Z\n\
+             2024-01-02T00:00:00Z\n\
+             /blob/devstoreaccount1/my-container/dir/my blob.txt\n\
+             \n\
+             168.1.5.60-168.1.5.70\n\
+             https\n\
+             2024-08-04\n\
+             b\n\
+             \n\n\n\n\n\n"
+        );
+    }
+
+    #[test]
+    pub fn container_sas_string_to_sign() {
+        let mut values = BlobSasValues::new(
+            "my-container",
+            None,
+            BlobSasPermissions {
+                list: true,
+                ..Default::default()
+            },
+            time("2024-01-02T00:00:00Z"),
+        );
+        values.identifier = Some("policy-1".to_string());
+
+        assert_eq!(
+            values.string_to_sign("devstoreaccount1"),
+            "l\n\
+             \n\
+             2024-01-02T00:00:00Z\n\
+             /blob/devstoreaccount1/my-container\n\
+             policy-1\n\
+             \n\
+             \n\
+             2024-08-04\n\
+             c\n\
+             \n\n\n\n\n\n"
+        );
+    }
+
+    #[test]
+    pub fn blob_sas_token() {
+        let mut values = BlobSasValues::new(
+            "my-container",
+            Some("my-blob.txt"),
+            BlobSasPermissions {
+                read: true,
+                ..Default::default()
+            },
+            time("2024-01-02T00:00:00Z"),
+        );
+        values.protocol = Some(SasProtocol::HttpsAndHttp);
+
+        let token = values.sign(&credential()).unwrap();
+
+        assert_eq!(
+            token.secret(),
+            "sv=2024-08-04&se=2024-01-02T00%3A00%3A00Z&sr=b&sp=r&spr=https%2Chttp\
+             &sig=mPnXbAjkfIqIRFfM85MNjqjcXu7yal86Xcg40T28Xgo%3D"
+        );
+    }
+
+    #[test]
+    pub fn account_sas_string_to_sign() {
+        let mut values = AccountSasValues::new(
+            AccountSasServices {
+                blob: true,
+                queue: true,
+                ..Default::default()
+            },
+            AccountSasResourceTypes {
+                service: true,
+                container: true,
+                object: true,
+            },
+            AccountSasPermissions {
+                read: true,
+                write: true,
+                list: true,
+                ..Default::default()
+            },
+            time("2024-01-02T00:00:00Z"),
+        );
+        values.start = Some(time("2024-01-01T00:00:00Z"));
+        values.ip_range = Some(SasIpRange::single(Ipv4Addr::new(10, 0, 0, 1).into()));
+        values.protocol = Some(SasProtocol::Https);
+
+        assert_eq!(
+            values.string_to_sign("devstoreaccount1"),
+            "devstoreaccount1\n\
+             rwl\n\
+             bq\n\
+             sco\n\
+             2024-01-01T00:00:00Z\n\
+             2024-01-02T00:00:00Z\n\
+             10.0.0.1\n\
+             https\n\
+             2024-08-04\n\
+             \n"
+        );
+    }
+
+    #[test]
+    pub fn account_sas_token() {
+        let values = AccountSasValues::new(
+            AccountSasServices {
+                blob: true,
+                ..Default::default()
+            },
+            AccountSasResourceTypes {
+                container: true,
+                ..Default::default()
+            },
+            AccountSasPermissions {
+                list: true,
+                ..Default::default()
+            },
+            time("2024-01-02T00:00:00Z"),
+        );
+
+        let token = values.sign(&credential()).unwrap();
+
+        assert_eq!(
+            token.secret(),
+            "sv=2024-08-04&ss=b&srt=c&sp=l&se=2024-01-02T00%3A00%3A00Z\
+             &sig=2pavPH8OIioSTI93LtZSB%2FX0NhgQ9Db1wvnzbmbz3iM%3D"
+        );
+    }
+}
@@ -16,6 +16,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 ///
 /// The classification of error is intentionally fairly coarse.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// An HTTP status code that was not expected.
     #[cfg(feature = "http")]
@@ -31,6 +32,14 @@ pub enum ErrorKind {
     Credential,
     /// An error having to do with the mock framework.
     MockFramework,
+    /// A single attempt to send a request did not complete within its per-try timeout.
+    ///
+    /// The request may succeed if retried.
+    TryTimeout,
+    /// An operation did not complete before its overall deadline.
+    Timeout,
+    /// An operation was cancelled before it completed.
+    Cancelled,
     /// A catch all for other kinds of errors.
     Other,
 }
@@ -63,6 +72,9 @@ impl Display for ErrorKind {
             ErrorKind::DataConversion => f.write_str("DataConversion"),
             ErrorKind::Credential => f.write_str("Credential"),
             ErrorKind::MockFramework => f.write_str("MockFramework"),
+            ErrorKind::TryTimeout => f.write_str("TryTimeout"),
+            ErrorKind::Timeout => f.write_str("Timeout"),
+            ErrorKind::Cancelled => f.write_str("Cancelled"),
             ErrorKind::Other => f.write_str("Other"),
         }
     }
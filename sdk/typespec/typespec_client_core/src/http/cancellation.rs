@@ -0,0 +1,303 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::Context;
+use futures::future::{self, Either};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Poll, Waker},
+};
+use typespec::error::{Error, ErrorKind, Result};
+
+/// A token used to cooperatively cancel client method calls.
+///
+/// Add a `CancellationToken` to the [`Context`] passed to a client method, and call [`CancellationToken::cancel()`]
+/// from anywhere else to abort the call. Any delay between retries and any request waiting on the transport end promptly,
+/// and the call fails with [`ErrorKind::Cancelled`].
+///
+/// Clones share the same state, so cancelling any clone cancels them all. Because a [`Pager`](crate::http::Pager) keeps
+/// the `Context` it was created with, cancelling the token also stops a `Pager` from fetching any more pages.
+///
+/// # Examples
+///
+/// ```
+/// # use typespec_client_core::http::{CancellationToken, Context};
+/// let token = CancellationToken::new();
+/// let context = Context::new().with_value(token.clone());
+///
+/// // Later, perhaps from another task:
+/// token.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    cancelled: bool,
+    next_id: u64,
+    wakers: HashMap<u64, Waker>,
+}
+
+impl CancellationToken {
+    /// Creates a new `CancellationToken` that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels this token and every clone of it.
+    ///
+    /// Cancelling a token more than once has no further effect.
+    pub fn cancel(&self) {
+        let wakers = {
+            let mut state = self.state.lock().expect("cancellation state poisoned");
+            state.cancelled = true;
+            std::mem::take(&mut state.wakers)
+        };
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state
+            .lock()
+            .expect("cancellation state poisoned")
+            .cancelled
+    }
+
+    /// Returns a future that completes when this token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+            id: None,
+        }
+    }
+
+    /// Runs `future` to completion, or fails with [`ErrorKind::Cancelled`] as soon as this token is cancelled.
+    ///
+    /// If the token has already been cancelled, `future` is never polled.
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Result<F::Output> {
+        if self.is_cancelled() {
+            return Err(cancelled_error());
+        }
+        futures::pin_mut!(future);
+        match future::select(future, self.cancelled()).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(cancelled_error()),
+        }
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// A future that completes when a [`CancellationToken`] is cancelled.
+///
+/// Returned by [`CancellationToken::cancelled()`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Cancelled {
+    token: CancellationToken,
+    id: Option<u64>,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        let mut state = this
+            .token
+            .state
+            .lock()
+            .expect("cancellation state poisoned");
+        if state.cancelled {
+            return Poll::Ready(());
+        }
+        let id = *this.id.get_or_insert_with(|| {
+            state.next_id += 1;
+            state.next_id
+        });
+        state.wakers.insert(id, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for Cancelled {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            if let Ok(mut state) = self.token.state.lock() {
+                state.wakers.remove(&id);
+            }
+        }
+    }
+}
+
+fn cancelled_error() -> Error {
+    Error::message(ErrorKind::Cancelled, "the operation was cancelled")
+}
+
+/// Runs `future` to completion, or fails with [`ErrorKind::Cancelled`] as soon as the [`CancellationToken`] in `ctx`, if any, is cancelled.
+pub(crate) async fn run_until_cancelled<F: Future>(
+    ctx: &Context<'_>,
+    future: F,
+) -> Result<F::Output> {
+    match ctx.value::<CancellationToken>() {
+        Some(token) => token.run_until_cancelled(future).await,
+        None => Ok(future.await),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        headers::Headers,
+        policies::{Policy, PolicyResult},
+        ClientOptions, FixedRetryOptions, Method, Pipeline, Request, Response, RetryOptions,
+        StatusCode, TransportOptions,
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, Instant},
+    };
+
+    /// Responds to every request with the given status, after never responding at all if `hang` is set.
+    #[derive(Debug)]
+    struct MockTransport {
+        status: StatusCode,
+        hang: bool,
+        attempts: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for MockTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if self.hang {
+                future::pending::<()>().await;
+            }
+            Ok(Response::from_bytes(self.status, Headers::new(), ""))
+        }
+    }
+
+    /// Sends a request through a pipeline that retries after a minute, and cancels it after 20 milliseconds.
+    async fn send_and_cancel(transport: Arc<MockTransport>) -> PolicyResult {
+        let mut options = ClientOptions::new(TransportOptions::new_custom_policy(transport));
+        options.set_retry(RetryOptions::fixed(
+            FixedRetryOptions::default().delay(Duration::from_secs(60)),
+        ));
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+        let ctx = Context::new().with_value(token);
+        let mut request = Request::new("https://contoso.com".parse().unwrap(), Method::Get);
+        pipeline.send(&ctx, &mut request).await
+    }
+
+    #[tokio::test]
+    async fn cancels_request_waiting_on_transport() {
+        let transport = Arc::new(MockTransport {
+            status: StatusCode::Ok,
+            hang: true,
+            attempts: AtomicUsize::new(0),
+        });
+        let err = send_and_cancel(transport.clone()).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Cancelled);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cancels_delay_between_retries() {
+        let transport = Arc::new(MockTransport {
+            status: StatusCode::ServiceUnavailable,
+            hang: false,
+            attempts: AtomicUsize::new(0),
+        });
+        let start = Instant::now();
+        let err = send_and_cancel(transport.clone()).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Cancelled);
+        assert!(start.elapsed() < Duration::from_secs(30));
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cancel_wakes_every_waiter() {
+        let token = CancellationToken::new();
+        let waiters: Vec<_> = (0..3)
+            .map(|_| tokio::spawn(token.clone().cancelled()))
+            .collect();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!token.is_cancelled());
+
+        token.clone().cancel();
+        for waiter in waiters {
+            tokio::time::timeout(Duration::from_secs(5), waiter)
+                .await
+                .expect("waiter was not woken")
+                .unwrap();
+        }
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn run_until_cancelled() {
+        let token = CancellationToken::new();
+        assert_eq!(token.run_until_cancelled(async { 1 }).await.unwrap(), 1);
+
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        let err = token
+            .run_until_cancelled(future::pending::<()>())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Cancelled);
+
+        // Once cancelled, the future is not polled at all.
+        let err = token
+            .run_until_cancelled(async { unreachable!() })
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Cancelled);
+    }
+
+    #[test]
+    fn dropped_waiters_are_unregistered() {
+        let token = CancellationToken::new();
+        let mut cancelled = Box::pin(token.cancelled());
+        let waker = futures::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+        assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(token.state.lock().unwrap().wakers.len(), 1);
+
+        drop(cancelled);
+        assert!(token.state.lock().unwrap().wakers.is_empty());
+    }
+}
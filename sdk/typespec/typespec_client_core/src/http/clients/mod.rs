@@ -12,6 +12,8 @@ mod reqwest;
 use self::noop::new_noop_client;
 #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
 use self::reqwest::new_reqwest_client;
+#[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+pub use self::reqwest::{new_reqwest_client_with_options, ReqwestClientOptions};
 
 use crate::http::{Request, Response};
 use async_trait::async_trait;
@@ -19,6 +21,8 @@ use std::sync::Arc;
 use typespec::error::Result;
 
 /// Create a new [`HttpClient`].
+///
+/// When using the `reqwest` backend, call `new_reqwest_client_with_options()` instead to configure connection pooling and timeouts.
 pub fn new_http_client() -> Arc<dyn HttpClient> {
     #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
     {
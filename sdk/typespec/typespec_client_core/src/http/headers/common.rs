@@ -34,5 +34,6 @@ pub const PREFER: HeaderName = HeaderName::from_static("prefer");
 pub const RANGE: HeaderName = HeaderName::from_static("range");
 pub const RETRY_AFTER: HeaderName = HeaderName::from_static("retry-after");
 pub const SERVER: HeaderName = HeaderName::from_static("server");
+pub const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
 pub const USER_AGENT: HeaderName = HeaderName::from_static("user-agent");
 pub const WWW_AUTHENTICATE: HeaderName = HeaderName::from_static("www-authenticate");
@@ -3,6 +3,7 @@
 
 //! Types and functions for building HTTP clients.
 
+mod cancellation;
 mod clients;
 mod context;
 pub mod headers;
@@ -14,6 +15,7 @@ pub mod policies;
 pub mod request;
 pub mod response;
 
+pub use cancellation::*;
 pub use clients::*;
 pub use context::*;
 pub use headers::Header;
@@ -0,0 +1,94 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::headers::{self, HeaderName};
+
+/// Header names whose values are logged by default. All other header values are redacted.
+const DEFAULT_ALLOWED_HEADER_NAMES: &[HeaderName] = &[
+    headers::ACCEPT,
+    headers::CACHE_CONTROL,
+    headers::CONTENT_LENGTH,
+    headers::CONTENT_TYPE,
+    headers::DATE,
+    headers::ETAG,
+    headers::IF_MATCH,
+    headers::IF_MODIFIED_SINCE,
+    headers::IF_NONE_MATCH,
+    headers::IF_UNMODIFIED_SINCE,
+    headers::LAST_MODIFIED,
+    headers::RETRY_AFTER,
+    headers::RETRY_AFTER_MS,
+    headers::SERVER,
+    headers::TRACEPARENT,
+    headers::USER_AGENT,
+    headers::ERROR_CODE,
+    headers::X_MS_RETRY_AFTER_MS,
+    HeaderName::from_static("connection"),
+    HeaderName::from_static("expires"),
+    HeaderName::from_static("pragma"),
+    HeaderName::from_static("request-id"),
+    HeaderName::from_static("transfer-encoding"),
+    HeaderName::from_static("x-ms-client-request-id"),
+    HeaderName::from_static("x-ms-request-id"),
+    HeaderName::from_static("x-ms-return-client-request-id"),
+];
+
+/// Query parameters whose values are logged by default. All other query parameter values are redacted.
+const DEFAULT_ALLOWED_QUERY_PARAMS: &[&str] = &["api-version"];
+
+/// Options for logging requests and responses using the [`LoggingPolicy`](crate::http::policies::LoggingPolicy).
+///
+/// Header values and query parameter values are redacted unless they are on an allow-list,
+/// so that secrets like `Authorization` headers and SAS signatures are never logged.
+/// The allow-lists start with a set of well-known names that never contain secrets, and can be extended.
+///
+/// # Example
+///
+/// Also log the values of the `x-ms-version` header and the `comp` query parameter.
+/// ```
+/// # use typespec_client_core::http::{ClientOptions, LoggingOptions};
+/// let mut options = ClientOptions::default();
+/// options.set_logging(
+///     LoggingOptions::default()
+///         .additional_allowed_header_names(vec!["x-ms-version".into()])
+///         .additional_allowed_query_params(vec!["comp".to_string()]),
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LoggingOptions {
+    /// Header names whose values are logged, in addition to the defaults.
+    pub additional_allowed_header_names: Vec<HeaderName>,
+
+    /// Query parameters whose values are logged, in addition to the defaults.
+    pub additional_allowed_query_params: Vec<String>,
+}
+
+impl LoggingOptions {
+    setters! {
+        #[doc = "Set header names whose values are logged, in addition to the defaults."]
+        additional_allowed_header_names: Vec<HeaderName> => additional_allowed_header_names,
+        #[doc = "Set query parameters whose values are logged, in addition to the defaults."]
+        additional_allowed_query_params: Vec<String> => additional_allowed_query_params,
+    }
+
+    /// Returns `true` if the value of the header `name` may be logged.
+    pub(crate) fn is_allowed_header(&self, name: &HeaderName) -> bool {
+        DEFAULT_ALLOWED_HEADER_NAMES
+            .iter()
+            .chain(&self.additional_allowed_header_names)
+            .any(|allowed| allowed == name)
+    }
+
+    /// Returns `true` if the value of the query parameter `name` may be logged.
+    pub(crate) fn is_allowed_query_param(&self, name: &str) -> bool {
+        DEFAULT_ALLOWED_QUERY_PARAMS
+            .iter()
+            .copied()
+            .chain(
+                self.additional_allowed_query_params
+                    .iter()
+                    .map(String::as_str),
+            )
+            .any(|allowed| allowed.eq_ignore_ascii_case(name))
+    }
+}
@@ -4,15 +4,20 @@
 //! Options and builders for clients, client methods, and policies.
 
 pub mod builders;
+mod logging;
+mod proxy;
 mod retry;
 mod transport;
 
+pub use logging::*;
+pub use proxy::*;
 pub use retry::*;
 pub use transport::*;
 
 use crate::http::{policies::Policy, Context};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Client options allow customization of general client policies, retry options, and more.
 #[derive(Clone, Debug, Default)]
@@ -23,6 +28,10 @@ pub struct ClientOptions {
     pub(crate) per_try_policies: Vec<Arc<dyn Policy>>,
     /// Retry options.
     pub(crate) retry: Option<RetryOptions>,
+    /// The time allowed for each attempt to receive a response.
+    pub(crate) try_timeout: Option<Duration>,
+    /// Logging options. Requests and responses are only logged if set.
+    pub(crate) logging: Option<LoggingOptions>,
     // /// Telemetry options.
     // pub(crate) telemetry: Option<TelemetryOptions>,
     /// Transport options.
@@ -65,6 +74,25 @@ impl ClientOptions {
         self.retry = Some(retry.into());
     }
 
+    /// Set the time allowed for each attempt to receive a response, after which the attempt fails with
+    /// [`ErrorKind::TryTimeout`](typespec::error::ErrorKind::TryTimeout) and may be retried.
+    ///
+    /// Attempts are not bounded by default. To bound a whole client method call, including retries,
+    /// add a [`Deadline`](crate::http::policies::Deadline) to its [`Context`].
+    pub fn set_try_timeout(&mut self, try_timeout: Duration) {
+        self.try_timeout = Some(try_timeout);
+    }
+
+    /// Log every request and response, including retries, using the given [`LoggingOptions`].
+    ///
+    /// Requests and responses are not logged by default.
+    pub fn set_logging<P>(&mut self, logging: P)
+    where
+        P: Into<LoggingOptions>,
+    {
+        self.logging = Some(logging.into());
+    }
+
     // /// Set the [`TelemetryOptions`] used by the client.
     // pub fn set_telemetry<P>(&mut self, telemetry: P)
     // where
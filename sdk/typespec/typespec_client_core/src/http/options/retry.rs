@@ -2,7 +2,8 @@
 // Licensed under the MIT License.
 
 use crate::http::policies::{
-    ExponentialRetryPolicy, FixedRetryPolicy, NoRetryPolicy, Policy, RetryPolicy,
+    ClassifiedRetryPolicy, ExponentialRetryPolicy, FixedRetryPolicy, NoRetryPolicy, Policy,
+    RetryClassifier, RetryPolicy,
 };
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -21,7 +22,7 @@ enum RetryMode {
     Fixed(FixedRetryOptions),
 
     /// A custom retry policy
-    Custom(Arc<dyn Policy>),
+    Custom(Arc<dyn RetryPolicy>),
 
     /// Do not retry attempts.
     None,
@@ -54,6 +55,9 @@ impl Default for RetryMode {
 pub struct RetryOptions {
     /// The algorithm to use for calculating retry delays.
     mode: RetryMode,
+
+    /// Decides which failures are retried, if not the retry policy's default.
+    classifier: Option<Arc<dyn RetryClassifier>>,
 }
 
 impl RetryOptions {
@@ -61,6 +65,7 @@ impl RetryOptions {
     pub fn exponential(options: ExponentialRetryOptions) -> Self {
         Self {
             mode: RetryMode::Exponential(options),
+            classifier: None,
         }
     }
 
@@ -68,6 +73,7 @@ impl RetryOptions {
     pub fn fixed(options: FixedRetryOptions) -> Self {
         Self {
             mode: RetryMode::Fixed(options),
+            classifier: None,
         }
     }
 
@@ -75,6 +81,7 @@ impl RetryOptions {
     pub fn custom<T: RetryPolicy + 'static>(policy: Arc<T>) -> Self {
         Self {
             mode: RetryMode::Custom(policy),
+            classifier: None,
         }
     }
 
@@ -82,25 +89,83 @@ impl RetryOptions {
     pub fn none() -> Self {
         Self {
             mode: RetryMode::None,
+            classifier: None,
         }
     }
 
+    /// Set the [`RetryClassifier`] that decides which failures are retried.
+    ///
+    /// By default, the [`DefaultRetryClassifier`](crate::http::policies::DefaultRetryClassifier) is used,
+    /// unless a custom [`RetryPolicy`] decides otherwise. This has no effect if retries are disabled.
+    pub fn set_classifier<C: RetryClassifier + 'static>(&mut self, classifier: Arc<C>) {
+        self.classifier = Some(classifier);
+    }
+
+    /// Returns `true` if retries use a custom [`RetryPolicy`], which decides which failures are retried unless a classifier is set.
+    pub fn is_custom(&self) -> bool {
+        matches!(self.mode, RetryMode::Custom(_))
+    }
+
+    /// Gets the [`RetryClassifier`] that decides which failures are retried, if one was set.
+    pub fn classifier(&self) -> Option<&Arc<dyn RetryClassifier>> {
+        self.classifier.as_ref()
+    }
+
     pub(crate) fn to_policy(&self) -> Arc<dyn Policy> {
-        match &self.mode {
+        let policy: Arc<dyn RetryPolicy> = match &self.mode {
             RetryMode::Exponential(options) => Arc::new(ExponentialRetryPolicy::new(
                 options.initial_delay,
                 options.max_retries,
                 options.max_total_elapsed,
                 options.max_delay,
+                options.jitter,
             )),
             RetryMode::Fixed(options) => Arc::new(FixedRetryPolicy::new(
                 options.delay,
                 options.max_retries,
                 options.max_total_elapsed,
+                options.jitter,
             )),
             RetryMode::Custom(c) => c.clone(),
-            RetryMode::None => Arc::new(NoRetryPolicy::default()),
-        }
+            RetryMode::None => return Arc::new(NoRetryPolicy::default()),
+        };
+        Arc::new(ClassifiedRetryPolicy::new(policy, self.classifier.clone()))
+    }
+}
+
+/// Randomness added to the delay between retry attempts, so that many clients that failed at the same time do not all retry at the same time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Jitter {
+    /// Add a random delay of up to 256 milliseconds to the computed delay.
+    ///
+    /// This is the default.
+    #[default]
+    Additive,
+
+    /// Wait a random delay between zero and the computed delay.
+    ///
+    /// This spreads retries out the most, at the cost of sometimes retrying almost immediately.
+    Full,
+
+    /// Wait half the computed delay plus a random delay of up to the other half.
+    Equal,
+
+    /// Wait exactly the computed delay.
+    None,
+}
+
+impl Jitter {
+    /// Applies this jitter to the computed `delay`.
+    pub(crate) fn apply(self, delay: Duration) -> Duration {
+        let delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+        let random_up_to = |max: u64| rand::random::<u64>() % max.saturating_add(1);
+        let delay_ms = match self {
+            Jitter::Additive => delay_ms.saturating_add(u64::from(rand::random::<u8>())),
+            Jitter::Full => random_up_to(delay_ms),
+            Jitter::Equal => delay_ms / 2 + random_up_to(delay_ms - delay_ms / 2),
+            Jitter::None => delay_ms,
+        };
+        Duration::from_millis(delay_ms)
     }
 }
 
@@ -139,6 +204,11 @@ pub struct ExponentialRetryOptions {
     ///
     /// The default is 30 seconds. For SRE reasons, this is only respected when above 1 second.
     pub max_delay: Duration,
+
+    /// The randomness added to each delay.
+    ///
+    /// The default is [`Jitter::Additive`].
+    pub jitter: Jitter,
 }
 
 impl ExponentialRetryOptions {
@@ -147,6 +217,7 @@ impl ExponentialRetryOptions {
         max_retries: u32 => max_retries,
         max_total_elapsed: Duration => max_total_elapsed,
         max_delay: Duration => max_delay,
+        jitter: Jitter => jitter,
     }
 }
 
@@ -157,6 +228,7 @@ impl Default for ExponentialRetryOptions {
             max_retries: 8,
             max_total_elapsed: Duration::from_secs(60),
             max_delay: Duration::from_secs(30),
+            jitter: Jitter::default(),
         }
     }
 }
@@ -189,6 +261,11 @@ pub struct FixedRetryOptions {
     ///
     /// The default is 1 minute.
     pub max_total_elapsed: Duration,
+
+    /// The randomness added to each delay.
+    ///
+    /// The default is [`Jitter::Additive`].
+    pub jitter: Jitter,
 }
 
 impl FixedRetryOptions {
@@ -199,6 +276,8 @@ impl FixedRetryOptions {
         max_retries: u32 => max_retries,
         #[doc = "Set the maximum permissible elapsed time since starting to retry."]
         max_total_elapsed: Duration => max_total_elapsed,
+        #[doc = "Set the randomness added to each delay."]
+        jitter: Jitter => jitter,
     }
 }
 
@@ -208,6 +287,7 @@ impl Default for FixedRetryOptions {
             delay: Duration::from_millis(200),
             max_retries: 8,
             max_total_elapsed: Duration::from_secs(60),
+            jitter: Jitter::default(),
         }
     }
 }
@@ -1,6 +1,8 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+#[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+use crate::http::ProxyOptions;
 use crate::http::{clients, policies::Policy, Context, HttpClient, Request, Response};
 use std::sync::Arc;
 use typespec::error::Result;
@@ -27,6 +29,17 @@ impl TransportOptions {
         Self { inner }
     }
 
+    /// Creates a new `TransportOptions` that sends requests through a proxy, using the default `reqwest` client options otherwise.
+    ///
+    /// Call `new_reqwest_client_with_options()` instead to also configure connection pooling and timeouts.
+    #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+    pub fn new_with_proxy(proxy: ProxyOptions) -> Result<Self> {
+        let options = clients::ReqwestClientOptions::default().proxy(proxy);
+        Ok(Self::new(clients::new_reqwest_client_with_options(
+            options,
+        )?))
+    }
+
     /// Creates a new `TransportOptions` using the custom policy.
     ///
     /// This policy is expected to be the last policy in the pipeline.
@@ -3,10 +3,14 @@
 
 use std::{future::Future, pin::Pin};
 
-use futures::{stream::unfold, Stream};
-use typespec::Error;
+use futures::{
+    future::{self, Either},
+    stream::unfold,
+    Stream, StreamExt,
+};
+use typespec::{error::ErrorKind, Error};
 
-use crate::http::{headers::HeaderName, Response};
+use crate::http::{headers::HeaderName, CancellationToken, Response};
 
 /// The result of fetching a single page from a [`Pager`], whether the `Pager` should continue or is complete.
 pub enum PagerResult<T, C> {
@@ -120,6 +124,45 @@ impl<T> Pager<T> {
             stream: Box::pin(stream),
         }
     }
+
+    /// Stops this [`Pager`] as soon as `token` is cancelled.
+    ///
+    /// If the token is cancelled while a page is being fetched, or before the next page is requested,
+    /// the stream yields an error of kind [`ErrorKind::Cancelled`] and then ends.
+    ///
+    /// Pages fetched through a [`Pipeline`](crate::http::Pipeline) already observe a [`CancellationToken`] in the
+    /// [`Context`](crate::http::Context) passed to each request; use this when the callback also does other work
+    /// that should be abandoned, or to cancel a `Pager` independently of the requests it sends.
+    pub fn with_cancellation(self, token: CancellationToken) -> Self
+    where
+        T: 'static,
+    {
+        let stream = unfold(Some((self.stream, token)), |state| async move {
+            let (mut stream, token) = state?;
+            let next = if token.is_cancelled() {
+                None
+            } else {
+                match future::select(stream.next(), token.cancelled()).await {
+                    Either::Left((next, _)) => Some(next),
+                    Either::Right(_) => None,
+                }
+            };
+            match next {
+                Some(Some(item)) => Some((item, Some((stream, token)))),
+                Some(None) => None,
+                None => Some((
+                    Err(Error::message(
+                        ErrorKind::Cancelled,
+                        "the pager was cancelled",
+                    )),
+                    None,
+                )),
+            }
+        });
+        Self {
+            stream: Box::pin(stream),
+        }
+    }
 }
 
 impl<T> futures::Stream for Pager<T> {
@@ -155,9 +198,12 @@ mod tests {
     use typespec_macros::Model;
 
     use crate::http::{
-        headers::{HeaderName, HeaderValue},
-        Pager, PagerResult, Response, StatusCode,
+        headers::{HeaderName, HeaderValue, Headers},
+        CancellationToken, Pager, PagerResult, Response, StatusCode,
     };
+    use futures::future;
+    use std::time::Duration;
+    use typespec::error::ErrorKind;
 
     #[tokio::test]
     pub async fn standard_pagination() {
@@ -284,4 +330,31 @@ mod tests {
         assert_eq!(&typespec::error::ErrorKind::Other, err.kind());
         assert_eq!("yon request didst fail", format!("{}", err));
     }
+
+    #[tokio::test]
+    pub async fn cancellation_stops_pagination() {
+        let token = CancellationToken::new();
+        let mut pager: Pager<()> = Pager::from_callback(|continuation| async move {
+            match continuation {
+                None => Ok(PagerResult::Continue {
+                    response: Response::from_bytes(StatusCode::Ok, Headers::new(), ""),
+                    continuation: (),
+                }),
+                // The second page never arrives.
+                Some(()) => future::pending().await,
+            }
+        })
+        .with_cancellation(token.clone());
+
+        assert!(pager.next().await.unwrap().is_ok());
+
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        let err = pager.next().await.unwrap().unwrap_err();
+        assert_eq!(&ErrorKind::Cancelled, err.kind());
+        assert!(pager.next().await.is_none());
+    }
 }
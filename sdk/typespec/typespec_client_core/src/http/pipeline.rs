@@ -2,8 +2,11 @@
 // Licensed under the MIT License.
 
 use crate::http::{
-    policies::{CustomHeadersPolicy, Policy, TransportPolicy},
-    ClientOptions, Context, Request, Response, RetryOptions,
+    policies::{
+        CustomHeadersPolicy, LoggingPolicy, Policy, TimeoutPolicy, TransportPolicy,
+        TryTimeoutPolicy,
+    },
+    ClientOptions, Context, Request, Response,
 };
 use std::sync::Arc;
 
@@ -15,15 +18,19 @@ use std::sync::Arc;
 ///    immediately.
 /// 2. User-specified per-call policies are executed.
 /// 3. Telemetry policy.
-/// 4. Retry policy. It allows to re-execute the following policies.
-/// 5. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are re-executed
+/// 4. Timeout policy, which enforces the [`Deadline`](crate::http::policies::Deadline) in the [`Context`], if any.
+/// 5. Retry policy. It allows to re-execute the following policies.
+/// 6. Try timeout policy, if enabled with [`ClientOptions::set_try_timeout()`].
+/// 7. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are re-executed
 ///    in case of retries.
-/// 6. User-specified per-retry policies are executed.
-/// 7. Authorization policy. Authorization can depend on the HTTP headers and/or the request body so it
+/// 8. User-specified per-retry policies are executed.
+/// 9. Authorization policy. Authorization can depend on the HTTP headers and/or the request body so it
 ///    must be executed right before sending the request to the transport. Also, the authorization
 ///    can depend on the current time so it must be executed at every retry.
-/// 8. Transport policy. Transport policy is always the last policy and is the policy that
-///    actually constructs the `Response` to be passed up the pipeline.
+/// 10. Logging policy, if enabled with [`ClientOptions::set_logging()`]. It is executed right before the transport so it
+///     logs each request exactly as it is sent.
+/// 11. Transport policy. Transport policy is always the last policy and is the policy that
+///     actually constructs the `Response` to be passed up the pipeline.
 ///
 /// A pipeline is immutable. In other words a policy can either succeed and call the following
 /// policy of fail and return to the calling policy. Arbitrary policy "skip" must be avoided (but
@@ -47,21 +54,29 @@ impl Pipeline {
                 + per_call_policies.len()
                 + options.per_try_policies.len()
                 + per_retry_policies.len()
-                + 2,
+                + 5,
         );
 
         pipeline.extend_from_slice(&per_call_policies);
         pipeline.extend_from_slice(&options.per_call_policies);
 
         pipeline.push(Arc::new(CustomHeadersPolicy::default()));
+        pipeline.push(Arc::new(TimeoutPolicy::default()));
 
-        // TODO: Consider whether this should be initially customizable as we onboard more services.
-        let retry_policy = RetryOptions::default().to_policy();
+        let retry_policy = options.retry.unwrap_or_default().to_policy();
         pipeline.push(retry_policy);
 
+        if let Some(try_timeout) = options.try_timeout {
+            pipeline.push(Arc::new(TryTimeoutPolicy::new(try_timeout)));
+        }
+
         pipeline.extend_from_slice(&per_retry_policies);
         pipeline.extend_from_slice(&options.per_try_policies);
 
+        if let Some(logging) = options.logging {
+            pipeline.push(Arc::new(LoggingPolicy::new(logging)));
+        }
+
         let transport: Arc<dyn Policy> =
             Arc::new(TransportPolicy::new(options.transport.unwrap_or_default()));
 
@@ -94,11 +109,20 @@ impl Pipeline {
 mod tests {
     use super::*;
     use crate::{
-        http::{headers::Headers, policies::PolicyResult, Method, StatusCode, TransportOptions},
+        http::{
+            headers::Headers,
+            policies::{FailedAttempt, PolicyResult, RetryClassifier, RetryPolicy},
+            ExponentialRetryOptions, FixedRetryOptions, Method, RetryOptions, StatusCode,
+            TransportOptions,
+        },
         stream::BytesStream,
     };
     use bytes::Bytes;
     use serde::Deserialize;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
     use typespec_macros::Model;
 
     #[tokio::test]
@@ -145,4 +169,114 @@ mod tests {
         assert_eq!(1, model.foo);
         assert_eq!("baz", &model.bar);
     }
+
+    #[derive(Debug, Default)]
+    struct CountingResponder {
+        attempts: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for CountingResponder {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let stream: BytesStream = Bytes::new().into();
+            Ok(Response::new(
+                StatusCode::ServiceUnavailable,
+                Headers::new(),
+                Box::pin(stream),
+            ))
+        }
+    }
+
+    #[derive(Debug)]
+    struct OnceRetryPolicy;
+
+    impl RetryPolicy for OnceRetryPolicy {
+        fn is_expired(&self, _duration_since_start: Duration, retry_count: u32) -> bool {
+            retry_count >= 1
+        }
+
+        fn sleep_duration(&self, _retry_count: u32) -> Duration {
+            Duration::ZERO
+        }
+    }
+
+    async fn count_attempts(retry: Option<RetryOptions>) -> usize {
+        let transport = Arc::new(CountingResponder::default());
+        let mut options =
+            ClientOptions::new(TransportOptions::new_custom_policy(transport.clone()));
+        if let Some(retry) = retry {
+            options.set_retry(retry);
+        }
+        let pipeline = Pipeline::new(options, Vec::new(), Vec::new());
+
+        let mut request = Request::new("http://localhost".parse().unwrap(), Method::Get);
+        pipeline
+            .send::<()>(&Context::default(), &mut request)
+            .await
+            .expect_err("expected service unavailable");
+
+        transport.attempts.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn retry_none_sends_once() {
+        assert_eq!(1, count_attempts(Some(RetryOptions::none())).await);
+    }
+
+    #[tokio::test]
+    async fn retry_fixed_honors_max_retries() {
+        let retry = RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .delay(Duration::from_millis(10))
+                .max_retries(3u32),
+        );
+        assert_eq!(4, count_attempts(Some(retry)).await);
+    }
+
+    #[tokio::test]
+    async fn retry_exponential_honors_max_retries() {
+        let retry = RetryOptions::exponential(
+            ExponentialRetryOptions::default()
+                .initial_delay(Duration::from_millis(1))
+                .max_retries(2u32),
+        );
+        assert_eq!(3, count_attempts(Some(retry)).await);
+    }
+
+    #[tokio::test]
+    async fn retry_custom_uses_policy() {
+        let retry = RetryOptions::custom(Arc::new(OnceRetryPolicy));
+        assert_eq!(2, count_attempts(Some(retry)).await);
+    }
+
+    #[derive(Debug)]
+    struct NeverRetryClassifier;
+
+    impl RetryClassifier for NeverRetryClassifier {
+        fn should_retry(&self, _attempt: &FailedAttempt<'_>) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_classifier_decides_what_is_retried() {
+        let mut retry = RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .delay(Duration::from_millis(10))
+                .max_retries(3u32),
+        );
+        retry.set_classifier(Arc::new(NeverRetryClassifier));
+        assert_eq!(1, count_attempts(Some(retry)).await);
+
+        let mut retry = RetryOptions::custom(Arc::new(OnceRetryPolicy));
+        retry.set_classifier(Arc::new(NeverRetryClassifier));
+        assert_eq!(1, count_attempts(Some(retry)).await);
+    }
 }
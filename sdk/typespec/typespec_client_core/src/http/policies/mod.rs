@@ -8,11 +8,15 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 mod custom_headers;
+mod logging;
 mod retry;
+mod timeout;
 mod transport;
 
 pub use custom_headers::*;
+pub use logging::*;
 pub use retry::*;
+pub use timeout::*;
 pub use transport::*;
 
 /// A specialized `Result` type for policies.
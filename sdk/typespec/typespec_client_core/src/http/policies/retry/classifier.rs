@@ -0,0 +1,262 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::RetryPolicy;
+use crate::http::{headers::Headers, Method, Request, StatusCode};
+use async_trait::async_trait;
+use std::{sync::Arc, time::Duration};
+use typespec::error::{Error, ErrorKind};
+
+/// The status codes where a retry should be attempted.
+///
+/// On all other 4xx and 5xx status codes no retry is attempted.
+const RETRY_STATUSES: &[StatusCode] = &[
+    StatusCode::RequestTimeout,
+    StatusCode::TooManyRequests,
+    StatusCode::InternalServerError,
+    StatusCode::BadGateway,
+    StatusCode::ServiceUnavailable,
+    StatusCode::GatewayTimeout,
+];
+
+/// A failed attempt to send a request, which a [`RetryClassifier`] uses to decide whether to retry the request.
+#[derive(Debug)]
+pub struct FailedAttempt<'a> {
+    request: &'a Request,
+    response: Option<(StatusCode, u16, &'a Headers)>,
+    error_kind: &'a ErrorKind,
+}
+
+impl<'a> FailedAttempt<'a> {
+    /// An attempt that received an unsuccessful response.
+    pub(crate) fn response(
+        request: &'a Request,
+        status: StatusCode,
+        raw_status: u16,
+        headers: &'a Headers,
+        error_kind: &'a ErrorKind,
+    ) -> Self {
+        Self {
+            request,
+            response: Some((status, raw_status, headers)),
+            error_kind,
+        }
+    }
+
+    /// An attempt that failed without receiving a response.
+    pub(crate) fn error(request: &'a Request, error_kind: &'a ErrorKind) -> Self {
+        Self {
+            request,
+            response: None,
+            error_kind,
+        }
+    }
+
+    /// The request that was sent.
+    pub fn request(&self) -> &Request {
+        self.request
+    }
+
+    /// The status of the response, if a response was received.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.response.map(|(status, _, _)| status)
+    }
+
+    /// The status code the service returned, if a response was received, even if [`StatusCode`] cannot represent it.
+    ///
+    /// See [`Response::raw_status()`](crate::http::Response::raw_status()).
+    pub fn raw_status(&self) -> Option<u16> {
+        self.response.map(|(_, raw_status, _)| raw_status)
+    }
+
+    /// The headers of the response, if a response was received.
+    pub fn headers(&self) -> Option<&Headers> {
+        self.response.map(|(_, _, headers)| headers)
+    }
+
+    /// The kind of error the attempt failed with.
+    ///
+    /// For unsuccessful responses, this is an [`ErrorKind::HttpResponse`] with the status and error code, if any.
+    pub fn error_kind(&self) -> &ErrorKind {
+        self.error_kind
+    }
+}
+
+/// Decides whether a request should be retried after an attempt to send it failed.
+///
+/// Set a `RetryClassifier` using [`RetryOptions::set_classifier()`](crate::http::RetryOptions::set_classifier()).
+/// The retry policy still decides how long to wait between attempts and when to give up.
+pub trait RetryClassifier: std::fmt::Debug + Send + Sync {
+    /// Returns `true` if the request should be retried after `attempt` failed.
+    fn should_retry(&self, attempt: &FailedAttempt<'_>) -> bool;
+}
+
+/// The [`RetryClassifier`] used unless another is set.
+///
+/// Retries responses with status `408`, `429`, `500`, `502`, `503`, or `504`,
+/// as well as I/O errors and attempts that time out. All other failures are returned immediately.
+///
+/// # Example
+///
+/// Never retry non-idempotent requests like `POST`, which the service may have already processed.
+/// ```
+/// # use std::sync::Arc;
+/// # use typespec_client_core::http::{policies::DefaultRetryClassifier, RetryOptions};
+/// let mut retry = RetryOptions::default();
+/// retry.set_classifier(Arc::new(
+///     DefaultRetryClassifier::default().retry_non_idempotent(false),
+/// ));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DefaultRetryClassifier {
+    retry_non_idempotent: bool,
+}
+
+impl DefaultRetryClassifier {
+    setters! {
+        #[doc = "Set whether to retry requests with a non-idempotent method: `POST` or `PATCH`. The default is `true`."]
+        retry_non_idempotent: bool => retry_non_idempotent,
+    }
+}
+
+impl Default for DefaultRetryClassifier {
+    fn default() -> Self {
+        Self {
+            retry_non_idempotent: true,
+        }
+    }
+}
+
+impl RetryClassifier for DefaultRetryClassifier {
+    fn should_retry(&self, attempt: &FailedAttempt<'_>) -> bool {
+        if !self.retry_non_idempotent
+            && matches!(attempt.request().method(), Method::Post | Method::Patch)
+        {
+            return false;
+        }
+        match attempt.status() {
+            Some(status) => RETRY_STATUSES.contains(&status),
+            None => matches!(attempt.error_kind(), ErrorKind::Io | ErrorKind::TryTimeout),
+        }
+    }
+}
+
+/// A [`RetryPolicy`] that uses a [`RetryClassifier`], if set, to decide whether to retry, and another `RetryPolicy` for everything else.
+#[derive(Debug)]
+pub(crate) struct ClassifiedRetryPolicy {
+    policy: Arc<dyn RetryPolicy>,
+    classifier: Option<Arc<dyn RetryClassifier>>,
+}
+
+impl ClassifiedRetryPolicy {
+    pub(crate) fn new(
+        policy: Arc<dyn RetryPolicy>,
+        classifier: Option<Arc<dyn RetryClassifier>>,
+    ) -> Self {
+        Self { policy, classifier }
+    }
+}
+
+#[async_trait]
+impl RetryPolicy for ClassifiedRetryPolicy {
+    fn is_expired(&self, duration_since_start: Duration, retry_count: u32) -> bool {
+        self.policy.is_expired(duration_since_start, retry_count)
+    }
+
+    fn sleep_duration(&self, retry_count: u32) -> Duration {
+        self.policy.sleep_duration(retry_count)
+    }
+
+    fn should_retry(&self, attempt: &FailedAttempt<'_>) -> bool {
+        match &self.classifier {
+            Some(classifier) => classifier.should_retry(attempt),
+            None => self.policy.should_retry(attempt),
+        }
+    }
+
+    async fn wait(&self, error: &Error, retry_count: u32, retry_after: Option<Duration>) {
+        self.policy.wait(error, retry_count, retry_after).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(classifier: &DefaultRetryClassifier, method: Method, status: StatusCode) -> bool {
+        let request = Request::new("https://contoso.com".parse().unwrap(), method);
+        let headers = Headers::new();
+        let error_kind = ErrorKind::http_response(status, None);
+        classifier.should_retry(&FailedAttempt::response(
+            &request,
+            status,
+            status.into(),
+            &headers,
+            &error_kind,
+        ))
+    }
+
+    #[test]
+    fn default_classifier_retries_transient_statuses() {
+        let classifier = DefaultRetryClassifier::default();
+        assert!(classify(
+            &classifier,
+            Method::Get,
+            StatusCode::ServiceUnavailable
+        ));
+        assert!(classify(
+            &classifier,
+            Method::Post,
+            StatusCode::TooManyRequests
+        ));
+        assert!(!classify(&classifier, Method::Get, StatusCode::NotFound));
+        assert!(!classify(
+            &classifier,
+            Method::Get,
+            StatusCode::NotImplemented
+        ));
+    }
+
+    #[test]
+    fn default_classifier_retries_io_errors_and_try_timeouts() {
+        let classifier = DefaultRetryClassifier::default();
+        let request = Request::new("https://contoso.com".parse().unwrap(), Method::Get);
+        for (kind, expected) in [
+            (ErrorKind::Io, true),
+            (ErrorKind::TryTimeout, true),
+            (ErrorKind::Timeout, false),
+            (ErrorKind::DataConversion, false),
+        ] {
+            assert_eq!(
+                classifier.should_retry(&FailedAttempt::error(&request, &kind)),
+                expected,
+                "{kind}"
+            );
+        }
+    }
+
+    #[test]
+    fn default_classifier_can_skip_non_idempotent_requests() {
+        let classifier = DefaultRetryClassifier::default().retry_non_idempotent(false);
+        assert!(!classify(
+            &classifier,
+            Method::Post,
+            StatusCode::ServiceUnavailable
+        ));
+        assert!(!classify(
+            &classifier,
+            Method::Patch,
+            StatusCode::ServiceUnavailable
+        ));
+        assert!(classify(
+            &classifier,
+            Method::Put,
+            StatusCode::ServiceUnavailable
+        ));
+        assert!(classify(
+            &classifier,
+            Method::Delete,
+            StatusCode::ServiceUnavailable
+        ));
+    }
+}
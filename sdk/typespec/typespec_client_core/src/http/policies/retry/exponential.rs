@@ -2,11 +2,12 @@
 // Licensed under the MIT License.
 
 use super::RetryPolicy;
+use crate::http::Jitter;
 use std::time::Duration;
 
 /// Retry policy with exponential back-off.
 ///
-/// Retry policy with exponential back-off (with an added random delay up to 256 ms). Each retry
+/// Retry policy with exponential back-off (with an added random delay, by default up to 256 ms). Each retry
 /// will happen at least after an exponential wait time. So if x is the first retry wait, the
 /// second will be x*2, the third x*4 and so on. The policy will retry until the maximum number of
 /// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
@@ -17,6 +18,7 @@ pub struct ExponentialRetryPolicy {
     max_retries: u32,
     max_elapsed: Duration,
     max_delay: Duration,
+    jitter: Jitter,
 }
 
 impl ExponentialRetryPolicy {
@@ -25,12 +27,14 @@ impl ExponentialRetryPolicy {
         max_retries: u32,
         max_elapsed: Duration,
         max_delay: Duration,
+        jitter: Jitter,
     ) -> Self {
         Self {
             initial_delay: initial_delay.max(Duration::from_millis(1)),
             max_retries,
             max_elapsed,
             max_delay: max_delay.max(Duration::from_secs(1)),
+            jitter,
         }
     }
 }
@@ -41,10 +45,11 @@ impl RetryPolicy for ExponentialRetryPolicy {
     }
 
     fn sleep_duration(&self, retry_count: u32) -> Duration {
-        let sleep_ms = self.initial_delay.as_millis() as u64 * 2u64.pow(retry_count)
-            + u64::from(rand::random::<u8>());
-        let sleep_ms = sleep_ms.min(self.max_delay.as_millis().try_into().unwrap_or(u64::MAX));
-        Duration::from_millis(sleep_ms)
+        let delay = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(retry_count))
+            .min(self.max_delay);
+        self.jitter.apply(delay).min(self.max_delay)
     }
 }
 
@@ -61,6 +66,7 @@ mod tests {
             options.max_retries,
             options.max_total_elapsed,
             options.max_delay,
+            options.jitter,
         );
 
         let mut elapsed_time = Duration::from_secs(0);
@@ -92,4 +98,41 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        for (jitter, min, max) in [
+            (Jitter::None, 800, 800),
+            (Jitter::Additive, 800, 800 + 255),
+            (Jitter::Full, 0, 800),
+            (Jitter::Equal, 400, 800),
+        ] {
+            let policy = ExponentialRetryPolicy::new(
+                Duration::from_millis(200),
+                8,
+                Duration::from_secs(60),
+                Duration::from_secs(30),
+                jitter,
+            );
+            for _ in 0..100 {
+                let delay = policy.sleep_duration(2).as_millis();
+                assert!(
+                    (min..=max).contains(&delay),
+                    "{jitter:?}: {delay}ms not in {min}..={max}ms"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn jitter_does_not_exceed_max_delay() {
+        let policy = ExponentialRetryPolicy::new(
+            Duration::from_secs(1),
+            100,
+            Duration::MAX,
+            Duration::from_secs(5),
+            Jitter::Additive,
+        );
+        assert_eq!(policy.sleep_duration(64), Duration::from_secs(5));
+    }
 }
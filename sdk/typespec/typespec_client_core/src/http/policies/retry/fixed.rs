@@ -1,11 +1,12 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+use crate::http::Jitter;
 use std::time::Duration;
 
 /// Retry policy with a fixed back-off.
 ///
-/// Retry policy with fixed back-off (with an added random delay up to 256 ms). Each retry will
+/// Retry policy with fixed back-off (with an added random delay, by default up to 256 ms). Each retry will
 /// happen at least after the same, configured sleep time. The policy will retry until the maximum number of
 /// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
 /// wait time is not precise.
@@ -14,14 +15,21 @@ pub struct FixedRetryPolicy {
     delay: Duration,
     max_retries: u32,
     max_elapsed: Duration,
+    jitter: Jitter,
 }
 
 impl FixedRetryPolicy {
-    pub(crate) fn new(delay: Duration, max_retries: u32, max_elapsed: Duration) -> Self {
+    pub(crate) fn new(
+        delay: Duration,
+        max_retries: u32,
+        max_elapsed: Duration,
+        jitter: Jitter,
+    ) -> Self {
         Self {
             delay: delay.max(Duration::from_millis(10)),
             max_retries,
             max_elapsed,
+            jitter,
         }
     }
 }
@@ -32,7 +40,6 @@ impl super::RetryPolicy for FixedRetryPolicy {
     }
 
     fn sleep_duration(&self, _retry_count: u32) -> Duration {
-        let sleep_ms = self.delay.as_millis() as u64 + u64::from(rand::random::<u8>());
-        Duration::from_millis(sleep_ms)
+        self.jitter.apply(self.delay)
     }
 }
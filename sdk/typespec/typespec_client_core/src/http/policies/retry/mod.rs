@@ -1,10 +1,13 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+mod classifier;
 mod exponential;
 mod fixed;
 mod none;
 
+pub(crate) use classifier::ClassifiedRetryPolicy;
+pub use classifier::{DefaultRetryClassifier, FailedAttempt, RetryClassifier};
 pub use exponential::*;
 pub use fixed::*;
 pub use none::*;
@@ -13,6 +16,7 @@ use crate::{
     date::{self, OffsetDateTime},
     error::HttpError,
     http::{
+        cancellation::run_until_cancelled,
         headers::{Headers, RETRY_AFTER, RETRY_AFTER_MS, X_MS_RETRY_AFTER_MS},
         policies::{Policy, PolicyResult},
         Context, Request, StatusCode,
@@ -68,6 +72,12 @@ pub fn get_retry_after(headers: &Headers, now: DateTimeFn) -> Option<Duration> {
         })
 }
 
+/// The number of the current attempt to send a request, starting at 1.
+///
+/// Retry policies add this to the [`Context`] passed to the policies that follow them, so those policies can tell retries apart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryAttempt(pub u32);
+
 /// A retry policy.
 ///
 /// In the simple form, the policies need only differ in how
@@ -84,6 +94,12 @@ pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
     fn is_expired(&self, duration_since_start: Duration, retry_count: u32) -> bool;
     /// Determine how long before the next retry should be attempted.
     fn sleep_duration(&self, retry_count: u32) -> Duration;
+    /// Determine if the request should be retried after `attempt` failed.
+    ///
+    /// The default uses the [`DefaultRetryClassifier`].
+    fn should_retry(&self, attempt: &FailedAttempt<'_>) -> bool {
+        DefaultRetryClassifier::default().should_retry(attempt)
+    }
     /// A Future that will wait until the request can be retried.
     /// `error` is the [`Error`] value the led to a retry attempt.
     /// `retry_after` is the duration to wait before retrying, if provided by the server response.
@@ -97,18 +113,6 @@ pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
     }
 }
 
-/// The status codes where a retry should be attempted.
-///
-/// On all other 4xx and 5xx status codes no retry is attempted.
-const RETRY_STATUSES: &[StatusCode] = &[
-    StatusCode::RequestTimeout,
-    StatusCode::TooManyRequests,
-    StatusCode::InternalServerError,
-    StatusCode::BadGateway,
-    StatusCode::ServiceUnavailable,
-    StatusCode::GatewayTimeout,
-];
-
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl<T> Policy for T
@@ -131,7 +135,8 @@ where
                     "failed to reset body stream before retrying request",
                 )?;
             }
-            let result = next[0].send(ctx, request, &next[1..]).await;
+            let attempt_ctx = Context::with_context(ctx).with_value(RetryAttempt(retry_count + 1));
+            let result = next[0].send(&attempt_ctx, request, &next[1..]).await;
             // only start keeping track of time after the first request is made
             let start = start.get_or_insert_with(OffsetDateTime::now_utc);
             let (last_error, retry_after) = match result {
@@ -146,13 +151,15 @@ where
                 Ok(response) => {
                     // Error status code
                     let status = response.status();
+                    let raw_status = response.raw_status();
+                    let headers = response.headers().clone();
 
                     // For a 429 response (TooManyRequests) or 503 (ServiceUnavailable),
                     // use any "retry-after" headers returned by the server to determine how long to wait before retrying.
                     // https://learn.microsoft.com/en-us/azure/architecture/best-practices/retry-service-specific#retry-usage-guidance
                     let retry_after = match status {
                         StatusCode::TooManyRequests | StatusCode::ServiceUnavailable => {
-                            get_retry_after(response.headers(), OffsetDateTime::now_utc)
+                            get_retry_after(&headers, OffsetDateTime::now_utc)
                         }
                         _ => None,
                     };
@@ -164,12 +171,18 @@ where
                         http_error.error_code().map(std::borrow::ToOwned::to_owned),
                     );
 
-                    if !RETRY_STATUSES.contains(&status) {
+                    if !self.should_retry(&FailedAttempt::response(
+                        request,
+                        status,
+                        raw_status,
+                        &headers,
+                        &error_kind,
+                    )) {
                         debug!(
                             "server returned error status which will not be retried: {}",
                             status
                         );
-                        // Server didn't return a status we retry on so return early
+                        // The classifier didn't consider the status retriable so return early
                         let error = Error::full(
                             error_kind,
                             http_error,
@@ -186,18 +199,16 @@ where
                     (Error::new(error_kind, http_error), retry_after)
                 }
                 Err(error) => {
-                    if error.kind() == &ErrorKind::Io {
+                    if self.should_retry(&FailedAttempt::error(request, error.kind())) {
                         debug!(
-                            "io error occurred when making request which will be retried: {}",
+                            "error occurred when making request which will be retried: {}",
                             error
                         );
-                        // IO error so no Retry-After headers - leave the retry period up to the policy
+                        // No response so no Retry-After headers - leave the retry period up to the policy
                         let retry_after = None;
                         (error, retry_after)
                     } else {
-                        return Err(
-                            error.context("non-io error occurred which will not be retried")
-                        );
+                        return Err(error.context("error occurred which will not be retried"));
                     }
                 }
             };
@@ -211,7 +222,7 @@ where
             }
             retry_count += 1;
 
-            self.wait(&last_error, retry_count, retry_after).await;
+            run_until_cancelled(ctx, self.wait(&last_error, retry_count, retry_after)).await?;
         }
     }
 }
@@ -0,0 +1,263 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    date::OffsetDateTime,
+    http::{
+        policies::{Policy, PolicyResult},
+        Context, Request,
+    },
+    sleep::sleep,
+};
+use futures::future::{self, Either};
+use std::{sync::Arc, time::Duration};
+use typespec::error::{Error, ErrorKind};
+
+/// The time by which a client method call, including any retries, must complete.
+///
+/// Add a `Deadline` to the [`Context`] passed to a client method to bound how long the call may take.
+/// Because it is an absolute point in time, the same `Context` can be used for several calls
+/// (for example, every page of a [`Pager`](crate::http::Pager)) that must all complete by the deadline.
+/// Calls that do not complete in time fail with [`ErrorKind::Timeout`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use typespec_client_core::http::{policies::Deadline, Context};
+/// let context = Context::new().with_value(Deadline::after(Duration::from_secs(30)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Deadline(OffsetDateTime);
+
+impl Deadline {
+    /// Creates a `Deadline` at the given point in time.
+    pub fn at(deadline: OffsetDateTime) -> Self {
+        Self(deadline)
+    }
+
+    /// Creates a `Deadline` that is `timeout` from now.
+    pub fn after(timeout: Duration) -> Self {
+        Self(
+            OffsetDateTime::now_utc()
+                .saturating_add(timeout.try_into().unwrap_or(time::Duration::MAX)),
+        )
+    }
+
+    /// Returns the time remaining until the deadline, or [`Duration::ZERO`] if it has passed.
+    pub fn remaining(&self) -> Duration {
+        (self.0 - OffsetDateTime::now_utc())
+            .try_into()
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+/// Runs `future`, failing with an error of the given `kind` if it does not complete within `timeout`.
+///
+/// The timer is dropped as soon as `future` completes, which cancels it.
+async fn with_timeout<F>(
+    future: F,
+    timeout: Duration,
+    kind: ErrorKind,
+    message: String,
+) -> PolicyResult
+where
+    F: std::future::Future<Output = PolicyResult>,
+{
+    if timeout.is_zero() {
+        return Err(Error::message(kind, message));
+    }
+    futures::pin_mut!(future);
+    let timer = sleep(timeout);
+    futures::pin_mut!(timer);
+    match future::select(future, timer).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(Error::message(kind, message)),
+    }
+}
+
+/// [`Policy`] that fails a client method call with [`ErrorKind::Timeout`] if it does not complete by the [`Deadline`] in the [`Context`].
+///
+/// The deadline covers every attempt and the delays between them, so this policy runs before the retry policy.
+/// If the `Context` has no `Deadline`, the call is not bounded.
+#[derive(Clone, Debug, Default)]
+pub struct TimeoutPolicy {}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for TimeoutPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let Some(deadline) = ctx.value::<Deadline>().copied() else {
+            return next[0].send(ctx, request, &next[1..]).await;
+        };
+
+        let message = format!("request did not complete by its deadline of {}", deadline.0);
+        with_timeout(
+            next[0].send(ctx, request, &next[1..]),
+            deadline.remaining(),
+            ErrorKind::Timeout,
+            message,
+        )
+        .await
+    }
+}
+
+/// [`Policy`] that fails a single attempt to send a request with [`ErrorKind::TryTimeout`] if no response is received within a timeout.
+///
+/// This policy runs after the retry policy, which retries attempts that time out.
+/// Only the time until the response headers are received is bounded; reading the response body is not.
+#[derive(Clone, Debug)]
+pub struct TryTimeoutPolicy {
+    timeout: Duration,
+}
+
+impl TryTimeoutPolicy {
+    /// Creates a new `TryTimeoutPolicy` that allows each attempt `timeout` to receive a response.
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for TryTimeoutPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let message = format!("no response was received within {:?}", self.timeout);
+        with_timeout(
+            next[0].send(ctx, request, &next[1..]),
+            self.timeout,
+            ErrorKind::TryTimeout,
+            message,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        headers::Headers, ClientOptions, FixedRetryOptions, Jitter, Method, Pipeline, Response,
+        RetryOptions, StatusCode, TransportOptions,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Never responds to the first `hangs` attempts, then responds with `200 OK`.
+    #[derive(Debug)]
+    struct HangingTransport {
+        hangs: usize,
+        attempts: AtomicUsize,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl Policy for HangingTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < self.hangs {
+                future::pending::<()>().await;
+            }
+            Ok(Response::from_bytes(StatusCode::Ok, Headers::new(), ""))
+        }
+    }
+
+    fn pipeline(
+        hangs: usize,
+        max_retries: u32,
+        try_timeout: Option<Duration>,
+    ) -> (Pipeline, Arc<HangingTransport>) {
+        let transport = Arc::new(HangingTransport {
+            hangs,
+            attempts: AtomicUsize::new(0),
+        });
+        let mut options =
+            ClientOptions::new(TransportOptions::new_custom_policy(transport.clone()));
+        options.set_retry(RetryOptions::fixed(
+            FixedRetryOptions::default()
+                .delay(Duration::from_millis(1))
+                .jitter(Jitter::None)
+                .max_retries(max_retries),
+        ));
+        if let Some(try_timeout) = try_timeout {
+            options.set_try_timeout(try_timeout);
+        }
+        (Pipeline::new(options, Vec::new(), Vec::new()), transport)
+    }
+
+    async fn send(pipeline: &Pipeline, ctx: &Context<'_>) -> PolicyResult {
+        let mut request = Request::new("https://contoso.com".parse().unwrap(), Method::Get);
+        pipeline.send(ctx, &mut request).await
+    }
+
+    #[tokio::test]
+    async fn retries_attempts_that_time_out() {
+        let (pipeline, transport) = pipeline(2, 2, Some(Duration::from_millis(20)));
+        let response = send(&pipeline, &Context::new()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::Ok);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn fails_with_try_timeout_once_retries_are_exhausted() {
+        let (pipeline, transport) = pipeline(usize::MAX, 2, Some(Duration::from_millis(20)));
+        let err = send(&pipeline, &Context::new()).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::TryTimeout);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn fails_with_timeout_at_deadline() {
+        let (pipeline, transport) = pipeline(usize::MAX, 2, None);
+        let ctx = Context::new().with_value(Deadline::after(Duration::from_millis(50)));
+        let err = send(&pipeline, &ctx).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn deadline_bounds_retries() {
+        // Attempts time out and are retried until the deadline passes, well before retries are exhausted.
+        let (pipeline, transport) = pipeline(usize::MAX, 1000, Some(Duration::from_millis(10)));
+        let ctx = Context::new().with_value(Deadline::after(Duration::from_millis(100)));
+        let err = send(&pipeline, &ctx).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        let attempts = transport.attempts.load(Ordering::SeqCst);
+        assert!(attempts > 1 && attempts < 1000, "{attempts} attempts");
+    }
+
+    #[tokio::test]
+    async fn passed_deadline_fails_without_sending() {
+        let (pipeline, transport) = pipeline(0, 2, None);
+        let ctx = Context::new().with_value(Deadline::at(
+            OffsetDateTime::now_utc() - time::Duration::seconds(1),
+        ));
+        let err = send(&pipeline, &ctx).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn deadline_remaining() {
+        assert_eq!(
+            Deadline::at(OffsetDateTime::now_utc() - time::Duration::seconds(1)).remaining(),
+            Duration::ZERO
+        );
+        let remaining = Deadline::after(Duration::from_secs(60)).remaining();
+        assert!(remaining > Duration::from_secs(59) && remaining <= Duration::from_secs(60));
+        assert!(Deadline::after(Duration::MAX).remaining() > Duration::from_secs(60));
+    }
+}
@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::http::{
+    cancellation::run_until_cancelled,
     options::TransportOptions,
     policies::{Policy, PolicyResult},
     Context, Request,
@@ -36,6 +37,6 @@ impl Policy for TransportPolicy {
         debug!("the following request will be passed to the transport policy: {request:#?}");
         let response = { self.transport_options.send(ctx, request) };
 
-        response.await
+        run_until_cancelled(ctx, response).await?
     }
 }
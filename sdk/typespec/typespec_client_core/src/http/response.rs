@@ -53,6 +53,26 @@ impl Model for serde_json::Value {
     }
 }
 
+/// Converts a status code returned by a service to a [`StatusCode`].
+///
+/// Statuses that [`StatusCode`] cannot represent, such as Azure Cosmos DB's `449 Retry With`, are converted to the
+/// standard status of the same class, such as `400 Bad Request`. Keep the actual status code with [`Response::with_raw_status()`].
+///
+/// Fails if `raw_status` is not between 100 and 599.
+pub fn status_from_raw(raw_status: u16) -> crate::Result<StatusCode> {
+    StatusCode::try_from(raw_status).or_else(|_| match raw_status / 100 {
+        1 => Ok(StatusCode::Continue),
+        2 => Ok(StatusCode::Ok),
+        3 => Ok(StatusCode::MultipleChoice),
+        4 => Ok(StatusCode::BadRequest),
+        5 => Ok(StatusCode::InternalServerError),
+        _ => Err(typespec::Error::with_message(
+            ErrorKind::DataConversion,
+            || format!("invalid status code {raw_status}"),
+        )),
+    })
+}
+
 /// An HTTP response.
 ///
 /// The type parameter `T` is a marker type that indicates what the caller should expect to be able to deserialize the body into.
@@ -63,6 +83,7 @@ impl Model for serde_json::Value {
 /// However, because the type `T` is just a marker type, the user can also deserialize the body into a different type by calling [`Response::deserialize_body_into`].
 pub struct Response<T = ()> {
     status: StatusCode,
+    raw_status: u16,
     headers: Headers,
     body: ResponseBody,
     phantom: PhantomData<T>,
@@ -73,6 +94,7 @@ impl<T> Response<T> {
     pub fn new(status: StatusCode, headers: Headers, stream: PinnedStream) -> Self {
         Self {
             status,
+            raw_status: status.into(),
             headers,
             body: ResponseBody::new(stream),
             phantom: PhantomData,
@@ -83,6 +105,7 @@ impl<T> Response<T> {
     pub fn from_bytes(status: StatusCode, headers: Headers, bytes: impl Into<Bytes>) -> Self {
         Self {
             status,
+            raw_status: status.into(),
             headers,
             body: ResponseBody::from_bytes(bytes),
             phantom: PhantomData,
@@ -94,6 +117,21 @@ impl<T> Response<T> {
         self.status
     }
 
+    /// Sets the status code the service actually returned, when [`StatusCode`] cannot represent it.
+    ///
+    /// Services sometimes return non-standard statuses, such as Azure Cosmos DB's `449 Retry With`.
+    /// HTTP clients should set [`status()`](Response::status()) to the standard status of the same class, such as `400 Bad Request`,
+    /// and the actual status code here.
+    pub fn with_raw_status(mut self, raw_status: u16) -> Self {
+        self.raw_status = raw_status;
+        self
+    }
+
+    /// Get the status code the service returned, even if [`StatusCode`] cannot represent it.
+    pub fn raw_status(&self) -> u16 {
+        self.raw_status
+    }
+
     /// Get the headers from the response.
     pub fn headers(&self) -> &Headers {
         &self.headers
@@ -158,6 +196,7 @@ impl Response<()> {
     pub(crate) fn with_default_deserialize_type<T>(self) -> Response<T> {
         Response {
             status: self.status,
+            raw_status: self.raw_status,
             headers: self.headers,
             body: self.body,
             phantom: PhantomData,
@@ -214,6 +253,7 @@ impl<T> fmt::Debug for Response<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Response")
             .field("status", &self.status)
+            .field("raw_status", &self.raw_status)
             // TODO: Sanitize headers and emit body as "(body)".
             .finish_non_exhaustive()
     }
@@ -299,9 +339,27 @@ impl fmt::Debug for ResponseBody {
 #[cfg(test)]
 mod tests {
     use crate::http::headers::Headers;
-    use crate::http::{response::ResponseBody, Model, Response};
+    use crate::http::{
+        response::{status_from_raw, ResponseBody},
+        Model, Response, StatusCode,
+    };
     use typespec::error::ErrorKind;
 
+    #[test]
+    fn converts_raw_status() {
+        assert_eq!(status_from_raw(404).unwrap(), StatusCode::NotFound);
+        assert_eq!(status_from_raw(299).unwrap(), StatusCode::Ok);
+        assert_eq!(status_from_raw(449).unwrap(), StatusCode::BadRequest);
+        assert_eq!(
+            status_from_raw(599).unwrap(),
+            StatusCode::InternalServerError
+        );
+        assert_eq!(
+            status_from_raw(999).unwrap_err().kind(),
+            &ErrorKind::DataConversion
+        );
+    }
+
     #[tokio::test]
     pub async fn body_type_controls_consumption_of_response_body() {
         pub struct LazyBody;
@@ -17,6 +17,8 @@ pub mod json;
 pub mod parsing;
 pub mod sleep;
 pub mod stream;
+#[cfg(any(test, feature = "test"))]
+pub mod test;
 #[cfg(feature = "xml")]
 pub mod xml;
 
@@ -4,11 +4,8 @@
 use futures::Future;
 use std::{
     pin::Pin,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-    task::{Context, Poll},
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
     thread,
     time::Duration,
 };
@@ -17,40 +14,105 @@ use std::{
 ///
 /// Uses a simple thread based implementation for sleep. A more efficient
 /// implementation is available by using the `tokio_sleep` crate feature.
+///
+/// Dropping the future before it resolves stops the thread, so timers that are
+/// cancelled, such as timeouts for requests that completed, do not accumulate.
 pub fn sleep(duration: Duration) -> Sleep {
     Sleep {
-        signal: None,
+        timer: None,
         duration,
     }
 }
 
 #[derive(Debug)]
 pub struct Sleep {
-    signal: Option<Arc<AtomicBool>>,
+    timer: Option<Arc<Timer>>,
     duration: Duration,
 }
 
+/// State shared between a [`Sleep`] and the thread waiting for it to elapse.
+#[derive(Debug, Default)]
+struct Timer {
+    state: Mutex<TimerState>,
+    cancelled: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct TimerState {
+    elapsed: bool,
+    cancelled: bool,
+    waker: Option<Waker>,
+}
+
+impl Timer {
+    fn lock(&self) -> MutexGuard<'_, TimerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
 impl Future for Sleep {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        if let Some(signal) = &self.signal {
-            if signal.load(Ordering::Acquire) {
-                Poll::Ready(())
-            } else {
-                Poll::Pending
+        let this = self.get_mut();
+        if let Some(timer) = &this.timer {
+            let mut state = timer.lock();
+            if state.elapsed {
+                return Poll::Ready(());
+            }
+            state.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let timer = Arc::new(Timer::default());
+        timer.lock().waker = Some(cx.waker().clone());
+        this.timer = Some(timer.clone());
+        let duration = this.duration;
+        thread::spawn(move || {
+            let (mut state, _) = timer
+                .cancelled
+                .wait_timeout_while(timer.lock(), duration, |state| !state.cancelled)
+                .unwrap_or_else(PoisonError::into_inner);
+            if !state.cancelled {
+                state.elapsed = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
             }
-        } else {
-            let signal = Arc::new(AtomicBool::new(false));
-            let waker = cx.waker().clone();
-            let duration = self.duration;
-            self.get_mut().signal = Some(signal.clone());
-            thread::spawn(move || {
-                thread::sleep(duration);
-                signal.store(true, Ordering::Release);
-                waker.wake();
-            });
-            Poll::Pending
+        });
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(timer) = &self.timer {
+            timer.lock().cancelled = true;
+            timer.cancelled.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn dropping_stops_thread() {
+        let mut timer = sleep(Duration::from_secs(60));
+        assert!(futures::poll!(&mut timer).is_pending());
+        let shared = Arc::downgrade(timer.timer.as_ref().unwrap());
+        drop(timer);
+
+        // The thread releases the shared state when it exits.
+        let start = Instant::now();
+        while shared.upgrade().is_some() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "thread still running"
+            );
+            thread::sleep(Duration::from_millis(1));
         }
     }
 }
@@ -0,0 +1,36 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Shared utilities for testing client libraries built on `typespec_client_core`.
+
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+/// Collects everything written by a `tracing` subscriber.
+///
+/// Pass a clone to the subscriber's writer, e.g. `tracing_subscriber::fmt().with_writer(move || writer.clone())`,
+/// then read what was logged with [`TracingOutput::contents()`].
+#[derive(Clone, Debug, Default)]
+pub struct TracingOutput(Arc<Mutex<Vec<u8>>>);
+
+impl TracingOutput {
+    /// Gets everything written so far.
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap_or_else(|err| err.into_inner())).into_owned()
+    }
+}
+
+impl Write for TracingOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}